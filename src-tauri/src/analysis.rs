@@ -0,0 +1,274 @@
+use crate::cfg;
+use crate::cleanup::{add_line_numbers, remove_comments};
+use crate::descriptors::{self, Symbols, Table};
+use crate::keywords::{self, Token};
+use crate::optimize;
+use crate::quads;
+use crate::rpn::{self, Rpn};
+use crate::semantic;
+use crate::syntax;
+use crate::tree::{self, Node};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+pub struct Analysis {
+    /// Comment-stripped program with line numbers
+    pub output: String,
+    pub table: Option<Table>,
+    /// Syntax tree, present when parsing succeeded
+    pub tree: Option<Node>,
+    /// Declarations with their scopes and types
+    pub symbols: String,
+    /// Reverse Polish notation listing, empty when translation failed
+    pub rpn: String,
+    /// Quadruples as a table
+    pub quads: String,
+    /// Quadruples after `optimize`
+    pub optimized: String,
+    /// Optimisation log followed by a listing diff
+    pub diff: String,
+    /// Basic blocks of the optimised code with their successors
+    pub blocks: String,
+    /// Control flow graph of the unoptimised code as SVG
+    pub cfg: String,
+    pub error: String,
+}
+
+/// What gets written to disk by `save_as`
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub enum Output {
+    /// The program as edited
+    Source,
+    /// Listing after `remove_comments`
+    Cleaned,
+    Pseudocode,
+    /// Syntax tree as Graphviz DOT
+    TreeDot,
+    /// Syntax tree as indented text
+    TreeText,
+    /// Reverse Polish notation listing
+    Rpn,
+    /// Quadruple table
+    Quads,
+    /// Quadruples after optimisation
+    Optimized,
+    /// Control flow graph as Graphviz DOT
+    CfgDot,
+    /// Control flow graph as SVG
+    CfgSvg,
+}
+
+/// Runs the whole pipeline: comment removal, tokenisation, descriptor tables
+/// syntax and semantic analysis and translation to reverse Polish notation
+pub fn analyze(program: String) -> Analysis {
+    analyze_with_tokens(program).0
+}
+
+/// Same as `analyze`, also handing back the tokens for later stages.
+/// The tokens are empty when lexing failed
+pub fn analyze_with_tokens(program: String) -> (Analysis, Vec<Token>) {
+    let filtered = remove_comments(program);
+    let mut analysis = Analysis {
+        output: add_line_numbers(filtered.clone()),
+        table: None,
+        tree: None,
+        symbols: String::new(),
+        rpn: String::new(),
+        quads: String::new(),
+        optimized: String::new(),
+        diff: String::new(),
+        blocks: String::new(),
+        cfg: String::new(),
+        error: String::new(),
+    };
+    let tokens = match keywords::count_tokens(filtered) {
+        Ok(tokens) => tokens,
+        Err(e) => {
+            analysis.error = e.to_string();
+            return (analysis, vec![]);
+        }
+    };
+    analysis.table = descriptors::create_descriptors(tokens.clone());
+    let program = match syntax::parse(tokens.clone()) {
+        Ok(program) => program,
+        Err(e) => {
+            analysis.error = e.to_string();
+            return (analysis, tokens);
+        }
+    };
+    analysis.tree = Some(tree::from_program(&program));
+    let report = semantic::check(&program);
+    analysis.symbols = report.symbols;
+    let mut errors: Vec<String> = report.diagnostics.iter().map(|d| d.to_string()).collect();
+    let symbols = Symbols::new(&tokens);
+    match rpn::translate(&program, &symbols) {
+        Ok(rpn) => analysis.rpn = format!("{}\n{}", rpn.to_text(), rpn.listing()),
+        Err(e) => errors.push(e.to_string()),
+    }
+    match quads::translate(&program, &symbols) {
+        Ok(code) => {
+            analysis.quads = code.to_table();
+            analysis.cfg = cfg::to_svg(&code);
+            let optimized = optimize::optimize(&code);
+            analysis.optimized = optimized.code.to_table();
+            analysis.diff = format!(
+                "\n{}\n{}",
+                optimized.log.join("\n"),
+                optimize::diff(
+                    &optimize::listing(&code),
+                    &optimize::listing(&optimized.code)
+                )
+            );
+            analysis.blocks = cfg::to_text(&optimized.code);
+        }
+        Err(e) => errors.push(e.to_string()),
+    }
+    analysis.error = errors.join("\n");
+    (analysis, tokens)
+}
+
+/// Comment removal and tokenisation only
+pub fn tokenize(program: String) -> Result<Vec<Token>, String> {
+    keywords::count_tokens(remove_comments(program)).map_err(|e| e.to_string())
+}
+
+/// Front end up to reverse Polish notation, for the stack machine
+pub fn translate(program: String) -> Result<(Rpn, Symbols), String> {
+    let tokens = tokenize(program)?;
+    let tree = syntax::parse(tokens.clone()).map_err(|e| e.to_string())?;
+    let symbols = Symbols::new(&tokens);
+    let rpn = rpn::translate(&tree, &symbols).map_err(|e| e.to_string())?;
+    Ok((rpn, symbols))
+}
+
+/// Front end up to quadruples
+fn lower(program: String) -> Result<quads::Code, String> {
+    let tokens = tokenize(program)?;
+    let tree = syntax::parse(tokens.clone()).map_err(|e| e.to_string())?;
+    quads::translate(&tree, &Symbols::new(&tokens)).map_err(|e| e.to_string())
+}
+
+/// Produces the file contents for `output` from the edited program
+pub fn render(program: String, output: Output) -> Result<String, String> {
+    match output {
+        Output::Source => Ok(program),
+        Output::Cleaned => Ok(remove_comments(program)),
+        Output::Pseudocode => {
+            let tokens = tokenize(program)?;
+            match descriptors::create_descriptors(tokens) {
+                Some(table) => Ok(table.pseudocode),
+                None => Err("Pseudocode could not be generated".to_owned()),
+            }
+        }
+        Output::TreeDot | Output::TreeText => {
+            let analysis = analyze(program);
+            match analysis.tree {
+                Some(node) if output == Output::TreeDot => Ok(tree::to_dot(&node)),
+                Some(node) => Ok(tree::to_text(&node)),
+                None => Err(analysis.error),
+            }
+        }
+        Output::Rpn | Output::Quads | Output::Optimized => {
+            let analysis = analyze(program);
+            let text = match output {
+                Output::Rpn => analysis.rpn,
+                Output::Quads => analysis.quads,
+                _ => analysis.optimized,
+            };
+            if text.is_empty() {
+                Err(analysis.error)
+            } else {
+                Ok(text)
+            }
+        }
+        Output::CfgDot => Ok(cfg::to_dot(&lower(program)?)),
+        Output::CfgSvg => Ok(cfg::to_svg(&lower(program)?)),
+    }
+}
+
+impl Analysis {
+    /// Plain text report used by the command line mode
+    pub fn report(&self) -> String {
+        let mut report = self.output.clone();
+        if let Some(table) = &self.table {
+            report.push_str(&format!("\nдескрипторы\n{}\n", table.descriptors));
+            report.push_str(&format!("\nпсевдокод\n{}\n", table.pseudocode));
+            report.push_str(&format!("\n10 - ключ. слова{}\n", table.keywords));
+            report.push_str(&format!("\n20 - идентификаторы{}\n", table.identifiers));
+            report.push_str(&format!("\n30 - числовые константы{}\n", table.consts));
+            report.push_str(&format!("\n40 - операторы{}\n", table.operators));
+            report.push_str(&format!("\n50 - строковые константы{}\n", table.strings));
+            report.push_str(&format!("\n60 - разделители{}\n", table.separators));
+        }
+        if !self.symbols.is_empty() {
+            report.push_str(&format!("\nобласти видимости{}\n", self.symbols));
+        }
+        if !self.rpn.is_empty() {
+            report.push_str(&format!("\nПОЛИЗ\n{}\n", self.rpn));
+        }
+        if !self.quads.is_empty() {
+            report.push_str(&format!("\nтетрады\n{}", self.quads));
+        }
+        if !self.optimized.is_empty() {
+            report.push_str(&format!("\nоптимизация{}\n", self.diff));
+            report.push_str(&format!("\nбазовые блоки{}\n", self.blocks));
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM: &str = "int main() {\n  int x = 1; // one\n  /* two */ x = x + 2;\n}\n";
+
+    #[test]
+    fn analysis_fills_every_stage() {
+        let analysis = analyze(PROGRAM.to_owned());
+        assert_eq!(analysis.error, "");
+        assert_eq!(
+            analysis.output,
+            "0  int main() {\n1  int x = 1; x = x + 2;\n2  }\n"
+        );
+        assert_eq!(analysis.table.unwrap().identifiers, "\n0 main\n1 x");
+        assert!(analysis.tree.is_some());
+        assert!(analysis
+            .rpn
+            .starts_with("id1 const0 = pop id1 id1 const1 + = pop ret\n"));
+        assert!(!analysis.quads.is_empty());
+        assert!(analysis.cfg.starts_with("<svg"));
+    }
+
+    #[test]
+    fn lexer_errors_stop_the_pipeline() {
+        let (analysis, tokens) = analyze_with_tokens("int a = 1;\nab@".to_owned());
+        assert_eq!(analysis.error, "Identifier error at 1:3 (@)");
+        assert!(analysis.table.is_none() && analysis.tree.is_none());
+        assert!(tokens.is_empty());
+    }
+
+    #[test]
+    fn renders_each_output() {
+        let render = |output| render(PROGRAM.to_owned(), output).unwrap();
+        assert_eq!(render(Output::Source), PROGRAM);
+        assert_eq!(
+            render(Output::Cleaned),
+            "int main() {\nint x = 1; x = x + 2;\n}\n"
+        );
+        assert_eq!(
+            render(Output::Pseudocode),
+            "int id0 ( ) { int id1 = const0 ; id1 = id1 + const1 ; } "
+        );
+        assert!(render(Output::TreeText).starts_with("program\n  function int main\n"));
+        assert!(render(Output::TreeDot).starts_with("digraph ast {"));
+        assert!(render(Output::Quads).contains("| t1 = x + 2\n"));
+        assert!(render(Output::CfgDot).starts_with("digraph"));
+    }
+
+    #[test]
+    fn render_reports_why_an_output_is_missing() {
+        let error = render("x = 1;".to_owned(), Output::Rpn).unwrap_err();
+        assert_eq!(error, "Syntax error at 0:1: expected type, found x");
+    }
+}
@@ -0,0 +1,753 @@
+use crate::diagram::Diagram;
+use crate::grammar::format_table;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Nothing but blank lines, comments and malformed lines
+    Empty,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Empty => f.write_str("Automaton has no transitions"),
+        }
+    }
+}
+
+/// Problem found while reading or checking a description, `line` is 1-based, 0 when it has none
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub line: usize,
+    pub message: String,
+}
+
+impl Issue {
+    fn new(line: usize, message: String) -> Issue {
+        Issue { line, message }
+    }
+}
+
+impl std::fmt::Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.line == 0 {
+            f.write_str(&self.message)
+        } else {
+            write!(f, "line {}: {}", self.line, self.message)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Item {
+    Char(char),
+    /// Both ends included
+    Range(char, char),
+    Any,
+}
+
+/// Set of characters written the way the transition files do: `a-zA-Z0-9_`,
+/// `\s\n\t`, `\{`, `'l'` or `any`
+#[derive(Debug, Clone)]
+pub struct CharClass {
+    /// As written in the description
+    pub text: String,
+    pub items: Vec<Item>,
+}
+
+/// Characters the checks go through: tab, newline and printable ASCII
+pub fn alphabet() -> Vec<char> {
+    ['\t', '\n'].into_iter().chain(' '..='~').collect()
+}
+
+/// How a single character is written inside a class
+pub fn escape(c: char) -> String {
+    match c {
+        ' ' => "\\s".to_owned(),
+        '\n' => "\\n".to_owned(),
+        '\t' => "\\t".to_owned(),
+        '\\' => "\\\\".to_owned(),
+        '{' => "\\{".to_owned(),
+        c => c.to_string(),
+    }
+}
+
+impl CharClass {
+    pub fn parse(text: &str) -> Result<CharClass, String> {
+        if text.is_empty() {
+            return Err("empty character class".to_owned());
+        }
+        if text == "any" {
+            return Ok(CharClass {
+                text: text.to_owned(),
+                items: vec![Item::Any],
+            });
+        }
+        if let ['\'', c, '\''] = text.chars().collect::<Vec<char>>()[..] {
+            return Ok(CharClass {
+                text: text.to_owned(),
+                items: vec![Item::Char(c)],
+            });
+        }
+        Self::parse_set(text)
+    }
+
+    /// Characters and ranges only, without the `any` and `'x'` forms. This is
+    /// what goes between the brackets of a regular expression class
+    pub fn parse_set(text: &str) -> Result<CharClass, String> {
+        if text.is_empty() {
+            return Err("empty character class".to_owned());
+        }
+        let chars: Vec<char> = text.chars().collect();
+
+        // Unescape first so `\s` and friends can't start a range
+        let mut plain = vec![];
+        let mut iter = chars.into_iter();
+        while let Some(c) = iter.next() {
+            if c != '\\' {
+                plain.push((c, false));
+                continue;
+            }
+            let escaped = match iter.next() {
+                Some('s') => ' ',
+                Some('n') => '\n',
+                Some('t') => '\t',
+                Some(c) => c,
+                None => return Err("'\\' at the end of a character class".to_owned()),
+            };
+            plain.push((escaped, true));
+        }
+
+        let mut items = vec![];
+        let mut idx = 0;
+        while idx < plain.len() {
+            let (c, escaped) = plain[idx];
+            match (plain.get(idx + 1), plain.get(idx + 2)) {
+                (Some(('-', false)), Some(&(end, false)))
+                    if !escaped && c.is_ascii_alphanumeric() && end.is_ascii_alphanumeric() =>
+                {
+                    if end < c {
+                        return Err(format!("empty range {}-{}", c, end));
+                    }
+                    items.push(Item::Range(c, end));
+                    idx += 3;
+                }
+                _ => {
+                    items.push(Item::Char(c));
+                    idx += 1;
+                }
+            }
+        }
+        Ok(CharClass {
+            text: text.to_owned(),
+            items,
+        })
+    }
+
+    /// Shortest text for `chars`, consecutive letters and digits become ranges
+    pub fn from_chars(chars: &[char]) -> CharClass {
+        let mut chars = chars.to_vec();
+        chars.sort();
+        chars.dedup();
+        if chars == alphabet() {
+            return CharClass {
+                text: "any".to_owned(),
+                items: vec![Item::Any],
+            };
+        }
+        let mut items = vec![];
+        let mut text = String::new();
+        let mut idx = 0;
+        while idx < chars.len() {
+            let first = chars[idx];
+            let mut last = first;
+            while idx + 1 < chars.len()
+                && chars[idx + 1] as u32 == last as u32 + 1
+                && chars[idx + 1].is_ascii_alphanumeric()
+                && first.is_ascii_alphanumeric()
+                && (chars[idx + 1].is_ascii_digit() == first.is_ascii_digit())
+                && (chars[idx + 1].is_ascii_uppercase() == first.is_ascii_uppercase())
+            {
+                last = chars[idx + 1];
+                idx += 1;
+            }
+            if last as u32 >= first as u32 + 2 {
+                items.push(Item::Range(first, last));
+                text.push_str(&format!("{}-{}", first, last));
+            } else {
+                for c in first..=last {
+                    items.push(Item::Char(c));
+                    text.push_str(&escape(c));
+                }
+            }
+            idx += 1;
+        }
+        if text == "any" {
+            // The letters a, n and y, not every character
+            text = "\\any".to_owned();
+        }
+        CharClass { text, items }
+    }
+
+    pub fn contains(&self, c: char) -> bool {
+        self.items.iter().any(|item| match item {
+            Item::Char(x) => *x == c,
+            Item::Range(a, b) => *a <= c && c <= *b,
+            Item::Any => true,
+        })
+    }
+
+    /// Members from `alphabet`
+    pub fn chars(&self) -> Vec<char> {
+        alphabet()
+            .into_iter()
+            .filter(|c| self.contains(*c))
+            .collect()
+    }
+}
+
+/// Characters as a class, used in messages
+pub fn format_chars(chars: &[char]) -> String {
+    CharClass::from_chars(chars).text
+}
+
+#[derive(Debug, Clone)]
+pub struct Transition {
+    pub from: String,
+    pub class: CharClass,
+    pub to: String,
+    /// 1-based line of the description, 0 when it didn't come from one
+    pub line: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Automaton {
+    pub start: String,
+    /// In order of first appearance
+    pub states: Vec<String>,
+    pub accepting: BTreeSet<String>,
+    /// In order of the description, the first one whose class matches is taken
+    pub transitions: Vec<Transition>,
+}
+
+/// Splits a comma separated list of states, commas inside `(...)` or quotes
+/// belong to the name, as in `Separator(',')`
+fn split_states(text: &str) -> Vec<String> {
+    let mut states = vec![String::new()];
+    let mut depth = 0;
+    let mut quoted = false;
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            ',' if depth == 0 && !quoted => {
+                states.push(String::new());
+                continue;
+            }
+            '\\' if quoted => {
+                states.last_mut().unwrap().push(c);
+                if let Some(next) = chars.next() {
+                    states.last_mut().unwrap().push(next);
+                }
+                continue;
+            }
+            '\'' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted && depth > 0 => depth -= 1,
+            _ => {}
+        }
+        states.last_mut().unwrap().push(c);
+    }
+    states.iter().map(|s| s.trim().to_owned()).collect()
+}
+
+/// Reads `State->class->State` lines. The class is everything between the
+/// first and the last `->`, so `Sub->-->Decr` reads as `-`. Blank lines and
+/// `//` comments are skipped, `start: S` and `accept: A, B` override the
+/// defaults: the first state starts and every other state accepts, which is
+/// how `count_tokens` flushes a token from whatever state it stopped in.
+/// Malformed lines are reported and skipped, so are the assumed default and
+/// the dead ends it makes accepting
+pub fn parse(text: &str) -> Result<(Automaton, Vec<Issue>), Error> {
+    let mut issues = vec![];
+    let mut transitions = vec![];
+    let mut start = None;
+    let mut accepting = None;
+    for (idx, raw) in text.lines().enumerate() {
+        let line = idx + 1;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() || trimmed.starts_with("//") {
+            continue;
+        }
+        if let Some(state) = trimmed.strip_prefix("start:") {
+            start = Some((state.trim().to_owned(), line));
+            continue;
+        }
+        if let Some(states) = trimmed.strip_prefix("accept:") {
+            let states: BTreeSet<String> = split_states(states)
+                .into_iter()
+                .filter(|s| !s.is_empty())
+                .collect();
+            accepting = Some((states, line));
+            continue;
+        }
+        let (first, last) = match (raw.find("->"), raw.rfind("->")) {
+            (Some(first), Some(last)) if last > first => (first, last),
+            _ => {
+                issues.push(Issue::new(
+                    line,
+                    format!("expected State->class->State, found '{}'", trimmed),
+                ));
+                continue;
+            }
+        };
+        let from = &raw[..first];
+        let to = &raw[last + 2..];
+        for name in [from, to] {
+            if name.trim() != name {
+                issues.push(Issue::new(
+                    line,
+                    format!("whitespace around state name '{}'", name.trim()),
+                ));
+            }
+        }
+        let (from, to) = (from.trim(), to.trim());
+        if from.is_empty() || to.is_empty() {
+            issues.push(Issue::new(line, "missing state name".to_owned()));
+            continue;
+        }
+        match CharClass::parse(&raw[first + 2..last]) {
+            Ok(class) => transitions.push(Transition {
+                from: from.to_owned(),
+                class,
+                to: to.to_owned(),
+                line,
+            }),
+            Err(e) => issues.push(Issue::new(line, e)),
+        }
+    }
+    if transitions.is_empty() {
+        return Err(Error::Empty);
+    }
+
+    let mut states: Vec<String> = vec![];
+    for t in &transitions {
+        for name in [&t.from, &t.to] {
+            if !states.contains(name) {
+                states.push(name.clone());
+            }
+        }
+    }
+    let start = match start {
+        Some((state, line)) => {
+            if !states.contains(&state) {
+                issues.push(Issue::new(
+                    line,
+                    format!("start state {} has no transitions", state),
+                ));
+                states.push(state.clone());
+            }
+            state
+        }
+        None => states[0].clone(),
+    };
+    let accepting = match accepting {
+        Some((accepting, line)) => {
+            for state in &accepting {
+                if !states.contains(state) {
+                    issues.push(Issue::new(
+                        line,
+                        format!("accepting state {} has no transitions", state),
+                    ));
+                    states.push(state.clone());
+                }
+            }
+            accepting
+        }
+        None => {
+            issues.push(Issue::new(
+                0,
+                format!("no accept: line, every state but {} accepts", start),
+            ));
+            for state in states.iter().filter(|s| **s != start) {
+                if !transitions.iter().any(|t| t.from == *state) {
+                    let line = transitions
+                        .iter()
+                        .find(|t| t.to == *state)
+                        .map_or(0, |t| t.line);
+                    issues.push(Issue::new(
+                        line,
+                        format!("{} has no transitions and accepts only by default", state),
+                    ));
+                }
+            }
+            states.iter().filter(|s| **s != start).cloned().collect()
+        }
+    };
+    Ok((
+        Automaton {
+            start,
+            states,
+            accepting,
+            transitions,
+        },
+        issues,
+    ))
+}
+
+impl Automaton {
+    /// Transition taken from `state` on `c`, `None` ends the token
+    pub fn step(&self, state: &str, c: char) -> Option<&Transition> {
+        self.transitions
+            .iter()
+            .find(|t| t.from == state && t.class.contains(c))
+    }
+
+    pub fn outgoing<'a>(&'a self, state: &'a str) -> impl Iterator<Item = &'a Transition> + 'a {
+        self.transitions.iter().filter(move |t| t.from == state)
+    }
+
+    /// One edge per pair of states labelled with the characters that actually
+    /// take it, transitions shadowed by earlier ones drop out
+    pub fn edges(&self) -> Vec<(String, String, CharClass)> {
+        let mut edges: Vec<((&str, &str), Vec<char>)> = vec![];
+        for state in &self.states {
+            for c in alphabet() {
+                if let Some(t) = self.step(state, c) {
+                    let key = (state.as_str(), t.to.as_str());
+                    match edges.iter_mut().find(|(k, _)| *k == key) {
+                        Some((_, chars)) => chars.push(c),
+                        None => edges.push((key, vec![c])),
+                    }
+                }
+            }
+        }
+        edges
+            .into_iter()
+            .map(|((from, to), chars)| {
+                (
+                    from.to_owned(),
+                    to.to_owned(),
+                    CharClass::from_chars(&chars),
+                )
+            })
+            .collect()
+    }
+
+    /// State diagram of `edges`, `highlight` is drawn filled
+    pub fn diagram(&self, highlight: Option<&str>) -> Diagram {
+        // A state missing from `states` still gets drawn rather than lost
+        let mut names = self.states.clone();
+        let mut id = |state: &str| match names.iter().position(|s| s == state) {
+            Some(idx) => idx,
+            None => {
+                names.push(state.to_owned());
+                names.len() - 1
+            }
+        };
+        let start = id(&self.start);
+        let accepting = self.accepting.iter().map(|s| id(s)).collect();
+        let edges = self
+            .edges()
+            .into_iter()
+            .map(|(from, to, class)| (id(&from), id(&to), class.text))
+            .collect();
+        let highlight = highlight.and_then(|h| names.iter().position(|s| s == h));
+        Diagram {
+            names,
+            start,
+            accepting,
+            edges,
+            highlight,
+        }
+    }
+
+    /// States reachable from the start
+    pub fn reachable(&self) -> BTreeSet<String> {
+        let mut seen = BTreeSet::new();
+        let mut queue = vec![self.start.clone()];
+        while let Some(state) = queue.pop() {
+            if seen.insert(state.clone()) {
+                queue.extend(self.outgoing(&state).map(|t| t.to.clone()));
+            }
+        }
+        seen
+    }
+
+    /// Line a state first appears on
+    fn line_of(&self, state: &str) -> usize {
+        self.transitions
+            .iter()
+            .find(|t| t.from == state || t.to == state)
+            .map_or(0, |t| t.line)
+    }
+
+    /// Unreachable states, dead ends that don't accept and transitions whose
+    /// classes overlap an earlier one from the same state
+    pub fn validate(&self) -> Vec<Issue> {
+        let mut issues = vec![];
+        let reachable = self.reachable();
+        for state in self.states.iter().filter(|s| !reachable.contains(*s)) {
+            issues.push(Issue::new(
+                self.line_of(state),
+                format!("{} is unreachable from {}", state, self.start),
+            ));
+        }
+        for state in &self.states {
+            if self.outgoing(state).next().is_none() && !self.accepting.contains(state) {
+                issues.push(Issue::new(
+                    self.line_of(state),
+                    format!("{} has no transitions and is not accepting", state),
+                ));
+            }
+        }
+        for state in &self.states {
+            let outgoing: Vec<&Transition> = self.outgoing(state).collect();
+            for (j, later) in outgoing.iter().enumerate() {
+                let chars = later.class.chars();
+                for earlier in &outgoing[..j] {
+                    let common: Vec<char> = chars
+                        .iter()
+                        .cloned()
+                        .filter(|c| earlier.class.contains(*c))
+                        .collect();
+                    if !common.is_empty() {
+                        issues.push(Issue::new(
+                            later.line,
+                            format!(
+                                "{} on {} overlaps line {} on {}, line {} wins",
+                                state,
+                                later.class.text,
+                                earlier.line,
+                                format_chars(&common),
+                                earlier.line
+                            ),
+                        ));
+                    }
+                }
+                if !chars.is_empty()
+                    && chars
+                        .iter()
+                        .all(|c| self.step(state, *c).unwrap().line != later.line)
+                {
+                    issues.push(Issue::new(
+                        later.line,
+                        format!("{} -> {} is never taken", state, later.to),
+                    ));
+                }
+            }
+        }
+        issues.sort_by_key(|i| i.line);
+        issues
+    }
+
+    /// Every state with its role and number of outgoing transitions
+    pub fn format_states(&self) -> String {
+        let mut rows = vec![vec![
+            "state".to_owned(),
+            "role".to_owned(),
+            "out".to_owned(),
+        ]];
+        for state in &self.states {
+            let mut role = vec![];
+            if *state == self.start {
+                role.push("start");
+            }
+            if self.accepting.contains(state) {
+                role.push("accept");
+            }
+            rows.push(vec![
+                state.clone(),
+                role.join(", "),
+                self.outgoing(state).count().to_string(),
+            ]);
+        }
+        format!("\n{}", format_table(&rows))
+    }
+
+    /// Transition list `parse` reads back, with the start and accepting states spelled out
+    pub fn to_text(&self) -> String {
+        let accepting: Vec<&str> = self
+            .states
+            .iter()
+            .filter(|s| self.accepting.contains(*s))
+            .map(|s| s.as_str())
+            .collect();
+        let mut text = format!("start: {}\naccept: {}\n", self.start, accepting.join(", "));
+        for t in &self.transitions {
+            text.push_str(&format!("{}->{}->{}\n", t.from, t.class.text, t.to));
+        }
+        text
+    }
+
+    pub fn format_transitions(&self) -> String {
+        let mut rows = vec![vec![
+            "line".to_owned(),
+            "from".to_owned(),
+            "class".to_owned(),
+            "to".to_owned(),
+        ]];
+        for t in &self.transitions {
+            rows.push(vec![
+                t.line.to_string(),
+                t.from.clone(),
+                t.class.text.clone(),
+                t.to.clone(),
+            ]);
+        }
+        format!("\n{}", format_table(&rows))
+    }
+}
+
+pub fn format_issues(issues: &[Issue]) -> String {
+    issues.iter().map(|i| format!("\n{}", i)).collect()
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AutomatonReport {
+    pub states: String,
+    pub transitions: String,
+    /// Malformed lines followed by what `validate` found
+    pub issues: String,
+    pub error: String,
+}
+
+/// Parses and validates a transition list
+pub fn check(text: &str) -> AutomatonReport {
+    match parse(text) {
+        Ok((automaton, mut issues)) => {
+            issues.extend(automaton.validate());
+            issues.sort_by_key(|i| i.line);
+            AutomatonReport {
+                states: automaton.format_states(),
+                transitions: automaton.format_transitions(),
+                issues: format_issues(&issues),
+                error: String::new(),
+            }
+        }
+        Err(e) => AutomatonReport {
+            states: String::new(),
+            transitions: String::new(),
+            issues: String::new(),
+            error: e.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(issues: &[Issue]) -> Vec<String> {
+        issues.iter().map(|i| i.to_string()).collect()
+    }
+
+    #[test]
+    fn classes_read_ranges_escapes_and_quotes() {
+        let class = CharClass::parse("a-c\\s_").unwrap();
+        assert_eq!(
+            class.items,
+            [Item::Range('a', 'c'), Item::Char(' '), Item::Char('_')]
+        );
+        assert_eq!(CharClass::parse("'-'").unwrap().items, [Item::Char('-')]);
+        assert!(CharClass::parse("any").unwrap().contains('\n'));
+        assert_eq!(CharClass::parse("z-a").unwrap_err(), "empty range z-a");
+    }
+
+    #[test]
+    fn from_chars_writes_the_shortest_class() {
+        let chars: Vec<char> = ('a'..='f').chain(['0', '1', ' ']).collect();
+        assert_eq!(CharClass::from_chars(&chars).text, "\\s01a-f");
+        assert_eq!(CharClass::from_chars(&alphabet()).text, "any");
+        assert_eq!(CharClass::from_chars(&['a', 'n', 'y']).text, "\\any");
+    }
+
+    #[test]
+    fn defaults_to_the_first_state_and_accepts_the_rest() {
+        let (automaton, issues) = parse("S->a-z->Id\nId->a-z0-9->Id\nS->0-9->Num").unwrap();
+        assert_eq!(
+            messages(&issues),
+            [
+                "no accept: line, every state but S accepts",
+                "line 3: Num has no transitions and accepts only by default"
+            ]
+        );
+        assert_eq!(automaton.start, "S");
+        assert_eq!(automaton.states, ["S", "Id", "Num"]);
+        assert_eq!(
+            automaton.accepting,
+            BTreeSet::from(["Id".to_owned(), "Num".to_owned()])
+        );
+        assert_eq!(automaton.step("Id", '7').unwrap().to, "Id");
+        assert!(automaton.step("Num", 'x').is_none());
+    }
+
+    #[test]
+    fn reports_malformed_lines_and_keeps_going() {
+        let (automaton, issues) = parse("// comment\nS->a->A\nS a B\nS->->B\naccept: A").unwrap();
+        assert_eq!(automaton.transitions.len(), 1);
+        assert_eq!(
+            messages(&issues),
+            [
+                "line 3: expected State->class->State, found 'S a B'",
+                "line 4: empty character class"
+            ]
+        );
+        assert_eq!(
+            parse("// nothing").unwrap_err().to_string(),
+            "Automaton has no transitions"
+        );
+    }
+
+    #[test]
+    fn validate_finds_unreachable_dead_and_shadowed_transitions() {
+        let text = "start: S\naccept: A\nS->a-z->A\nS->x->B\nC->a->A";
+        let (automaton, _) = parse(text).unwrap();
+        assert_eq!(
+            messages(&automaton.validate()),
+            [
+                "line 4: B has no transitions and is not accepting",
+                "line 4: S on x overlaps line 3 on x, line 3 wins",
+                "line 4: S -> B is never taken",
+                "line 5: C is unreachable from S"
+            ]
+        );
+    }
+
+    #[test]
+    fn check_reports_dead_ends_without_an_accept_line() {
+        let report = check("S->a->A\nA->b->B\nA->c->A");
+        assert_eq!(
+            report.issues,
+            "\nno accept: line, every state but S accepts\nline 2: B has no transitions and accepts only by default"
+        );
+        let report = check("accept: A\nS->a->A\nA->b->B\nA->c->A");
+        assert_eq!(
+            report.issues,
+            "\nline 3: B has no transitions and is not accepting"
+        );
+    }
+
+    #[test]
+    fn to_text_reads_back_the_same_automaton() {
+        let (automaton, _) = parse("start: S\naccept: Id\nS->a-z->Id\nId->a-z0-9_->Id").unwrap();
+        let (again, issues) = parse(&automaton.to_text()).unwrap();
+        assert!(issues.is_empty());
+        assert_eq!(again.to_text(), automaton.to_text());
+        assert_eq!(again.accepting, automaton.accepting);
+    }
+
+    #[test]
+    fn accepting_states_without_transitions_are_kept() {
+        let (automaton, issues) = parse("start: A\naccept: B, Z\nA->a->B").unwrap();
+        assert_eq!(automaton.states, ["A", "B", "Z"]);
+        assert_eq!(
+            messages(&issues),
+            ["line 2: accepting state Z has no transitions"]
+        );
+        let diagram = automaton.diagram(Some("Z"));
+        assert_eq!(diagram.accepting, BTreeSet::from([1, 2]));
+        assert_eq!(diagram.highlight, Some(2));
+    }
+}
@@ -0,0 +1,273 @@
+use crate::automaton::{alphabet, format_chars, parse, Automaton};
+use crate::grammar::format_table;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DiffReport {
+    /// Added and removed states
+    pub states: String,
+    /// Characters whose target changed, compared state by state, so a
+    /// class written differently but meaning the same isn't a change
+    pub transitions: String,
+    pub accepting: String,
+    /// Whether both accept the same strings, with the shortest one they disagree on
+    pub equivalence: String,
+    pub error: String,
+}
+
+/// Kind, state, old target, new target, old line and new line of a changed transition
+type Row = (
+    &'static str,
+    String,
+    String,
+    String,
+    Option<usize>,
+    Option<usize>,
+);
+
+fn describe(line: Option<usize>) -> String {
+    line.map_or(String::new(), |l| l.to_string())
+}
+
+/// States, transitions and accepting states that differ from `old` to `new`
+pub fn diff(old: &Automaton, new: &Automaton) -> DiffReport {
+    let mut states = String::new();
+    for state in old.states.iter().filter(|s| !new.states.contains(s)) {
+        states.push_str(&format!("\n- {}", state));
+    }
+    for state in new.states.iter().filter(|s| !old.states.contains(s)) {
+        states.push_str(&format!("\n+ {}", state));
+    }
+    if old.start != new.start {
+        states.push_str(&format!("\nstart {} -> {}", old.start, new.start));
+    }
+
+    let mut all: Vec<&String> = old.states.iter().collect();
+    all.extend(new.states.iter().filter(|s| !old.states.contains(s)));
+    let mut rows: Vec<Row> = vec![];
+    // Characters of each row
+    let mut grouped: Vec<Vec<char>> = vec![];
+    for state in all {
+        for c in alphabet() {
+            let (before, after) = (old.step(state, c), new.step(state, c));
+            let kind = match (before, after) {
+                (None, None) => continue,
+                (Some(b), Some(a)) if b.to == a.to => continue,
+                (None, Some(_)) => "+",
+                (Some(_), None) => "-",
+                (Some(_), Some(_)) => "~",
+            };
+            let row = (
+                kind,
+                state.clone(),
+                before.map_or("-".to_owned(), |t| t.to.clone()),
+                after.map_or("-".to_owned(), |t| t.to.clone()),
+                before.map(|t| t.line),
+                after.map(|t| t.line),
+            );
+            if let Some(idx) = rows.iter().position(|r| *r == row) {
+                grouped[idx].push(c);
+            } else {
+                rows.push(row);
+                grouped.push(vec![c]);
+            }
+        }
+    }
+    let mut table = vec![vec![
+        "".to_owned(),
+        "state".to_owned(),
+        "class".to_owned(),
+        "old".to_owned(),
+        "new".to_owned(),
+        "old line".to_owned(),
+        "new line".to_owned(),
+    ]];
+    for ((kind, state, before, after, old_line, new_line), chars) in rows.iter().zip(&grouped) {
+        table.push(vec![
+            kind.to_string(),
+            state.clone(),
+            format_chars(chars),
+            before.clone(),
+            after.clone(),
+            describe(*old_line),
+            describe(*new_line),
+        ]);
+    }
+    let transitions = if rows.is_empty() {
+        String::new()
+    } else {
+        format!("\n{}", format_table(&table))
+    };
+
+    let mut accepting = String::new();
+    for state in old.accepting.difference(&new.accepting) {
+        accepting.push_str(&format!("\n- {}", state));
+    }
+    for state in new.accepting.difference(&old.accepting) {
+        accepting.push_str(&format!("\n+ {}", state));
+    }
+
+    let equivalence = match counterexample(old, new) {
+        None => "\nequivalent".to_owned(),
+        Some(word) => {
+            let side = if accepts(old, &word) { "old" } else { "new" };
+            format!(
+                "\nnot equivalent: {:?} is accepted only by the {} version",
+                word, side
+            )
+        }
+    };
+
+    DiffReport {
+        states,
+        transitions,
+        accepting,
+        equivalence,
+        error: String::new(),
+    }
+}
+
+/// Runs `word` from the start, a missing transition rejects
+pub fn accepts(automaton: &Automaton, word: &str) -> bool {
+    let mut state = automaton.start.as_str();
+    for c in word.chars() {
+        match automaton.step(state, c) {
+            Some(t) => state = &t.to,
+            None => return false,
+        }
+    }
+    automaton.accepting.contains(state)
+}
+
+/// Shortest string over `alphabet` accepted by exactly one of the two
+pub fn counterexample(a: &Automaton, b: &Automaton) -> Option<String> {
+    search(a, b, |in_a, in_b| in_a != in_b)
+}
+
+/// Shortest string `a` accepts and `b` doesn't, `None` when `b` accepts all of `a`
+pub fn not_included(a: &Automaton, b: &Automaton) -> Option<String> {
+    search(a, b, |in_a, in_b| in_a && !in_b)
+}
+
+/// Breadth-first search over pairs of states for the first one `found` holds
+/// on, given whether each side accepts. `None` is the dead state
+fn search(a: &Automaton, b: &Automaton, found: fn(bool, bool) -> bool) -> Option<String> {
+    type Pair = (Option<String>, Option<String>);
+    let accepting = |automaton: &Automaton, state: &Option<String>| {
+        state
+            .as_ref()
+            .is_some_and(|s| automaton.accepting.contains(s))
+    };
+    let start: Pair = (Some(a.start.clone()), Some(b.start.clone()));
+    let mut parent: BTreeMap<Pair, Option<(Pair, char)>> = BTreeMap::new();
+    parent.insert(start.clone(), None);
+    let mut queue = VecDeque::from([start]);
+    let chars = alphabet();
+    while let Some(pair) = queue.pop_front() {
+        if found(accepting(a, &pair.0), accepting(b, &pair.1)) {
+            let mut word = vec![];
+            let mut at = pair;
+            while let Some(Some((prev, c))) = parent.get(&at) {
+                word.push(*c);
+                at = prev.clone();
+            }
+            return Some(word.into_iter().rev().collect());
+        }
+        for &c in &chars {
+            let next: Pair = (
+                pair.0
+                    .as_ref()
+                    .and_then(|s| a.step(s, c))
+                    .map(|t| t.to.clone()),
+                pair.1
+                    .as_ref()
+                    .and_then(|s| b.step(s, c))
+                    .map(|t| t.to.clone()),
+            );
+            if next == (None, None) || parent.contains_key(&next) {
+                continue;
+            }
+            parent.insert(next.clone(), Some((pair.clone(), c)));
+            queue.push_back(next);
+        }
+    }
+    None
+}
+
+/// Parses both descriptions and compares them
+pub fn compare(old: &str, new: &str) -> DiffReport {
+    let parsed = parse(old)
+        .map_err(|e| format!("old: {}", e))
+        .and_then(|(old, _)| {
+            parse(new)
+                .map(|(new, _)| (old, new))
+                .map_err(|e| format!("new: {}", e))
+        });
+    match parsed {
+        Ok((old, new)) => diff(&old, &new),
+        Err(error) => DiffReport {
+            states: String::new(),
+            transitions: String::new(),
+            accepting: String::new(),
+            equivalence: String::new(),
+            error,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn automaton(text: &str) -> Automaton {
+        parse(text).unwrap().0
+    }
+
+    #[test]
+    fn a_class_written_differently_is_no_change() {
+        let report = compare("S->a-c->A", "S->abc->A");
+        assert_eq!(report.transitions, "");
+        assert_eq!(report.equivalence, "\nequivalent");
+    }
+
+    #[test]
+    fn lists_changed_states_and_transitions() {
+        let report = compare("S->a->A\nS->b->B", "S->a->C\nS->b->B");
+        assert_eq!(report.states, "\n- A\n+ C");
+        assert!(report.transitions.contains("~"));
+        assert_eq!(report.accepting, "\n- A\n+ C");
+        assert_eq!(report.equivalence, "\nequivalent");
+    }
+
+    #[test]
+    fn finds_the_shortest_disagreement() {
+        let old = automaton("S->a->A\nA->a->A");
+        let new = automaton("S->a->A\nA->a->B\nB->a->A");
+        assert_eq!(counterexample(&old, &new), None);
+        let new = automaton("start: S\naccept: B\nS->a->A\nA->a->B");
+        assert_eq!(counterexample(&old, &new).as_deref(), Some("a"));
+        assert_eq!(
+            compare("S->a->A\nA->a->A", "start: S\naccept: B\nS->a->A\nA->a->B").equivalence,
+            "\nnot equivalent: \"a\" is accepted only by the old version"
+        );
+    }
+
+    #[test]
+    fn inclusion_is_one_sided() {
+        let words = automaton("S->a-b->A\nA->a-b->A");
+        let a_only = automaton("S->a->A\nA->a->A");
+        assert_eq!(not_included(&a_only, &words), None);
+        assert_eq!(not_included(&words, &a_only).as_deref(), Some("b"));
+        assert!(accepts(&words, "abba"));
+        assert!(!accepts(&words, ""));
+    }
+
+    #[test]
+    fn reports_which_side_failed_to_parse() {
+        assert_eq!(
+            compare("// nothing", "S->a->A").error,
+            "old: Automaton has no transitions"
+        );
+    }
+}
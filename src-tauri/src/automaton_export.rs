@@ -0,0 +1,197 @@
+use crate::automaton::Automaton;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum Format {
+    Drawio,
+    Dot,
+    Mermaid,
+}
+
+impl Format {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Drawio => "drawio",
+            Self::Dot => "dot",
+            Self::Mermaid => "mmd",
+        }
+    }
+}
+
+pub fn render(automaton: &Automaton, format: Format) -> String {
+    match format {
+        Format::Drawio => to_drawio(automaton),
+        Format::Dot => to_dot(automaton),
+        Format::Mermaid => to_mermaid(automaton),
+    }
+}
+
+/// Graphviz DOT, accepting states are double circles and the start has an arrow from nowhere
+pub fn to_dot(automaton: &Automaton) -> String {
+    automaton.diagram(None).to_dot()
+}
+
+/// Mermaid `stateDiagram-v2`. Names like `Letter('a')` aren't valid ids, so
+/// states are `s0`, `s1`... with the name as their description
+pub fn to_mermaid(automaton: &Automaton) -> String {
+    // Mermaid reads `#...;` as an entity, which also hides `;` and `"`
+    let escape = |x: &str| {
+        x.replace('#', "#35;")
+            .replace(';', "#59;")
+            .replace('"', "#quot;")
+            .replace(':', "#58;")
+    };
+    let diagram = automaton.diagram(None);
+    let mut out = String::from("stateDiagram-v2\n");
+    for (idx, state) in diagram.names.iter().enumerate() {
+        out.push_str(&format!("    state \"{}\" as s{}\n", escape(state), idx));
+    }
+    out.push_str(&format!("    [*] --> s{}\n", diagram.start));
+    for (from, to, label) in &diagram.edges {
+        out.push_str(&format!("    s{} --> s{} : {}\n", from, to, escape(label)));
+    }
+    for state in &diagram.accepting {
+        out.push_str(&format!("    s{} --> [*]\n", state));
+    }
+    out
+}
+
+/// Text of an XML attribute or element, for draw.io cells and SVG labels
+pub fn escape_xml(x: &str) -> String {
+    x.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Text of a quoted Graphviz DOT label
+pub fn escape_dot(x: &str) -> String {
+    x.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Style of a state, `import` looks for `doubleEllipse` to find accepting ones
+pub const STATE_STYLE: &str = "ellipse;";
+pub const ACCEPTING_STYLE: &str = "ellipse;shape=doubleEllipse;";
+/// Filled dot the start state is reached from
+pub const INITIAL_STYLE: &str = "ellipse;fillColor=#000000;strokeColor=#000000;";
+const EDGE_STYLE: &str = "edgeStyle=none;endArrow=block;curved=1;";
+
+const CHAR_WIDTH: usize = 7;
+const HEIGHT: usize = 40;
+const ROW_GAP: usize = 30;
+const COLUMN_GAP: usize = 80;
+
+/// draw.io diagram laid out left to right by distance from the start
+pub fn to_drawio(automaton: &Automaton) -> String {
+    let diagram = automaton.diagram(None);
+    let width = |state: usize| (diagram.names[state].chars().count() * CHAR_WIDTH + 30).max(HEIGHT);
+    // 0 and 1 are the root cells
+    let id = |state: usize| state + 2;
+    let mut cells = String::new();
+    let mut x = HEIGHT;
+    let mut start_y = 0;
+    for layer in diagram.layers() {
+        let column = layer.iter().map(|s| width(*s)).max().unwrap_or(HEIGHT);
+        for (row, state) in layer.iter().enumerate() {
+            let y = row * (HEIGHT + ROW_GAP);
+            if *state == diagram.start {
+                start_y = y;
+            }
+            let style = if diagram.accepting.contains(state) {
+                ACCEPTING_STYLE
+            } else {
+                STATE_STYLE
+            };
+            cells.push_str(&format!(
+                "        <mxCell id=\"{}\" value=\"{}\" style=\"{}\" vertex=\"1\" parent=\"1\">\n          <mxGeometry x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" as=\"geometry\"/>\n        </mxCell>\n",
+                id(*state),
+                escape_xml(&diagram.names[*state]),
+                style,
+                x + (column - width(*state)) / 2,
+                y,
+                width(*state),
+                HEIGHT
+            ));
+        }
+        x += column + COLUMN_GAP;
+    }
+
+    let initial = id(diagram.names.len());
+    cells.push_str(&format!(
+        "        <mxCell id=\"{}\" value=\"\" style=\"{}\" vertex=\"1\" parent=\"1\">\n          <mxGeometry x=\"0\" y=\"{}\" width=\"14\" height=\"14\" as=\"geometry\"/>\n        </mxCell>\n",
+        initial,
+        INITIAL_STYLE,
+        start_y + (HEIGHT - 14) / 2
+    ));
+    let edge = |id: usize, value: &str, source: usize, target: usize| {
+        format!(
+            "        <mxCell id=\"{}\" value=\"{}\" style=\"{}\" edge=\"1\" parent=\"1\" source=\"{}\" target=\"{}\">\n          <mxGeometry relative=\"1\" as=\"geometry\"/>\n        </mxCell>\n",
+            id, escape_xml(value), EDGE_STYLE, source, target
+        )
+    };
+    cells.push_str(&edge(initial + 1, "", initial, id(diagram.start)));
+    for (idx, (from, to, label)) in diagram.edges.iter().enumerate() {
+        cells.push_str(&edge(initial + 2 + idx, label, id(*from), id(*to)));
+    }
+
+    format!(
+        "<mxfile host=\"automata_project\">\n  <diagram name=\"automaton\">\n    <mxGraphModel>\n      <root>\n        <mxCell id=\"0\"/>\n        <mxCell id=\"1\" parent=\"0\"/>\n{}      </root>\n    </mxGraphModel>\n  </diagram>\n</mxfile>\n",
+        cells
+    )
+}
+
+/// Parses a transition list and renders it
+pub fn export(text: &str, format: Format) -> Result<String, String> {
+    let (automaton, _) = crate::automaton::parse(text).map_err(|e| e.to_string())?;
+    Ok(render(&automaton, format))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn automaton(text: &str) -> Automaton {
+        crate::automaton::parse(text).unwrap().0
+    }
+
+    #[test]
+    fn mermaid_numbers_states_and_escapes_names() {
+        assert_eq!(
+            to_mermaid(&automaton("S->;->Separator(';')")),
+            "stateDiagram-v2\n    state \"S\" as s0\n    state \"Separator('#59;')\" as s1\n    [*] --> s0\n    s0 --> s1 : #59;\n    s1 --> [*]\n"
+        );
+    }
+
+    #[test]
+    fn escapes_labels_for_xml_and_dot() {
+        assert_eq!(escape_xml("a<'&'>\""), "a&lt;&apos;&amp;&apos;&gt;&quot;");
+        assert_eq!(escape_dot("\\\"x\""), "\\\\\\\"x\\\"");
+        let diagram = crate::diagram::Diagram {
+            names: vec!["\"q\"".to_owned()],
+            start: 0,
+            accepting: Default::default(),
+            edges: vec![(0, 0, "<".to_owned())],
+            highlight: None,
+        };
+        assert!(diagram.to_dot().contains("label=\"\\\"q\\\"\""));
+        assert!(diagram.to_svg().contains("&lt;"));
+    }
+
+    #[test]
+    fn drawio_marks_accepting_states_and_the_start() {
+        let xml = to_drawio(&automaton("S->a->A\nA->b->S"));
+        assert_eq!(xml.matches(ACCEPTING_STYLE).count(), 1);
+        assert!(xml.contains(INITIAL_STYLE));
+        assert!(xml.contains("source=\"4\" target=\"2\""));
+        assert!(xml.contains("value=\"a\""));
+    }
+
+    #[test]
+    fn accepting_states_without_transitions_are_exported() {
+        let text = "start: A\naccept: B, Z\nA->a->B";
+        for format in [Format::Drawio, Format::Dot, Format::Mermaid] {
+            assert!(export(text, format).unwrap().contains('Z'));
+        }
+    }
+}
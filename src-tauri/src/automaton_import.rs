@@ -0,0 +1,332 @@
+use crate::automaton::{Automaton, CharClass, Transition};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// `mxCell` with the attributes the import looks at
+#[derive(Debug, Default, Clone)]
+struct Cell {
+    id: String,
+    value: String,
+    style: String,
+    vertex: bool,
+    edge: bool,
+    parent: String,
+    source: String,
+    target: String,
+}
+
+/// Decodes the five XML entities and numeric references
+fn decode(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let end = match rest.find(';') {
+            Some(end) if end <= 10 => end,
+            _ => {
+                out.push('&');
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        let entity = &rest[1..end];
+        let decoded = match entity {
+            "amp" => Some('&'),
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            "nbsp" => Some(' '),
+            _ => entity
+                .strip_prefix("#x")
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Name and attributes of every start tag, in document order
+fn tags(xml: &str) -> Vec<(String, BTreeMap<String, String>)> {
+    let mut tags = vec![];
+    let mut rest = xml;
+    while let Some(open) = rest.find('<') {
+        rest = &rest[open + 1..];
+        if rest.starts_with('/') || rest.starts_with('?') || rest.starts_with('!') {
+            continue;
+        }
+        let name_end = rest
+            .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .unwrap_or(rest.len());
+        let name = rest[..name_end].to_owned();
+        rest = &rest[name_end..];
+        let mut attributes = BTreeMap::new();
+        loop {
+            rest = rest.trim_start();
+            if rest.is_empty() || rest.starts_with('>') || rest.starts_with("/>") {
+                break;
+            }
+            let eq = match rest.find('=') {
+                Some(eq) => eq,
+                None => break,
+            };
+            let key = rest[..eq].trim().to_owned();
+            rest = rest[eq + 1..].trim_start();
+            let quote = match rest.chars().next() {
+                Some(q @ ('"' | '\'')) => q,
+                _ => break,
+            };
+            let end = rest[1..].find(quote).map_or(rest.len(), |e| e + 1);
+            attributes.insert(key, decode(&rest[1..end]));
+            rest = &rest[(end + 1).min(rest.len())..];
+        }
+        tags.push((name, attributes));
+    }
+    tags
+}
+
+/// Text of a label, HTML labels (`html=1`) lose their markup. Line breaks
+/// only wrap the label, classes spell a newline as `\n`
+fn label(value: &str, style: &str) -> String {
+    let text = if style.contains("html=1") {
+        let mut plain = String::new();
+        let mut in_tag = false;
+        for c in value.chars() {
+            match c {
+                '<' => in_tag = true,
+                '>' if in_tag => in_tag = false,
+                c if !in_tag => plain.push(c),
+                _ => {}
+            }
+        }
+        decode(&plain)
+    } else {
+        value.to_owned()
+    };
+    text.lines()
+        .map(|l| l.trim())
+        .collect::<Vec<&str>>()
+        .concat()
+}
+
+fn cells(xml: &str) -> Vec<Cell> {
+    let mut cells = vec![];
+    // `<object>` and `<UserObject>` carry the id and label of the `mxCell` inside them
+    let mut wrapper: Option<(String, String)> = None;
+    for (name, attributes) in tags(xml) {
+        let get = |key: &str| attributes.get(key).cloned().unwrap_or_default();
+        match name.as_str() {
+            "object" | "UserObject" => wrapper = Some((get("id"), get("label"))),
+            "mxCell" => {
+                let mut cell = Cell {
+                    id: get("id"),
+                    value: get("value"),
+                    style: get("style"),
+                    vertex: get("vertex") == "1",
+                    edge: get("edge") == "1",
+                    parent: get("parent"),
+                    source: get("source"),
+                    target: get("target"),
+                };
+                if let Some((id, value)) = wrapper.take() {
+                    if cell.id.is_empty() {
+                        cell.id = id;
+                        cell.value = value;
+                    }
+                }
+                cells.push(cell);
+            }
+            _ => {}
+        }
+    }
+    cells
+}
+
+/// The filled dot an arrow to the start state leaves from
+fn is_initial(cell: &Cell) -> bool {
+    cell.value.trim().is_empty()
+        && cell.style.to_lowercase().split(';').any(|x| {
+            matches!(
+                x.trim(),
+                "fillcolor=#000000" | "fillcolor=#000" | "fillcolor=black"
+            )
+        })
+}
+
+fn is_accepting(cell: &Cell) -> bool {
+    cell.style.contains("doubleEllipse") || cell.style.contains("double=1")
+}
+
+/// Reads a draw.io diagram: labelled vertices are states, a double ellipse
+/// (or double border) accepts, edge labels are character classes and an arrow
+/// from a filled dot marks the start. Without the dot the first state starts.
+/// Returns the automaton and what had to be skipped
+pub fn import(xml: &str) -> Result<(Automaton, Vec<String>), String> {
+    if !xml.contains("<mxGraphModel") {
+        if xml.contains("<diagram") {
+            return Err(
+                "The diagram is compressed, save it with Extras > Compressed turned off".to_owned(),
+            );
+        }
+        return Err("Not a draw.io diagram".to_owned());
+    }
+    let cells = cells(xml);
+    let mut issues = vec![];
+    let mut names: BTreeMap<&str, String> = BTreeMap::new();
+    let mut states: Vec<String> = vec![];
+    let mut accepting = BTreeSet::new();
+    let mut initial = BTreeSet::new();
+    for cell in cells
+        .iter()
+        .filter(|c| c.vertex && !c.style.contains("edgeLabel"))
+    {
+        if is_initial(cell) {
+            initial.insert(cell.id.as_str());
+            continue;
+        }
+        let name = label(&cell.value, &cell.style);
+        if name.is_empty() {
+            continue;
+        }
+        if is_accepting(cell) {
+            accepting.insert(name.clone());
+        }
+        if !states.contains(&name) {
+            states.push(name.clone());
+        }
+        names.insert(&cell.id, name);
+    }
+    // Labels draw.io keeps as separate cells on top of their edge
+    let mut edge_labels: BTreeMap<&str, String> = BTreeMap::new();
+    for cell in cells
+        .iter()
+        .filter(|c| c.vertex && c.style.contains("edgeLabel"))
+    {
+        let text = label(&cell.value, &cell.style);
+        if !text.is_empty() {
+            edge_labels.entry(&cell.parent).or_insert(text);
+        }
+    }
+
+    let mut start = None;
+    let mut transitions = vec![];
+    for cell in cells.iter().filter(|c| c.edge) {
+        if initial.contains(cell.source.as_str()) {
+            match names.get(cell.target.as_str()) {
+                Some(name) => start = Some(name.clone()),
+                None => issues.push("arrow from the start dot doesn't end at a state".to_owned()),
+            }
+            continue;
+        }
+        let (from, to) = match (
+            names.get(cell.source.as_str()),
+            names.get(cell.target.as_str()),
+        ) {
+            (Some(from), Some(to)) => (from, to),
+            _ => {
+                issues.push(format!("edge {} isn't connected to two states", cell.id));
+                continue;
+            }
+        };
+        let text = match label(&cell.value, &cell.style) {
+            text if !text.is_empty() => text,
+            _ => match edge_labels.get(cell.id.as_str()) {
+                Some(text) => text.clone(),
+                None => {
+                    issues.push(format!("edge from {} to {} has no label", from, to));
+                    continue;
+                }
+            },
+        };
+        match CharClass::parse(&text) {
+            Ok(class) => transitions.push(Transition {
+                from: from.clone(),
+                class,
+                to: to.clone(),
+                line: 0,
+            }),
+            Err(e) => issues.push(format!("edge from {} to {}: {}", from, to, e)),
+        }
+    }
+    if states.is_empty() {
+        return Err("The diagram has no states".to_owned());
+    }
+    Ok((
+        Automaton {
+            start: start.unwrap_or_else(|| states[0].clone()),
+            states,
+            accepting,
+            transitions,
+        },
+        issues,
+    ))
+}
+
+/// Transition list of the diagram, what was skipped comes first as comments
+pub fn import_text(xml: &str) -> Result<String, String> {
+    let (automaton, issues) = import(xml)?;
+    let mut text: String = issues.iter().map(|i| format!("// {}\n", i)).collect();
+    text.push_str(&automaton.to_text());
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::automaton::parse;
+    use crate::automaton_diff::counterexample;
+    use crate::automaton_export::to_drawio;
+
+    #[test]
+    fn reads_back_an_exported_diagram() {
+        let (automaton, _) =
+            parse("start: S\naccept: Id, Amp\nS->a-z->Id\nId->a-z0-9->Id\nS->&->Amp").unwrap();
+        let (again, issues) = import(&to_drawio(&automaton)).unwrap();
+        assert!(issues.is_empty());
+        assert_eq!(again.start, "S");
+        assert_eq!(again.accepting, automaton.accepting);
+        assert_eq!(counterexample(&automaton, &again), None);
+    }
+
+    #[test]
+    fn html_labels_lose_their_markup() {
+        assert_eq!(decode("a&lt;b&#59;&#x41;&bogus"), "a<b;A&bogus");
+        assert_eq!(label("<b>a-z</b><br>0-9", "html=1;"), "a-z0-9");
+        assert_eq!(label("a-z\n  _", "ellipse;"), "a-z_");
+    }
+
+    #[test]
+    fn takes_labels_from_their_own_cells_and_reports_loose_edges() {
+        let xml = r#"<mxGraphModel><root>
+            <mxCell id="0"/><mxCell id="1" parent="0"/>
+            <object label="S" id="a"><mxCell style="ellipse;" vertex="1" parent="1"/></object>
+            <mxCell id="b" value="T" style="ellipse;double=1;" vertex="1" parent="1"/>
+            <mxCell id="e" value="" edge="1" parent="1" source="a" target="b"/>
+            <mxCell id="l" value="x" style="edgeLabel;" vertex="1" parent="e"/>
+            <mxCell id="f" value="y" edge="1" parent="1" source="b"/>
+        </root></mxGraphModel>"#;
+        let (automaton, issues) = import(xml).unwrap();
+        assert_eq!(automaton.to_text(), "start: S\naccept: T\nS->x->T\n");
+        assert_eq!(issues, ["edge f isn't connected to two states"]);
+    }
+
+    #[test]
+    fn rejects_compressed_diagrams() {
+        assert_eq!(
+            import("<mxfile><diagram>eJx</diagram></mxfile>").unwrap_err(),
+            "The diagram is compressed, save it with Extras > Compressed turned off"
+        );
+    }
+}
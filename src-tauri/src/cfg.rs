@@ -0,0 +1,374 @@
+use crate::automaton_export::{escape_dot, escape_xml};
+use crate::quads::{Code, Operand, Quad};
+
+/// Quadruples `start..end` run one after another, control only enters at `start`
+#[derive(Clone, Debug)]
+pub struct Block {
+    pub start: usize,
+    pub end: usize,
+    /// Indices into `Graph::blocks`
+    pub successors: Vec<usize>,
+}
+
+/// Control flow graph of one function, or of the global initialisers
+#[derive(Clone, Debug)]
+pub struct Graph {
+    pub name: String,
+    pub blocks: Vec<Block>,
+}
+
+/// Quadruple range of every function, global initialisers first if there are any
+pub fn ranges(code: &Code) -> Vec<(String, usize, usize)> {
+    let mut ranges = vec![];
+    let first = code.functions.first().map_or(code.quads.len(), |f| f.start);
+    if first > 0 {
+        ranges.push(("global".to_owned(), 0, first));
+    }
+    for (idx, f) in code.functions.iter().enumerate() {
+        let end = code
+            .functions
+            .get(idx + 1)
+            .map_or(code.quads.len(), |next| next.start);
+        ranges.push((f.name.clone(), f.start, end));
+    }
+    ranges
+}
+
+fn target(q: &Quad) -> Option<usize> {
+    match (q.op.as_str(), &q.result) {
+        ("jz" | "jmp", Operand::Address(target)) => Some(*target),
+        _ => None,
+    }
+}
+
+/// Splits `start..end` into basic blocks. Leaders are the first quadruple,
+/// jump targets and whatever follows a jump or `ret`
+pub fn build(quads: &[Quad], name: &str, start: usize, end: usize) -> Graph {
+    let mut leaders = vec![start];
+    for (idx, q) in quads.iter().enumerate().take(end).skip(start) {
+        if let Some(target) = target(q) {
+            if target >= start && target < end {
+                leaders.push(target);
+            }
+        }
+        if matches!(q.op.as_str(), "jz" | "jmp" | "ret") && idx + 1 < end {
+            leaders.push(idx + 1);
+        }
+    }
+    leaders.sort();
+    leaders.dedup();
+
+    let block_of = |idx: usize| leaders.iter().position(|x| *x == idx);
+    let blocks = leaders
+        .iter()
+        .enumerate()
+        .map(|(b, &first)| {
+            let last = leaders.get(b + 1).map_or(end, |next| *next);
+            let q = &quads[last - 1];
+            let mut successors = vec![];
+            if let Some(target) = target(q).and_then(block_of) {
+                successors.push(target);
+            }
+            let falls_through = !matches!(q.op.as_str(), "jmp" | "ret");
+            if falls_through && last < end && !successors.contains(&(b + 1)) {
+                successors.push(b + 1);
+            }
+            Block {
+                start: first,
+                end: last,
+                successors,
+            }
+        })
+        .collect();
+    Graph {
+        name: name.to_owned(),
+        blocks,
+    }
+}
+
+pub fn graphs(code: &Code) -> Vec<Graph> {
+    ranges(code)
+        .into_iter()
+        .map(|(name, start, end)| build(&code.quads, &name, start, end))
+        .collect()
+}
+
+impl Graph {
+    /// Blocks that can't be reached from the entry
+    pub fn unreachable(&self) -> Vec<usize> {
+        let mut seen = vec![false; self.blocks.len()];
+        let mut queue = vec![0];
+        while let Some(b) = queue.pop() {
+            if b >= seen.len() || seen[b] {
+                continue;
+            }
+            seen[b] = true;
+            queue.extend(self.blocks[b].successors.iter().cloned());
+        }
+        (0..self.blocks.len()).filter(|b| !seen[*b]).collect()
+    }
+}
+
+/// Every block with its quadruples and successors
+pub fn to_text(code: &Code) -> String {
+    let mut text = String::new();
+    for graph in graphs(code) {
+        text.push_str(&format!("\n{}:", graph.name));
+        for (idx, block) in graph.blocks.iter().enumerate() {
+            let successors: Vec<String> =
+                block.successors.iter().map(|b| format!("B{}", b)).collect();
+            text.push_str(&format!(
+                "\nB{} [{}..{}] -> {}",
+                idx,
+                block.start,
+                block.end - 1,
+                successors.join(", ")
+            ));
+            for q in &code.quads[block.start..block.end] {
+                text.push_str(&format!("\n    {}", q.to_source()));
+            }
+        }
+    }
+    text
+}
+
+/// `true`/`false` on the two edges leaving a conditional jump
+fn edge_label(quads: &[Quad], graph: &Graph, from: usize, to: usize) -> &'static str {
+    let last = &quads[graph.blocks[from].end - 1];
+    if last.op != "jz" {
+        return "";
+    }
+    if target(last) == Some(graph.blocks[to].start) {
+        "false"
+    } else {
+        "true"
+    }
+}
+
+/// Block name followed by its quadruples, jumps name the target block
+fn block_lines(quads: &[Quad], graph: &Graph, idx: usize) -> Vec<String> {
+    let block = &graph.blocks[idx];
+    let name = |target: usize| match graph.blocks.iter().position(|b| b.start == target) {
+        Some(b) => format!("B{}", b),
+        None => format!("@{}", target),
+    };
+    std::iter::once(format!("B{}", idx))
+        .chain(
+            quads[block.start..block.end]
+                .iter()
+                .map(|q| match (q.op.as_str(), target(q)) {
+                    ("jz", Some(t)) => format!("if not {} goto {}", q.arg1.to_source(), name(t)),
+                    ("jmp", Some(t)) => format!("goto {}", name(t)),
+                    _ => q.to_source(),
+                }),
+        )
+        .collect()
+}
+
+/// Graphviz DOT digraph with one cluster per function
+pub fn to_dot(code: &Code) -> String {
+    let mut out = String::from("digraph cfg {\n    node [shape=box, fontname=monospace];\n");
+    for (g, graph) in graphs(code).iter().enumerate() {
+        out.push_str(&format!(
+            "    subgraph cluster_{} {{\n        label=\"{}\";\n",
+            g,
+            escape_dot(&graph.name)
+        ));
+        for idx in 0..graph.blocks.len() {
+            let label: Vec<String> = block_lines(&code.quads, graph, idx)
+                .iter()
+                .map(|x| escape_dot(x))
+                .collect();
+            out.push_str(&format!(
+                "        g{}b{} [label=\"{}\\l\"];\n",
+                g,
+                idx,
+                label.join("\\l")
+            ));
+        }
+        for (idx, block) in graph.blocks.iter().enumerate() {
+            for s in &block.successors {
+                let label = edge_label(&code.quads, graph, idx, *s);
+                out.push_str(&format!("        g{}b{} -> g{}b{}", g, idx, g, s));
+                if !label.is_empty() {
+                    out.push_str(&format!(" [label=\"{}\"]", label));
+                }
+                out.push_str(";\n");
+            }
+        }
+        out.push_str("    }\n");
+    }
+    out.push_str("}\n");
+    out
+}
+
+const CHAR_WIDTH: usize = 7;
+const LINE_HEIGHT: usize = 14;
+/// Room left and right of the blocks for edges that skip over other blocks
+const GUTTER: usize = 60;
+const GAP: usize = 30;
+
+/// SVG with one column per function. Blocks are stacked in program order,
+/// edges to the next block go straight down, forward jumps curve around the
+/// right side and back edges around the left
+pub fn to_svg(code: &Code) -> String {
+    let mut body = String::new();
+    let mut x = 0;
+    let mut height = 0;
+    for graph in graphs(code) {
+        let lines: Vec<Vec<String>> = graph
+            .blocks
+            .iter()
+            .enumerate()
+            .map(|(idx, _)| block_lines(&code.quads, &graph, idx))
+            .collect();
+        let width = lines
+            .iter()
+            .flatten()
+            .map(|l| l.chars().count() * CHAR_WIDTH + 10)
+            .max()
+            .unwrap_or(0)
+            .max(graph.name.chars().count() * CHAR_WIDTH);
+        let left = x + GUTTER;
+        // Top of every block
+        let mut tops = vec![];
+        let mut y = LINE_HEIGHT * 2;
+        body.push_str(&format!(
+            "<text x=\"{}\" y=\"{}\" font-weight=\"bold\">{}</text>\n",
+            left,
+            LINE_HEIGHT,
+            escape_xml(&graph.name)
+        ));
+        for block in &lines {
+            tops.push(y);
+            let h = block.len() * LINE_HEIGHT + 6;
+            body.push_str(&format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"white\" stroke=\"black\"/>\n",
+                left, y, width, h
+            ));
+            for (n, line) in block.iter().enumerate() {
+                let weight = if n == 0 { " font-weight=\"bold\"" } else { "" };
+                body.push_str(&format!(
+                    "<text x=\"{}\" y=\"{}\"{}>{}</text>\n",
+                    left + 5,
+                    y + (n + 1) * LINE_HEIGHT,
+                    weight,
+                    escape_xml(line)
+                ));
+            }
+            y += h + GAP;
+        }
+        let bottom = |b: usize| tops[b] + lines[b].len() * LINE_HEIGHT + 6;
+        let middle = |b: usize| (tops[b] + bottom(b)) / 2;
+        for (idx, block) in graph.blocks.iter().enumerate() {
+            for &s in &block.successors {
+                let label = edge_label(&code.quads, &graph, idx, s);
+                let (path, label_x, label_y) = if s == idx + 1 {
+                    let cx = left + width / 2;
+                    (
+                        format!("M{},{} L{},{}", cx, bottom(idx), cx, tops[s]),
+                        cx + 4,
+                        bottom(idx) + GAP / 2,
+                    )
+                } else {
+                    // Longer jumps bend further out so they don't overlap
+                    let bend = (10 + 8 * idx.abs_diff(s)).min(GUTTER - 5);
+                    let (side, out) = if s > idx {
+                        (left + width, left + width + bend)
+                    } else {
+                        (left, left - bend)
+                    };
+                    (
+                        format!(
+                            "M{},{} C{},{} {},{} {},{}",
+                            side,
+                            middle(idx),
+                            out,
+                            middle(idx),
+                            out,
+                            middle(s),
+                            side,
+                            middle(s)
+                        ),
+                        out,
+                        (middle(idx) + middle(s)) / 2,
+                    )
+                };
+                body.push_str(&format!(
+                    "<path d=\"{}\" fill=\"none\" stroke=\"black\" marker-end=\"url(#arrow)\"/>\n",
+                    path
+                ));
+                if !label.is_empty() {
+                    body.push_str(&format!(
+                        "<text x=\"{}\" y=\"{}\" font-size=\"10\">{}</text>\n",
+                        label_x, label_y, label
+                    ));
+                }
+            }
+        }
+        height = height.max(y);
+        x = left + width + GUTTER;
+    }
+    format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-family=\"monospace\" font-size=\"12\">\n\
+         <defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"6\" markerHeight=\"6\" orient=\"auto\">\
+         <path d=\"M0,0 L10,5 L0,10 z\"/></marker></defs>\n{}</svg>\n",
+        x, height, body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::descriptors::Symbols;
+    use crate::keywords::count_tokens;
+    use crate::quads::translate;
+    use crate::syntax::parse;
+
+    fn lower(program: &str) -> Code {
+        let tokens = count_tokens(program.to_owned()).unwrap();
+        let symbols = Symbols::new(&tokens);
+        translate(&parse(tokens).unwrap(), &symbols).unwrap()
+    }
+
+    const LOOP: &str = "int n = 3;
+int main() { int i = 0; while (i < n) i = i + 1; return i; }";
+
+    #[test]
+    fn splits_at_jumps_and_their_targets() {
+        let graphs = graphs(&lower(LOOP));
+        assert_eq!(graphs[0].name, "global");
+        let main = &graphs[1];
+        let blocks: Vec<(usize, usize, Vec<usize>)> = main
+            .blocks
+            .iter()
+            .map(|b| (b.start, b.end, b.successors.clone()))
+            .collect();
+        assert_eq!(
+            blocks,
+            [
+                (1, 2, vec![1]),
+                (2, 4, vec![3, 2]),
+                (4, 7, vec![1]),
+                (7, 8, vec![]),
+                (8, 9, vec![])
+            ]
+        );
+        assert_eq!(main.unreachable(), [4]);
+    }
+
+    #[test]
+    fn dot_labels_the_branches() {
+        let dot = to_dot(&lower(LOOP));
+        assert!(dot.contains("g1b1 [label=\"B1\\lt1 = i < n\\lif not t1 goto B3\\l\"];"));
+        assert!(dot.contains("g1b1 -> g1b3 [label=\"false\"];"));
+        assert!(dot.contains("g1b1 -> g1b2 [label=\"true\"];"));
+    }
+
+    #[test]
+    fn svg_escapes_the_quadruples() {
+        let svg = to_svg(&lower(LOOP));
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("t1 = i &lt; n"));
+    }
+}
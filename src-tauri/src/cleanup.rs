@@ -15,10 +15,10 @@ pub fn remove_comments(mut program: String) -> String {
     let mut current_idx: usize = 0;
     let mut current: char;
     while current_idx < program.chars().count() {
-        current = program.as_str().as_bytes()[current_idx] as char;
+        current = program.as_bytes()[current_idx] as char;
         match state {
             // Alphanumeric char
-            State::String | State::Extra => {
+            State::String => {
                 state = match current {
                     ' ' | '\t' => State::Whitespace,
                     '\n' => State::Newline,
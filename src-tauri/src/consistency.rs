@@ -0,0 +1,256 @@
+use crate::automaton::{alphabet, format_chars, Automaton, CharClass, Transition};
+use crate::grammar::format_table;
+use crate::keywords::{probe, Probe};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// `Letter(l)` in a description stands for every `Letter('…')` of the lexer,
+/// a bare `Separator` for every `Separator(…)`
+fn matches(documented: &str, implemented: &str) -> bool {
+    if documented == implemented {
+        return true;
+    }
+    let (base, arg) = match documented.split_once('(') {
+        Some((base, arg)) => (base, Some(arg)),
+        None => (documented, None),
+    };
+    match (implemented.split_once('('), arg) {
+        (Some((other, _)), None) => other == base,
+        (Some((other, _)), Some(arg)) => other == base && !arg.starts_with('\''),
+        (None, _) => false,
+    }
+}
+
+/// Documented state a lexer state belongs to, an exact name wins over a pattern
+fn documented<'a>(automaton: &'a Automaton, implemented: &str) -> Option<&'a str> {
+    let states = &automaton.states;
+    states
+        .iter()
+        .find(|s| *s == implemented)
+        .or_else(|| states.iter().find(|s| matches(s, implemented)))
+        .map(|s| s.as_str())
+}
+
+/// What the lexer does in one documented state on one character
+#[derive(Default)]
+struct Implemented {
+    targets: BTreeSet<String>,
+    ended: bool,
+    failed: bool,
+}
+
+impl Implemented {
+    /// Targets by their documented names. One named after the character
+    /// read, like `Character('!')` on `!`, is shown as `Character(c)` so that
+    /// such edges group into a single row
+    fn describe(&self, automaton: &Automaton, from: &str, c: char) -> String {
+        let read = format!("({:?})", c);
+        let mut what: Vec<String> = self
+            .targets
+            .iter()
+            .map(|t| match t.strip_suffix(&read) {
+                Some(base) if t != from => format!("{}(c)", base),
+                _ => documented(automaton, t).unwrap_or(t).to_owned(),
+            })
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect();
+        if self.ended {
+            what.push("ends token".to_owned());
+        }
+        if self.failed {
+            what.push("error".to_owned());
+        }
+        what.join(", ")
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ConsistencyReport {
+    /// Edges of the lexer missing from the description, edges of the
+    /// description the lexer doesn't have and edges going elsewhere
+    pub differences: String,
+    pub summary: String,
+    pub error: String,
+}
+
+/// Steps `count_tokens` through every state on every character of `alphabet`
+/// and compares the edges with the description
+pub fn compare(automaton: &Automaton) -> ConsistencyReport {
+    let chars = alphabet();
+    // Documented state (or the lexer name if there is none) and character
+    let mut order: Vec<String> = vec![];
+    let mut lexer: BTreeMap<(String, char), Implemented> = BTreeMap::new();
+    let mut covered: BTreeSet<String> = BTreeSet::new();
+    for (state, c, probe) in probe(&chars) {
+        let name = match documented(automaton, &state) {
+            Some(name) => {
+                covered.insert(name.to_owned());
+                name.to_owned()
+            }
+            None => state,
+        };
+        if !order.contains(&name) {
+            order.push(name.clone());
+        }
+        let entry = lexer.entry((name, c)).or_default();
+        match probe {
+            Probe::Moved { to, .. } => {
+                entry.targets.insert(to);
+            }
+            Probe::Ended => entry.ended = true,
+            Probe::Failed => entry.failed = true,
+        }
+    }
+
+    // kind, state, documented, implemented, line -> characters
+    let mut rows: Vec<(&str, String, String, String, usize)> = vec![];
+    let mut grouped: Vec<Vec<char>> = vec![];
+    let mut add = |row: (&'static str, String, String, String, usize), c: char| {
+        if let Some(idx) = rows.iter().position(|r| *r == row) {
+            grouped[idx].push(c);
+        } else {
+            rows.push(row);
+            grouped.push(vec![c]);
+        }
+    };
+    for state in &order {
+        for &c in &chars {
+            let implemented = &lexer[&(state.clone(), c)];
+            let documented = automaton.step(state, c);
+            let kind = match documented {
+                Some(t) if implemented.targets.iter().any(|x| matches(&t.to, x)) => continue,
+                Some(_) if implemented.targets.is_empty() => "extra",
+                Some(_) => "changed",
+                None if implemented.targets.is_empty() => continue,
+                None => "missing",
+            };
+            add(
+                (
+                    kind,
+                    state.clone(),
+                    documented.map_or("-".to_owned(), |t| t.to.clone()),
+                    implemented.describe(automaton, state, c),
+                    documented.map_or(0, |t| t.line),
+                ),
+                c,
+            );
+        }
+    }
+    let mut uncovered = 0;
+    for state in automaton.states.iter().filter(|s| !covered.contains(*s)) {
+        uncovered += 1;
+        for t in automaton.outgoing(state) {
+            for c in t.class.chars() {
+                add(
+                    (
+                        "extra",
+                        state.clone(),
+                        t.to.clone(),
+                        "no such state".to_owned(),
+                        t.line,
+                    ),
+                    c,
+                );
+            }
+        }
+    }
+
+    let mut table = vec![vec![
+        "kind".to_owned(),
+        "state".to_owned(),
+        "class".to_owned(),
+        "description".to_owned(),
+        "lexer".to_owned(),
+        "line".to_owned(),
+    ]];
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for ((kind, state, documented, implemented, line), chars) in rows.iter().zip(&grouped) {
+        *counts.entry(kind).or_default() += 1;
+        table.push(vec![
+            kind.to_string(),
+            state.clone(),
+            format_chars(chars),
+            documented.clone(),
+            implemented.clone(),
+            if *line == 0 {
+                String::new()
+            } else {
+                line.to_string()
+            },
+        ]);
+    }
+    let summary = format!(
+        "\n{} lexer states, {} documented states not in the lexer\nmissing {}, extra {}, changed {}",
+        order.len(),
+        uncovered,
+        counts.get("missing").unwrap_or(&0),
+        counts.get("extra").unwrap_or(&0),
+        counts.get("changed").unwrap_or(&0)
+    );
+    ConsistencyReport {
+        differences: format!("\n{}", format_table(&table)),
+        summary,
+        error: String::new(),
+    }
+}
+
+/// The automaton `count_tokens` implements, as found by `probe`. States a
+/// token can be flushed from accept
+pub fn lexer_automaton() -> Automaton {
+    let mut states: Vec<String> = vec![];
+    let mut accepting = BTreeSet::new();
+    // (from, to) -> characters, in order of discovery
+    let mut edges: Vec<((String, String), Vec<char>)> = vec![];
+    for (state, c, probe) in probe(&alphabet()) {
+        if !states.contains(&state) {
+            states.push(state.clone());
+        }
+        match probe {
+            Probe::Moved { to, flush } => {
+                if flush {
+                    accepting.insert(to.clone());
+                }
+                if !states.contains(&to) {
+                    states.push(to.clone());
+                }
+                let key = (state, to);
+                match edges.iter_mut().find(|(k, _)| *k == key) {
+                    Some((_, chars)) => chars.push(c),
+                    None => edges.push((key, vec![c])),
+                }
+            }
+            Probe::Ended => {
+                accepting.insert(state);
+            }
+            Probe::Failed => {}
+        }
+    }
+    let transitions = edges
+        .into_iter()
+        .map(|((from, to), chars)| Transition {
+            from,
+            class: CharClass::from_chars(&chars),
+            to,
+            line: 0,
+        })
+        .collect();
+    Automaton {
+        start: states[0].clone(),
+        states,
+        accepting,
+        transitions,
+    }
+}
+
+/// Parses a transition list and compares it with `count_tokens`
+pub fn check(text: &str) -> ConsistencyReport {
+    match crate::automaton::parse(text) {
+        Ok((automaton, _)) => compare(&automaton),
+        Err(e) => ConsistencyReport {
+            differences: String::new(),
+            summary: String::new(),
+            error: e.to_string(),
+        },
+    }
+}
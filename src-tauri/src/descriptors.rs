@@ -1,6 +1,8 @@
 use crate::keywords::{Token, TokenType};
+use serde::{Deserialize, Serialize};
 use std::vec::Vec;
 
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Table {
     pub descriptors: String,
     pub pseudocode: String,
@@ -12,6 +14,46 @@ pub struct Table {
     pub operators: String,
 }
 
+/// Rows of the identifier, constant and string tables, same numbering as `create_descriptors`
+#[derive(Clone, Debug)]
+pub struct Symbols {
+    pub identifiers: Vec<String>,
+    pub consts: Vec<String>,
+    pub strings: Vec<String>,
+}
+
+impl Symbols {
+    pub fn new(tokens: &[Token]) -> Symbols {
+        let unique = |token_type: TokenType| {
+            let mut set: Vec<String> = Vec::new();
+            for i in tokens.iter().filter(|x| x.token_type == token_type) {
+                if !set.contains(&i.token) {
+                    set.push(i.token.clone());
+                }
+            }
+            set
+        };
+        Symbols {
+            identifiers: unique(TokenType::Identifier),
+            consts: unique(TokenType::ConstValue),
+            strings: unique(TokenType::StringLiteral),
+        }
+    }
+
+    /// Row of a name as it appears in the syntax tree
+    pub fn identifier(&self, name: &str) -> Option<usize> {
+        self.identifiers.iter().position(|x| x == name)
+    }
+
+    pub fn constant(&self, value: &str) -> Option<usize> {
+        self.consts.iter().position(|x| x == value)
+    }
+
+    pub fn string(&self, value: &str) -> Option<usize> {
+        self.strings.iter().position(|x| x == value)
+    }
+}
+
 pub fn create_descriptors(tokens: Vec<Token>) -> Option<Table> {
     let clone = tokens.clone();
     let (identifiers, rest): (Vec<Token>, Vec<Token>) = clone
@@ -80,7 +122,6 @@ pub fn create_descriptors(tokens: Vec<Token>) -> Option<Table> {
                         .iter()
                         .position(|y| y == &x.token)
                         .unwrap()
-                        .to_string()
                 ),
                 TokenType::Identifier => format!(
                     "({},{})",
@@ -90,7 +131,6 @@ pub fn create_descriptors(tokens: Vec<Token>) -> Option<Table> {
                         .iter()
                         .position(|y| y == &x.token)
                         .unwrap()
-                        .to_string()
                 ),
                 TokenType::Operator => format!(
                     "({},{})",
@@ -100,7 +140,6 @@ pub fn create_descriptors(tokens: Vec<Token>) -> Option<Table> {
                         .iter()
                         .position(|y| y == &x.token)
                         .unwrap()
-                        .to_string()
                 ),
                 TokenType::ConstValue => format!(
                     "({},{})",
@@ -110,7 +149,6 @@ pub fn create_descriptors(tokens: Vec<Token>) -> Option<Table> {
                         .iter()
                         .position(|y| y == &x.token)
                         .unwrap()
-                        .to_string()
                 ),
                 TokenType::StringLiteral => format!(
                     "({},{})",
@@ -120,7 +158,6 @@ pub fn create_descriptors(tokens: Vec<Token>) -> Option<Table> {
                         .iter()
                         .position(|y| y == &x.token)
                         .unwrap()
-                        .to_string()
                 ),
                 TokenType::Separator => format!(
                     "({},{})",
@@ -130,7 +167,6 @@ pub fn create_descriptors(tokens: Vec<Token>) -> Option<Table> {
                         .iter()
                         .position(|y| y == &x.token)
                         .unwrap()
-                        .to_string()
                 ),
             }
         })
@@ -147,7 +183,6 @@ pub fn create_descriptors(tokens: Vec<Token>) -> Option<Table> {
                         .iter()
                         .position(|y| y == &x.token)
                         .unwrap()
-                        .to_string()
                 ),
                 TokenType::Operator => format!("{} ", x.token),
                 TokenType::ConstValue => format!(
@@ -156,7 +191,6 @@ pub fn create_descriptors(tokens: Vec<Token>) -> Option<Table> {
                         .iter()
                         .position(|y| y == &x.token)
                         .unwrap()
-                        .to_string()
                 ),
                 TokenType::StringLiteral => format!(
                     "str{} ",
@@ -164,7 +198,6 @@ pub fn create_descriptors(tokens: Vec<Token>) -> Option<Table> {
                         .iter()
                         .position(|y| y == &x.token)
                         .unwrap()
-                        .to_string()
                 ),
                 TokenType::Separator => format!("{} ", x.token),
             }
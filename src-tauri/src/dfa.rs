@@ -0,0 +1,277 @@
+use crate::automaton::{alphabet, format_chars, Automaton, CharClass, Transition};
+use crate::automaton_diff::{accepts, counterexample, not_included};
+use crate::grammar::format_table;
+use crate::nfa::{parse_regex, Nfa};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Debug, Clone)]
+pub struct Dfa {
+    /// NFA states each DFA state stands for, `D0` is the start
+    pub sets: Vec<BTreeSet<usize>>,
+    /// Accepting state and the name of what it accepts
+    pub accepting: BTreeMap<usize, String>,
+    /// Target of every defined transition, by state and character
+    pub moves: Vec<BTreeMap<char, usize>>,
+}
+
+pub fn format_set(set: &BTreeSet<usize>) -> String {
+    let items: Vec<String> = set.iter().map(|s| s.to_string()).collect();
+    format!("{{{}}}", items.join(", "))
+}
+
+pub fn name(state: usize) -> String {
+    format!("D{}", state)
+}
+
+impl Dfa {
+    /// Subset construction. Returns the DFA and the trace: every ε-closure
+    /// computed and whether it made a new state. When a set holds several
+    /// accepting NFA states the lowest numbered one names it, so the first
+    /// part of a `Nfa::union` wins
+    pub fn from_nfa(nfa: &Nfa) -> (Dfa, Vec<String>) {
+        let mut trace = vec![];
+        let start = nfa.closure(&BTreeSet::from([nfa.start]));
+        trace.push(format!(
+            "{} = ε-closure({{{}}}) = {}",
+            name(0),
+            nfa.start,
+            format_set(&start)
+        ));
+        let mut dfa = Dfa {
+            sets: vec![start],
+            accepting: BTreeMap::new(),
+            moves: vec![],
+        };
+        let mut idx = 0;
+        while idx < dfa.sets.len() {
+            let set = dfa.sets[idx].clone();
+            if let Some(state) = set.iter().find(|s| nfa.accepting.contains_key(s)) {
+                dfa.accepting.insert(idx, nfa.accepting[state].clone());
+            }
+            // Characters with the same move go through one ε-closure
+            let mut groups: Vec<(BTreeSet<usize>, Vec<char>)> = vec![];
+            for c in alphabet() {
+                let moved = nfa.step(&set, c);
+                if moved.is_empty() {
+                    continue;
+                }
+                match groups.iter_mut().find(|(m, _)| *m == moved) {
+                    Some((_, chars)) => chars.push(c),
+                    None => groups.push((moved, vec![c])),
+                }
+            }
+            let mut moves = BTreeMap::new();
+            for (moved, chars) in groups {
+                let closure = nfa.closure(&moved);
+                let (target, new) = match dfa.sets.iter().position(|s| *s == closure) {
+                    Some(target) => (target, ""),
+                    None => {
+                        dfa.sets.push(closure.clone());
+                        (dfa.sets.len() - 1, " (new)")
+                    }
+                };
+                trace.push(format!(
+                    "{} on {}: move = {}, ε-closure = {} = {}{}",
+                    name(idx),
+                    format_chars(&chars),
+                    format_set(&moved),
+                    format_set(&closure),
+                    name(target),
+                    new
+                ));
+                for c in chars {
+                    moves.insert(c, target);
+                }
+            }
+            dfa.moves.push(moves);
+            idx += 1;
+        }
+        (dfa, trace)
+    }
+
+    /// Characters no state tells apart, each group is a column of the table
+    pub fn columns(&self) -> Vec<Vec<char>> {
+        let mut columns: Vec<(Vec<Option<usize>>, Vec<char>)> = vec![];
+        for c in alphabet() {
+            let targets: Vec<Option<usize>> =
+                self.moves.iter().map(|m| m.get(&c).cloned()).collect();
+            if targets.iter().all(|t| t.is_none()) {
+                continue;
+            }
+            match columns.iter_mut().find(|(t, _)| *t == targets) {
+                Some((_, chars)) => chars.push(c),
+                None => columns.push((targets, vec![c])),
+            }
+        }
+        columns.into_iter().map(|(_, chars)| chars).collect()
+    }
+
+    /// Transition table, `->` marks the start and `*` accepting states
+    pub fn to_table(&self) -> String {
+        let columns = self.columns();
+        let mut header = vec![
+            "".to_owned(),
+            "state".to_owned(),
+            "NFA states".to_owned(),
+            "accepts".to_owned(),
+        ];
+        header.extend(columns.iter().map(|chars| format_chars(chars)));
+        let mut rows = vec![header];
+        for (idx, set) in self.sets.iter().enumerate() {
+            let mark = match (idx == 0, self.accepting.contains_key(&idx)) {
+                (true, true) => "->*",
+                (true, false) => "->",
+                (false, true) => "*",
+                (false, false) => "",
+            };
+            let mut row = vec![
+                mark.to_owned(),
+                name(idx),
+                format_set(set),
+                self.accepting.get(&idx).cloned().unwrap_or_default(),
+            ];
+            for chars in &columns {
+                row.push(
+                    self.moves[idx]
+                        .get(&chars[0])
+                        .map_or("-".to_owned(), |t| name(*t)),
+                );
+            }
+            rows.push(row);
+        }
+        format!("\n{}", format_table(&rows))
+    }
+
+    /// The same DFA as a transition list with states `D0`, `D1`...
+    pub fn to_automaton(&self) -> Automaton {
+        let mut transitions = vec![];
+        for (from, moves) in self.moves.iter().enumerate() {
+            let mut targets: Vec<(usize, Vec<char>)> = vec![];
+            for (c, to) in moves {
+                match targets.iter_mut().find(|(t, _)| t == to) {
+                    Some((_, chars)) => chars.push(*c),
+                    None => targets.push((*to, vec![*c])),
+                }
+            }
+            for (to, chars) in targets {
+                transitions.push(Transition {
+                    from: name(from),
+                    class: CharClass::from_chars(&chars),
+                    to: name(to),
+                    line: 0,
+                });
+            }
+        }
+        Automaton {
+            start: name(0),
+            states: (0..self.sets.len()).map(name).collect(),
+            accepting: self.accepting.keys().map(|s| name(*s)).collect(),
+            transitions,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DfaReport {
+    /// ε-closures in the order the construction computed them
+    pub trace: String,
+    pub table: String,
+    /// Transition list, opens in the automaton editor
+    pub text: String,
+    pub svg: String,
+    pub error: String,
+}
+
+/// Subset construction DFA of the Thompson NFA of `regex`, with its trace
+pub fn build(regex: &str) -> Result<(Dfa, Vec<String>), String> {
+    let parsed = parse_regex(regex).map_err(|e| e.to_string())?;
+    Ok(Dfa::from_nfa(&Nfa::from_regex(&parsed, regex)))
+}
+
+/// Thompson NFA of `regex` turned into a DFA by the subset construction
+pub fn regex_to_dfa(regex: &str) -> DfaReport {
+    match build(regex) {
+        Ok((dfa, trace)) => {
+            let automaton = dfa.to_automaton();
+            DfaReport {
+                trace: trace.iter().map(|l| format!("\n{}", l)).collect(),
+                table: dfa.to_table(),
+                text: automaton.to_text(),
+                svg: automaton.diagram(None).to_svg(),
+                error: String::new(),
+            }
+        }
+        Err(error) => DfaReport {
+            trace: String::new(),
+            table: String::new(),
+            text: String::new(),
+            svg: String::new(),
+            error,
+        },
+    }
+}
+
+/// Whether the lexer `count_tokens` implements accepts everything the DFA of
+/// `regex` does and whether the two are equivalent, with the shortest string
+/// that shows otherwise
+pub fn compare_with_lexer(regex: &str) -> Result<String, String> {
+    let (dfa, _) = build(regex)?;
+    let (dfa, lexer) = (dfa.to_automaton(), crate::consistency::lexer_automaton());
+    let mut out = format!(
+        "\nDFA: {} states, lexer: {} states",
+        dfa.states.len(),
+        lexer.states.len()
+    );
+    match not_included(&dfa, &lexer) {
+        None => out.push_str("\nthe lexer accepts every string of the DFA"),
+        Some(word) => out.push_str(&format!("\nthe lexer rejects {:?}", word)),
+    }
+    match counterexample(&dfa, &lexer) {
+        None => out.push_str("\nequivalent"),
+        Some(word) => {
+            let side = if accepts(&dfa, &word) { "DFA" } else { "lexer" };
+            out.push_str(&format!(
+                "\nnot equivalent: {:?} is accepted only by the {}",
+                word, side
+            ));
+        }
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subset_construction_of_the_textbook_example() {
+        let (dfa, trace) = build("(a|b)*abb").unwrap();
+        assert_eq!(dfa.sets.len(), 5);
+        assert_eq!(dfa.accepting.keys().collect::<Vec<_>>(), [&4]);
+        assert!(trace[0].starts_with("D0 = ε-closure({"));
+        assert!(trace.iter().any(|l| l.ends_with("(new)")));
+        let automaton = dfa.to_automaton();
+        assert!(accepts(&automaton, "aabb"));
+        assert!(!accepts(&automaton, "abab"));
+    }
+
+    #[test]
+    fn columns_group_characters_with_the_same_targets() {
+        let (dfa, _) = build("[a-c]x|d").unwrap();
+        assert_eq!(dfa.columns(), [vec!['a', 'b', 'c'], vec!['d'], vec!['x']]);
+        assert!(dfa.to_table().contains("a-c"));
+    }
+
+    #[test]
+    fn rejects_characters_outside_of_the_alphabet() {
+        assert_eq!(
+            build("é+").unwrap_err(),
+            "Regex error at 0: 'é' is outside of the alphabet"
+        );
+        assert_eq!(
+            regex_to_dfa("[aé]").error,
+            "Regex error at 2: 'é' is outside of the alphabet"
+        );
+    }
+}
@@ -0,0 +1,198 @@
+use crate::automaton_export::{escape_dot, escape_xml};
+use std::collections::BTreeSet;
+
+/// State diagram to draw: states by index, edges as (from, to, label)
+pub struct Diagram {
+    pub names: Vec<String>,
+    pub start: usize,
+    pub accepting: BTreeSet<usize>,
+    pub edges: Vec<(usize, usize, String)>,
+    /// Drawn filled, the simulator's current state
+    pub highlight: Option<usize>,
+}
+
+const CHAR_WIDTH: f64 = 7.0;
+const MIN_RADIUS: f64 = 18.0;
+const COLUMN_GAP: f64 = 90.0;
+const ROW_GAP: f64 = 40.0;
+const MARGIN: f64 = 50.0;
+
+impl Diagram {
+    /// Columns of states by their distance from the start, unreachable states last
+    pub fn layers(&self) -> Vec<Vec<usize>> {
+        let mut layer = vec![usize::MAX; self.names.len()];
+        layer[self.start] = 0;
+        let mut queue = vec![self.start];
+        while !queue.is_empty() {
+            let state = queue.remove(0);
+            for (from, to, _) in &self.edges {
+                if *from == state && layer[*to] == usize::MAX {
+                    layer[*to] = layer[state] + 1;
+                    queue.push(*to);
+                }
+            }
+        }
+        let deepest = layer.iter().filter(|l| **l != usize::MAX).max().unwrap();
+        let mut layers = vec![vec![]; deepest + 2];
+        for (state, l) in layer.iter().enumerate() {
+            layers[(*l).min(deepest + 1)].push(state);
+        }
+        layers.retain(|l| !l.is_empty());
+        layers
+    }
+
+    fn radius(&self, state: usize) -> f64 {
+        (self.names[state].chars().count() as f64 * CHAR_WIDTH / 2.0 + 8.0).max(MIN_RADIUS)
+    }
+
+    /// States in columns left to right by distance from the start. Edges are
+    /// curved so the two directions between a pair don't overlap, loops sit on top
+    pub fn to_svg(&self) -> String {
+        let mut centers = vec![(0.0, 0.0); self.names.len()];
+        let mut x = MARGIN;
+        let mut height: f64 = 0.0;
+        for layer in self.layers() {
+            let column = layer
+                .iter()
+                .map(|s| self.radius(*s))
+                .fold(MIN_RADIUS, f64::max);
+            let mut y = MARGIN;
+            for state in layer {
+                let r = self.radius(state);
+                centers[state] = (x + column, y + r);
+                y += 2.0 * r + ROW_GAP;
+            }
+            height = height.max(y);
+            x += 2.0 * column + COLUMN_GAP;
+        }
+
+        let mut body = String::new();
+        for (from, to, label) in &self.edges {
+            let (x1, y1) = centers[*from];
+            let (x2, y2) = centers[*to];
+            let (r1, r2) = (self.radius(*from), self.radius(*to));
+            let (path, lx, ly) = if from == to {
+                let top = y1 - r1;
+                (
+                    format!(
+                        "M{:.1},{:.1} C{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}",
+                        x1 - 8.0,
+                        top,
+                        x1 - 25.0,
+                        top - 35.0,
+                        x1 + 25.0,
+                        top - 35.0,
+                        x1 + 8.0,
+                        top
+                    ),
+                    x1,
+                    top - 30.0,
+                )
+            } else {
+                let (dx, dy) = (x2 - x1, y2 - y1);
+                let length = (dx * dx + dy * dy).sqrt();
+                let (ux, uy) = (dx / length, dy / length);
+                // Bend to the left of the direction of travel
+                let bend = 20.0 + length / 10.0;
+                let (cx, cy) = ((x1 + x2) / 2.0 + uy * bend, (y1 + y2) / 2.0 - ux * bend);
+                let towards = |(px, py): (f64, f64), (qx, qy): (f64, f64), r: f64| {
+                    let (ex, ey) = (qx - px, qy - py);
+                    let l = (ex * ex + ey * ey).sqrt();
+                    (px + ex / l * r, py + ey / l * r)
+                };
+                let (sx, sy) = towards((x1, y1), (cx, cy), r1);
+                let (ex, ey) = towards((x2, y2), (cx, cy), r2);
+                (
+                    format!(
+                        "M{:.1},{:.1} Q{:.1},{:.1} {:.1},{:.1}",
+                        sx, sy, cx, cy, ex, ey
+                    ),
+                    (sx + 2.0 * cx + ex) / 4.0,
+                    (sy + 2.0 * cy + ey) / 4.0 - 3.0,
+                )
+            };
+            body.push_str(&format!(
+                "<path d=\"{}\" fill=\"none\" stroke=\"black\" marker-end=\"url(#arrow)\"/>\n\
+                 <text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" font-size=\"10\">{}</text>\n",
+                path,
+                lx,
+                ly,
+                escape_xml(label)
+            ));
+        }
+
+        for (state, name) in self.names.iter().enumerate() {
+            let (cx, cy) = centers[state];
+            let r = self.radius(state);
+            let fill = if self.highlight == Some(state) {
+                "#ffd54f"
+            } else {
+                "white"
+            };
+            body.push_str(&format!(
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"{}\" stroke=\"black\"/>\n",
+                cx, cy, r, fill
+            ));
+            if self.accepting.contains(&state) {
+                body.push_str(&format!(
+                    "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"none\" stroke=\"black\"/>\n",
+                    cx,
+                    cy,
+                    r - 4.0
+                ));
+            }
+            body.push_str(&format!(
+                "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>\n",
+                cx,
+                cy + 4.0,
+                escape_xml(name)
+            ));
+        }
+        let (sx, sy) = centers[self.start];
+        body.push_str(&format!(
+            "<path d=\"M{:.1},{:.1} L{:.1},{:.1}\" stroke=\"black\" marker-end=\"url(#arrow)\"/>\n",
+            sx - self.radius(self.start) - 30.0,
+            sy,
+            sx - self.radius(self.start),
+            sy
+        ));
+
+        format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.0}\" height=\"{:.0}\" font-family=\"monospace\" font-size=\"12\">\n\
+             <defs><marker id=\"arrow\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" markerWidth=\"6\" markerHeight=\"6\" orient=\"auto\">\
+             <path d=\"M0,0 L10,5 L0,10 z\"/></marker></defs>\n{}</svg>\n",
+            x, height, body
+        )
+    }
+
+    /// Graphviz DOT of the same diagram
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from(
+            "digraph automaton {\n    rankdir=LR;\n    node [shape=circle, fontname=monospace];\n    __start [shape=point];\n",
+        );
+        for (idx, name) in self.names.iter().enumerate() {
+            let shape = if self.accepting.contains(&idx) {
+                "doublecircle"
+            } else {
+                "circle"
+            };
+            out.push_str(&format!(
+                "    s{} [label=\"{}\", shape={}];\n",
+                idx,
+                escape_dot(name),
+                shape
+            ));
+        }
+        out.push_str(&format!("    __start -> s{};\n", self.start));
+        for (from, to, label) in &self.edges {
+            out.push_str(&format!(
+                "    s{} -> s{} [label=\"{}\"];\n",
+                from,
+                to,
+                escape_dot(label)
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
@@ -0,0 +1,628 @@
+use crate::keywords::Token;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub const EPSILON: &str = "ε";
+pub const END: &str = "$";
+/// Parses stop here, a grammar with conflicts can expand forever
+pub const MAX_STEPS: usize = 1000;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Line of the grammar and what is wrong with it
+    Syntax(usize, String),
+    Empty,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Syntax(line, what) => write!(f, "Grammar error at line {}: {}", line, what),
+            Self::Empty => f.write_str("Grammar has no productions"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Production {
+    pub head: String,
+    /// Empty for ε
+    pub body: Vec<String>,
+}
+
+impl std::fmt::Display for Production {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.body.is_empty() {
+            write!(f, "{} -> {}", self.head, EPSILON)
+        } else {
+            write!(f, "{} -> {}", self.head, self.body.join(" "))
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Grammar {
+    pub start: String,
+    pub productions: Vec<Production>,
+    /// In order of definition
+    pub nonterminals: Vec<String>,
+    /// In order of first use
+    pub terminals: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Lexeme {
+    Symbol(String),
+    /// `'...'`, always a terminal
+    Quoted(String),
+    Meta(char),
+}
+
+fn lex_line(line: &str, number: usize) -> Result<Vec<Lexeme>, Error> {
+    let mut lexemes = vec![];
+    let mut chars = line.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '|' | '{' | '}' | '[' | ']' | '(' | ')' => {
+                chars.next();
+                lexemes.push(Lexeme::Meta(c));
+            }
+            '\'' | '"' => {
+                chars.next();
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some(q) if q == c => break,
+                        Some(q) => text.push(q),
+                        None => return Err(Error::Syntax(number, "unterminated quote".to_owned())),
+                    }
+                }
+                lexemes.push(Lexeme::Quoted(text));
+            }
+            _ => {
+                let mut text = String::new();
+                while let Some(&c) = chars.peek() {
+                    if c.is_whitespace() || "|{}[]()".contains(c) {
+                        break;
+                    }
+                    text.push(c);
+                    chars.next();
+                }
+                lexemes.push(Lexeme::Symbol(text));
+            }
+        }
+    }
+    Ok(lexemes)
+}
+
+/// Turns EBNF groups into fresh nonterminals while reading a rule
+struct RuleReader<'a> {
+    lexemes: &'a [Lexeme],
+    pos: usize,
+    line: usize,
+    head: String,
+    extra: Vec<Production>,
+    fresh: &'a mut usize,
+}
+
+impl RuleReader<'_> {
+    fn fresh(&mut self) -> String {
+        *self.fresh += 1;
+        format!("{}_{}", self.head, self.fresh)
+    }
+
+    fn alternatives(&mut self) -> Result<Vec<Vec<String>>, Error> {
+        let mut alternatives = vec![self.sequence()?];
+        while self.lexemes.get(self.pos) == Some(&Lexeme::Meta('|')) {
+            self.pos += 1;
+            alternatives.push(self.sequence()?);
+        }
+        Ok(alternatives)
+    }
+
+    fn sequence(&mut self) -> Result<Vec<String>, Error> {
+        let mut sequence = vec![];
+        while let Some(lexeme) = self.lexemes.get(self.pos).cloned() {
+            match lexeme {
+                Lexeme::Symbol(s) if s == EPSILON || s == "eps" => self.pos += 1,
+                Lexeme::Symbol(s) | Lexeme::Quoted(s) => {
+                    self.pos += 1;
+                    sequence.push(s);
+                }
+                Lexeme::Meta(open @ ('{' | '[' | '(')) => {
+                    self.pos += 1;
+                    let close = match open {
+                        '{' => '}',
+                        '[' => ']',
+                        _ => ')',
+                    };
+                    let mut alternatives = self.alternatives()?;
+                    if self.lexemes.get(self.pos) != Some(&Lexeme::Meta(close)) {
+                        return Err(Error::Syntax(self.line, format!("expected '{}'", close)));
+                    }
+                    self.pos += 1;
+                    let name = self.fresh();
+                    match open {
+                        // X -> α X | ε
+                        '{' => {
+                            for alternative in alternatives.iter_mut() {
+                                alternative.push(name.clone());
+                            }
+                            alternatives.push(vec![]);
+                        }
+                        // X -> α | ε
+                        '[' => alternatives.push(vec![]),
+                        _ => {}
+                    }
+                    self.extra
+                        .extend(alternatives.into_iter().map(|body| Production {
+                            head: name.clone(),
+                            body,
+                        }));
+                    sequence.push(name);
+                }
+                Lexeme::Meta(_) => break,
+            }
+        }
+        Ok(sequence)
+    }
+}
+
+/// Reads rules of the form `Head -> a B | c` (or `::=`), one per line.
+/// Lines starting with `|` continue the previous rule, `{}`, `[]` and `()`
+/// are EBNF repetition, option and grouping. Symbols that never appear
+/// on the left are terminals: either a `TokenType` class name or a lexeme
+pub fn parse_grammar(text: &str) -> Result<Grammar, Error> {
+    let mut productions: Vec<Production> = vec![];
+    let mut extra: Vec<Production> = vec![];
+    let mut fresh = 0;
+    let mut head: Option<String> = None;
+    for (idx, line) in text.lines().enumerate() {
+        let number = idx + 1;
+        let lexemes = lex_line(line, number)?;
+        let rest = match lexemes.first() {
+            None => continue,
+            Some(Lexeme::Meta('|')) => match &head {
+                Some(_) => &lexemes[1..],
+                None => return Err(Error::Syntax(number, "'|' before any rule".to_owned())),
+            },
+            Some(Lexeme::Symbol(name)) => match lexemes.get(1) {
+                Some(Lexeme::Symbol(arrow)) if arrow == "->" || arrow == "::=" => {
+                    head = Some(name.clone());
+                    &lexemes[2..]
+                }
+                _ => {
+                    return Err(Error::Syntax(
+                        number,
+                        "expected '->' after the rule name".to_owned(),
+                    ))
+                }
+            },
+            _ => return Err(Error::Syntax(number, "expected a rule name".to_owned())),
+        };
+        let mut reader = RuleReader {
+            lexemes: rest,
+            pos: 0,
+            line: number,
+            head: head.clone().unwrap(),
+            extra: vec![],
+            fresh: &mut fresh,
+        };
+        let alternatives = reader.alternatives()?;
+        if reader.pos != rest.len() {
+            return Err(Error::Syntax(number, "unbalanced brackets".to_owned()));
+        }
+        extra.extend(reader.extra);
+        productions.extend(alternatives.into_iter().map(|body| Production {
+            head: head.clone().unwrap(),
+            body,
+        }));
+    }
+    productions.extend(extra);
+    if productions.is_empty() {
+        return Err(Error::Empty);
+    }
+
+    let mut nonterminals: Vec<String> = vec![];
+    for p in &productions {
+        if !nonterminals.contains(&p.head) {
+            nonterminals.push(p.head.clone());
+        }
+    }
+    let mut terminals: Vec<String> = vec![];
+    for p in &productions {
+        for s in &p.body {
+            if !nonterminals.contains(s) && !terminals.contains(s) {
+                terminals.push(s.clone());
+            }
+        }
+    }
+    Ok(Grammar {
+        start: productions[0].head.clone(),
+        productions,
+        nonterminals,
+        terminals,
+    })
+}
+
+pub type Sets = BTreeMap<String, BTreeSet<String>>;
+
+impl Grammar {
+    pub fn is_terminal(&self, symbol: &str) -> bool {
+        !self.nonterminals.iter().any(|x| x == symbol)
+    }
+
+    pub fn first_sets(&self) -> Sets {
+        let mut first: Sets = self
+            .nonterminals
+            .iter()
+            .map(|x| (x.clone(), BTreeSet::new()))
+            .collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for p in &self.productions {
+                let add = self.first_of(&p.body, &first);
+                let set = first.get_mut(&p.head).unwrap();
+                let before = set.len();
+                set.extend(add);
+                changed |= set.len() != before;
+            }
+        }
+        first
+    }
+
+    /// FIRST of a sentential form, contains ε when it can derive nothing
+    pub fn first_of(&self, symbols: &[String], first: &Sets) -> BTreeSet<String> {
+        let mut result = BTreeSet::new();
+        for s in symbols {
+            if self.is_terminal(s) {
+                result.insert(s.clone());
+                return result;
+            }
+            let set = &first[s];
+            result.extend(set.iter().filter(|x| *x != EPSILON).cloned());
+            if !set.contains(EPSILON) {
+                return result;
+            }
+        }
+        result.insert(EPSILON.to_owned());
+        result
+    }
+
+    pub fn follow_sets(&self, first: &Sets) -> Sets {
+        let mut follow: Sets = self
+            .nonterminals
+            .iter()
+            .map(|x| (x.clone(), BTreeSet::new()))
+            .collect();
+        follow.get_mut(&self.start).unwrap().insert(END.to_owned());
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for p in &self.productions {
+                for (idx, s) in p.body.iter().enumerate() {
+                    if self.is_terminal(s) {
+                        continue;
+                    }
+                    let rest = self.first_of(&p.body[idx + 1..], first);
+                    let mut add: BTreeSet<String> =
+                        rest.iter().filter(|x| *x != EPSILON).cloned().collect();
+                    if rest.contains(EPSILON) {
+                        add.extend(follow[&p.head].iter().cloned());
+                    }
+                    let set = follow.get_mut(s).unwrap();
+                    let before = set.len();
+                    set.extend(add);
+                    changed |= set.len() != before;
+                }
+            }
+        }
+        follow
+    }
+}
+
+/// Terminal a token is looked up by: its lexeme if the grammar uses it, otherwise its class
+pub fn terminal_for(token: &Token, terminals: &[String]) -> String {
+    let lexeme = token.token.as_str();
+    if terminals.iter().any(|t| t == lexeme) {
+        lexeme.to_owned()
+    } else {
+        format!("{:?}", token.token_type)
+    }
+}
+
+/// Pads every column of `rows` to the same width
+pub fn format_table(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|c| {
+            rows.iter()
+                .filter_map(|r| r.get(c))
+                .map(|x| x.chars().count())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    rows.iter()
+        .map(|r| {
+            r.iter()
+                .enumerate()
+                .map(|(c, x)| format!("{}{}", x, " ".repeat(widths[c] - x.chars().count())))
+                .collect::<Vec<String>>()
+                .join(" | ")
+                .trim_end()
+                .to_owned()
+                + "\n"
+        })
+        .collect()
+}
+
+pub fn format_productions(grammar: &Grammar) -> String {
+    grammar
+        .productions
+        .iter()
+        .enumerate()
+        .map(|(idx, p)| format!("\n{} {}", idx, p))
+        .collect()
+}
+
+pub fn format_sets(grammar: &Grammar, sets: &Sets) -> String {
+    grammar
+        .nonterminals
+        .iter()
+        .map(|x| {
+            let set: Vec<&str> = sets[x].iter().map(|s| s.as_str()).collect();
+            format!("\n{} {{ {} }}", x, set.join(", "))
+        })
+        .collect()
+}
+
+/// Top of the stack shown in a trace row
+pub fn format_stack(stack: &[String]) -> String {
+    const SHOWN: usize = 12;
+    if stack.len() > SHOWN {
+        format!("... {}", stack[stack.len() - SHOWN..].join(" "))
+    } else {
+        stack.join(" ")
+    }
+}
+
+/// Remaining input shown in a trace row
+pub fn format_input(tokens: &[Token], pos: usize) -> String {
+    const SHOWN: usize = 8;
+    let mut input: Vec<&str> = tokens
+        .iter()
+        .skip(pos)
+        .take(SHOWN)
+        .map(|t| t.token.as_str())
+        .collect();
+    if tokens.len() > pos + SHOWN {
+        input.push("...");
+    }
+    input.push(END);
+    input.join(" ")
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Ll1Report {
+    pub productions: String,
+    pub first: String,
+    pub follow: String,
+    /// Predictive table, cells hold production numbers
+    pub table: String,
+    pub conflicts: String,
+    /// Stack, input and action for every step of the parse
+    pub trace: String,
+    /// Parse error, empty when the program was accepted
+    pub error: String,
+}
+
+/// Computes FIRST/FOLLOW and the LL(1) table of `grammar`, then parses `tokens` with it
+pub fn ll1(grammar: &Grammar, tokens: &[Token]) -> Ll1Report {
+    let first = grammar.first_sets();
+    let follow = grammar.follow_sets(&first);
+
+    let mut table: BTreeMap<(String, String), Vec<usize>> = BTreeMap::new();
+    for (idx, p) in grammar.productions.iter().enumerate() {
+        let predict = grammar.first_of(&p.body, &first);
+        let mut lookahead: BTreeSet<String> =
+            predict.iter().filter(|x| *x != EPSILON).cloned().collect();
+        if predict.contains(EPSILON) {
+            lookahead.extend(follow[&p.head].iter().cloned());
+        }
+        for a in lookahead {
+            table.entry((p.head.clone(), a)).or_default().push(idx);
+        }
+    }
+
+    let mut columns = grammar.terminals.clone();
+    columns.push(END.to_owned());
+    let mut rows = vec![std::iter::once(String::new())
+        .chain(columns.iter().cloned())
+        .collect::<Vec<String>>()];
+    for x in &grammar.nonterminals {
+        let mut row = vec![x.clone()];
+        for a in &columns {
+            row.push(match table.get(&(x.clone(), a.clone())) {
+                Some(cell) => cell
+                    .iter()
+                    .map(|p| p.to_string())
+                    .collect::<Vec<String>>()
+                    .join(","),
+                None => String::new(),
+            });
+        }
+        rows.push(row);
+    }
+    let conflicts: String = table
+        .iter()
+        .filter(|(_, cell)| cell.len() > 1)
+        .map(|((x, a), cell)| {
+            let cell: Vec<String> = cell
+                .iter()
+                .map(|p| grammar.productions[*p].to_string())
+                .collect();
+            format!("\nM[{}, {}]: {}", x, a, cell.join(" / "))
+        })
+        .collect();
+
+    let mut trace = vec![vec![
+        "stack".to_owned(),
+        "input".to_owned(),
+        "action".to_owned(),
+    ]];
+    let mut stack = vec![END.to_owned(), grammar.start.clone()];
+    let mut pos = 0;
+    let error = loop {
+        let top = stack.last().unwrap().clone();
+        let lookahead = match tokens.get(pos) {
+            Some(t) => terminal_for(t, &grammar.terminals),
+            None => END.to_owned(),
+        };
+        let mut row = vec![format_stack(&stack), format_input(tokens, pos)];
+        if trace.len() > MAX_STEPS {
+            break format!("Parse stopped after {} steps", MAX_STEPS);
+        }
+        if top == END && lookahead == END {
+            row.push("accept".to_owned());
+            trace.push(row);
+            break String::new();
+        }
+        if grammar.is_terminal(&top) {
+            if top != lookahead {
+                row.push("error".to_owned());
+                trace.push(row);
+                break parse_error(tokens, pos, &format!("'{}'", top));
+            }
+            row.push(format!("match {}", top));
+            stack.pop();
+            pos += 1;
+        } else {
+            match table.get(&(top.clone(), lookahead)) {
+                Some(cell) => {
+                    let p = &grammar.productions[cell[0]];
+                    row.push(p.to_string());
+                    stack.pop();
+                    stack.extend(p.body.iter().rev().cloned());
+                }
+                None => {
+                    row.push("error".to_owned());
+                    trace.push(row);
+                    break parse_error(tokens, pos, &top);
+                }
+            }
+        }
+        trace.push(row);
+    };
+
+    Ll1Report {
+        productions: format_productions(grammar),
+        first: format_sets(grammar, &first),
+        follow: format_sets(grammar, &follow),
+        table: format_table(&rows),
+        conflicts,
+        trace: format_table(&trace),
+        error,
+    }
+}
+
+/// `expected` is empty when no token can follow, e.g. in a state that only a nonproductive rule reaches
+pub fn parse_error(tokens: &[Token], pos: usize, expected: &str) -> String {
+    match tokens.get(pos) {
+        Some(t) if expected.is_empty() => format!(
+            "Syntax error at {}:{}: unexpected {}",
+            t.location.line, t.location.column, t.token
+        ),
+        None if expected.is_empty() => "Syntax error: unexpected end of program".to_owned(),
+        Some(t) => format!(
+            "Syntax error at {}:{}: expected {}, found {}",
+            t.location.line, t.location.column, expected, t.token
+        ),
+        None => format!("Syntax error: expected {}, found end of program", expected),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keywords::count_tokens;
+
+    const EXPRESSIONS: &str = "E -> T E'
+E' -> + T E' | ε
+T -> F T'
+T' -> * F T' | ε
+F -> '(' E ')' | Identifier";
+
+    fn set(items: &[&str]) -> BTreeSet<String> {
+        items.iter().map(|x| x.to_string()).collect()
+    }
+
+    #[test]
+    fn first_and_follow_of_the_expression_grammar() {
+        let grammar = parse_grammar(EXPRESSIONS).unwrap();
+        assert_eq!(grammar.nonterminals, ["E", "E'", "T", "T'", "F"]);
+        assert_eq!(grammar.terminals, ["+", "*", "(", ")", "Identifier"]);
+        let first = grammar.first_sets();
+        assert_eq!(first["E"], set(&["(", "Identifier"]));
+        assert_eq!(first["E'"], set(&["+", EPSILON]));
+        assert_eq!(first["T'"], set(&["*", EPSILON]));
+        let follow = grammar.follow_sets(&first);
+        assert_eq!(follow["E"], set(&[")", END]));
+        assert_eq!(follow["E'"], set(&[")", END]));
+        assert_eq!(follow["T"], set(&["+", ")", END]));
+        assert_eq!(follow["T'"], set(&["+", ")", END]));
+        assert_eq!(follow["F"], set(&["+", "*", ")", END]));
+    }
+
+    #[test]
+    fn ll1_parses_a_program() {
+        let grammar = parse_grammar(EXPRESSIONS).unwrap();
+        let report = ll1(&grammar, &count_tokens("a + b * ( c )".to_owned()).unwrap());
+        assert_eq!(report.conflicts, "");
+        assert_eq!(report.error, "");
+        assert!(report.trace.trim_end().ends_with("accept"));
+
+        let report = ll1(&grammar, &count_tokens("a + * b".to_owned()).unwrap());
+        assert_eq!(report.error, "Syntax error at 0:5: expected T, found *");
+    }
+
+    #[test]
+    fn reports_conflicts() {
+        let grammar = parse_grammar("S -> a b | a c").unwrap();
+        let report = ll1(&grammar, &[]);
+        assert_eq!(report.conflicts, "\nM[S, a]: S -> a b / S -> a c");
+    }
+
+    #[test]
+    fn ebnf_groups_become_nonterminals() {
+        let grammar = parse_grammar("L -> x { ',' x } [ ';' ]").unwrap();
+        let productions: Vec<String> = grammar.productions.iter().map(|p| p.to_string()).collect();
+        assert_eq!(
+            productions,
+            [
+                "L -> x L_1 L_2",
+                "L_1 -> , x L_1",
+                "L_1 -> ε",
+                "L_2 -> ;",
+                "L_2 -> ε"
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_the_faulty_line() {
+        let error = parse_grammar("S -> a\nT b").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Grammar error at line 2: expected '->' after the rule name"
+        );
+        assert_eq!(
+            parse_grammar("\n").unwrap_err().to_string(),
+            "Grammar has no productions"
+        );
+    }
+}
@@ -0,0 +1,4338 @@
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+pub enum Error {
+    IncorrectIdentifier(Location),
+    IncorrectKeyword(Location),
+    IncorrectOperator(Location),
+    IncorrectConstant(Location),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::IncorrectIdentifier(l) => {
+                write!(
+                    f,
+                    "Identifier error at {}:{} ({})",
+                    l.line, l.column, l.char
+                )
+            }
+            Self::IncorrectKeyword(l) => {
+                write!(f, "Keyword error at {}:{} ({})", l.line, l.column, l.char)
+            }
+            Self::IncorrectOperator(l) => {
+                write!(f, "Operator error at {}:{} ({})", l.line, l.column, l.char)
+            }
+            Self::IncorrectConstant(l) => {
+                write!(f, "Constant error at {}:{} ({})", l.line, l.column, l.char)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, Ord, PartialOrd, PartialEq)]
+pub enum TokenType {
+    Keyword,
+    Identifier,
+    Operator,
+    ConstValue,
+    StringLiteral,
+    Separator,
+    // None,
+}
+
+/// Some keyword states have no transition into them yet
+#[derive(Debug, Clone)]
+#[allow(dead_code)]
+pub enum State {
+    // Intermediate
+    /// \t, spaces, \n
+    Whitespace,
+    Base,
+    /// '(',')','[',']', '{', '}', ';'
+    Separator(char),
+    /// Generic state for letters
+    Letter(char),
+    /// State for identifier characters
+    Identifier(char),
+    /// Fallback state for characters
+    Character(char),
+    /// Generic state for numbers
+    Number(char),
+    /// _, used for variable name starts
+    Underscore,
+    /// Anything that starts with a # is a preprocessor command
+    Preprocessor,
+
+    Operator(char),
+    KeywordEnd,
+    StringLiteral(char),
+    // Operators:
+    /// +
+    Add,
+    /// -
+    Sub,
+    /// *
+    Mul,
+    /// /
+    Div,
+    /// %
+    Mod,
+    /// <<
+    Shl,
+    /// >>
+    Shr,
+    /// &&
+    And,
+    /// ||
+    Or,
+    /// ^
+    BitXor,
+    /// &
+    BitAnd,
+    /// |
+    BitOr,
+    /// ++
+    Incr,
+    /// --
+    Decr,
+    // Comparison operators:
+    /// \<
+    LT,
+    /// \>
+    GT,
+    /// <=
+    LE,
+    /// >=
+    GE,
+    /// ==
+    Eq,
+    /// !=
+    NEq,
+    /// !
+    Neg,
+    // Assign operators:
+    /// =
+    Assign,
+    /// +=
+    AddAssign,
+    /// -=
+    SubAssign,
+    /// *=
+    MulAssign,
+    /// /=
+    DivAssign,
+    /// %=
+    ModAssign,
+    /// <\<\=
+    ShlAssign,
+    /// \>\>\=
+    ShrAssign,
+    /// &=
+    BitAndAssign,
+    /// |=
+    BitOrAssign,
+    // Special operators:
+    /// ,
+    Comma,
+    /// ->
+    Arrow,
+
+    // Keywords
+    /// asm
+    AsmS,
+    /// auto
+    AutoU,
+    AutoT,
+    /// bool
+    BoolO,
+    BoolO2,
+    /// break
+    BreakR,
+    BreakE,
+    BreakA,
+    /// case | catch
+    CaA,
+    /// case
+    CaseS,
+    /// catch
+    CatchT,
+    CatchC,
+    /// char
+    CharH,
+    CharA,
+    /// class
+    ClassL,
+    ClassA,
+    ClassS,
+    /// compl | concept | const
+    CoO,
+    /// compl
+    ComplM,
+    ComplP,
+    /// concept | const
+    ConN,
+    /// concept
+    ConceptC,
+    ConceptE,
+    ConceptP,
+    /// const
+    ConstS,
+    /// default | delete
+    DeE,
+    DefaultF,
+    DefaultA,
+    DefaultU,
+    DefaultL,
+    /// Delete
+    DeleteL,
+    DeleteE,
+    DeleteT,
+    /// do | double
+    DoO,
+    /// double
+    DoubleU,
+    DoubleB,
+    DoubleL,
+    /// else
+    ElseL,
+    ElseS,
+    /// enum
+    EnumN,
+    EnumU,
+    /// export | extern
+    ExX,
+    /// export
+    ExportP,
+    ExportO,
+    ExportR,
+    /// extern
+    ExternT,
+    ExternE,
+    ExternR,
+    /// false
+    FalseA,
+    FalseL,
+    FalseS,
+    /// float
+    FloatL,
+    FloatO,
+    FloatA,
+    /// for
+    ForO,
+    /// friend
+    FriendR,
+    FriendI,
+    FriendE,
+    FriendN,
+    /// goto
+    GotoO,
+    GotoT,
+    /// if
+    IfF,
+    /// int | inline
+    InN,
+    /// inline
+    InlineL,
+    InlineI,
+    InlineN,
+    /// int
+    /// long
+    LongO,
+    LongN,
+    /// mutable
+    MutableU,
+    MutableT,
+    MutableA,
+    MutableB,
+    MutableL,
+    /// namespace
+    NamespaceA,
+    NamespaceM,
+    NamespaceE,
+    NamespaceS,
+    NamespaceP,
+    NamespaceA2,
+    NamespaceC,
+    NewE,
+    /// nullptr
+    NullptrU,
+    NullptrL,
+    NullptrL2,
+    NullptrP,
+    NullptrT,
+    /// operator
+    OperatorP,
+    OperatorE,
+    OperatorR,
+    OperatorA,
+    OperatorT,
+    OperatorO,
+    /// private | protected
+    PrR,
+    /// private
+    PrivateI,
+    PrivateV,
+    PrivateA,
+    PrivateT,
+    /// protected
+    ProtectedO,
+    ProtectedT,
+    ProtectedE,
+    ProtectedC,
+    ProtectedT2,
+    ProtectedE2,
+    /// public
+    PublicU,
+    PublicB,
+    PublicL,
+    PublicI,
+    /// return
+    ReturnE,
+    ReturnT,
+    ReturnU,
+    ReturnR,
+    /// short
+    ShortH,
+    ShortO,
+    ShortR,
+    /// signed || sizeof
+    SiI,
+    /// signed
+    SignedG,
+    SignedN,
+    SignedE,
+    /// sizeof
+    SizeofZ,
+    SizeofE,
+    SizeofO,
+    /// static | struct
+    StT,
+    /// static
+    StaticA,
+    StaticT,
+    StaticI,
+    /// struct
+    StructR,
+    StructU,
+    StructC,
+    /// switch
+    SwitchW,
+    SwitchI,
+    SwitchT,
+    SwitchC,
+    /// template
+    TemplateE,
+    TemplateM,
+    TemplateP,
+    TemplateL,
+    TemplateA,
+    TemplateT,
+    /// this | throw
+    ThH,
+    /// this
+    ThisI,
+    /// throw
+    ThrowR,
+    ThrowO,
+    /// true | try
+    TrR,
+    /// true
+    TrueU,
+    /// try
+    /// union | unsigned
+    UnN,
+    /// union
+    UnionI,
+    UnionO,
+    /// unsigned
+    UnsignedS,
+    UnsignedI,
+    UnsignedG,
+    UnsignedN,
+    UnsignedE,
+    /// using
+    UsingS,
+    UsingI,
+    UsingN,
+    /// virtual
+    VirtualI,
+    VirtualR,
+    VirtualT,
+    VirtualU,
+    VirtualA,
+    /// void
+    VoidO,
+    VoidI,
+    /// while
+    WhileH,
+    WhileI,
+    WhileL,
+    NumberAfterExponent(char),
+    NumberAfterDot(char),
+    NumberAfterExponentWithSign(char),
+    OperatorEnd,
+}
+fn is_separator(c: char) -> bool {
+    matches!(c, '(' | ')' | '[' | ']' | '{' | '}' | ';' | ',' | ':')
+}
+fn is_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\n' | '\t')
+}
+
+#[derive(Clone, Debug)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub char: char,
+}
+
+#[derive(Clone, Debug)]
+pub struct Token {
+    pub token_type: TokenType,
+    pub token: String,
+    /// Position of the first character of the token
+    pub location: Location,
+}
+
+/// What reading one character does to the scanner
+struct Move {
+    state: State,
+    /// The buffer holds a whole token
+    is_writable: bool,
+    /// The character belongs to the next token and is read again
+    reread: bool,
+}
+
+/// One step of `count_tokens` from `state` on `current`
+fn transition(
+    mut state: State,
+    current: char,
+    location: &mut Location,
+    buff: &mut String,
+) -> Result<Move, Error> {
+    let mut is_writable = false;
+    let mut reread = false;
+    match state {
+        State::Whitespace => {
+            if current == '\n' {
+                location.column = 0;
+                location.line += 1;
+            };
+            state = match current {
+                ' ' | '\n' | '\t' => State::Whitespace,
+                '+' => {
+                    buff.push(current);
+                    State::Add
+                }
+                '-' => {
+                    buff.push(current);
+                    State::Sub
+                }
+                '*' => {
+                    buff.push(current);
+                    State::Mul
+                }
+                '/' => {
+                    buff.push(current);
+                    State::Div
+                }
+                '%' => {
+                    buff.push(current);
+                    State::Mod
+                }
+                '<' => {
+                    buff.push(current);
+                    State::LT
+                }
+                '>' => {
+                    buff.push(current);
+                    State::GT
+                }
+                '=' => {
+                    buff.push(current);
+                    State::Assign
+                }
+                '!' => {
+                    buff.push(current);
+                    State::Neg
+                }
+                '&' => {
+                    buff.push(current);
+                    State::BitAnd
+                }
+                '|' => {
+                    buff.push(current);
+                    State::BitOr
+                }
+                '^' => {
+                    buff.push(current);
+                    State::BitXor
+                }
+                ',' => {
+                    buff.push(current);
+                    is_writable = true;
+                    State::Separator(current)
+                }
+                '_' => {
+                    buff.push(current);
+                    State::Underscore
+                }
+                c if c.is_alphabetic() => {
+                    buff.push(current);
+                    State::Letter(c)
+                }
+                c if c.is_numeric() => {
+                    buff.push(current);
+                    State::Number(c)
+                }
+                c if is_separator(c) => {
+                    buff.push(current);
+                    is_writable = true;
+                    State::Separator(current)
+                }
+                '#' => {
+                    buff.push(current);
+                    State::Preprocessor
+                }
+                _ => State::Character(current),
+            };
+        }
+        State::Preprocessor => match current {
+            c if c.is_alphanumeric() => {
+                state = State::Preprocessor;
+                buff.push(c);
+            }
+            c if is_whitespace(c) => {
+                is_writable = true;
+                reread = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::Underscore => match current {
+            c if c.is_alphanumeric() => {
+                buff.push(c);
+                state = State::Identifier(c);
+            }
+            c if is_whitespace(c) => {
+                is_writable = true;
+                state = State::Whitespace
+            }
+            _ => return Err(Error::IncorrectIdentifier(location.clone())),
+        },
+        State::Add => match current {
+            '=' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::AddAssign
+            }
+            '+' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::Incr
+            }
+            c if c.is_numeric() => {
+                buff.push(current);
+                state = State::Number(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => {
+                reread = true;
+                is_writable = true;
+                // return Err(Error::IncorrectOperator(location.clone()));
+            }
+        },
+        State::Sub => match current {
+            '=' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::SubAssign
+            }
+            '-' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::Decr
+            }
+            '>' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::Arrow
+            }
+            c if c.is_numeric() => {
+                buff.push(current);
+                state = State::Number(c);
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => {
+                reread = true;
+                is_writable = true;
+                // return Err(Error::IncorrectOperator(location.clone()));
+            }
+        },
+        State::Mul => match current {
+            '=' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::MulAssign
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => {
+                reread = true;
+                is_writable = true;
+                // return Err(Error::IncorrectOperator(location.clone()));
+            }
+        },
+        State::Div => match current {
+            '=' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::DivAssign
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => {
+                reread = true;
+                is_writable = true;
+                // return Err(Error::IncorrectOperator(location.clone()));
+            }
+        },
+        State::Mod => match current {
+            '=' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::ModAssign
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => {
+                reread = true;
+                is_writable = true;
+                // return Err(Error::IncorrectOperator(location.clone()));
+            }
+        },
+        State::Shl => match current {
+            '=' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::ShlAssign
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => {
+                // reread = true;
+                // is_writable = true;
+                // return Err(Error::IncorrectOperator(location.clone()));
+            }
+        },
+        State::Shr => match current {
+            '=' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::ShrAssign
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => {
+                reread = true;
+                is_writable = true;
+                // return Err(Error::IncorrectOperator(location.clone()));
+            }
+        },
+        State::And => match current {
+            '&' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::BitAnd;
+            }
+            '=' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::BitAndAssign;
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => {
+                reread = true;
+                is_writable = true;
+                // return Err(Error::IncorrectOperator(location.clone()));
+            }
+        },
+        State::Or => match current {
+            '|' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::BitOr;
+            }
+            '=' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::BitOrAssign
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectOperator(location.clone())),
+        },
+        State::BitXor => match current {
+            c if is_whitespace(c) || is_separator(c) => {
+                buff.push(current);
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectOperator(location.clone())),
+        },
+        State::BitAnd => match current {
+            '=' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::BitAndAssign
+            }
+            '&' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::And
+            }
+            c if is_whitespace(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectOperator(location.clone())),
+        },
+        State::BitOr => match current {
+            '=' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::BitOrAssign
+            }
+            '|' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::Or
+            }
+            c if is_whitespace(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectOperator(location.clone())),
+        },
+        State::Incr => match current {
+            c if is_whitespace(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectOperator(location.clone())),
+        },
+        State::Decr => match current {
+            c if is_whitespace(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectOperator(location.clone())),
+        },
+        State::LT => match current {
+            '=' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::LE
+            }
+            '<' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::Shl
+            }
+            c if is_whitespace(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectOperator(location.clone())),
+        },
+        State::GT => match current {
+            '=' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::GE
+            }
+            '>' => {
+                buff.push(current);
+                is_writable = true;
+                state = State::Shr
+            }
+            c if is_whitespace(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectOperator(location.clone())),
+        },
+        State::LE => match current {
+            c if is_whitespace(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectOperator(location.clone())),
+        },
+        State::GE => match current {
+            c if is_whitespace(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectOperator(location.clone())),
+        },
+        State::Eq => match current {
+            c if is_whitespace(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectOperator(location.clone())),
+        },
+        State::NEq => match current {
+            c if is_whitespace(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectOperator(location.clone())),
+        },
+        State::Neg => match current {
+            '=' => {
+                buff.push(current);
+                state = State::NEq
+            }
+            c if is_whitespace(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectOperator(location.clone())),
+        },
+        State::Assign => {
+            match current {
+                '=' => {
+                    buff.push(current);
+                    state = State::Eq
+                }
+                c if is_whitespace(c) => {
+                    reread = true;
+                    is_writable = true;
+                }
+                _ => {
+                    reread = true;
+                    is_writable = true;
+                } // return Err(Error::IncorrectOperator(location.clone())),
+            };
+        }
+        State::AddAssign => {
+            match current {
+                c if is_whitespace(c) => {
+                    reread = true;
+                    is_writable = true;
+                }
+                _ => return Err(Error::IncorrectOperator(location.clone())),
+            };
+        }
+        State::SubAssign => {
+            match current {
+                c if is_whitespace(c) => {
+                    reread = true;
+                    is_writable = true;
+                }
+                _ => return Err(Error::IncorrectOperator(location.clone())),
+            };
+        }
+        State::MulAssign => {
+            match current {
+                c if is_whitespace(c) => {
+                    reread = true;
+                    is_writable = true;
+                }
+                _ => return Err(Error::IncorrectOperator(location.clone())),
+            };
+        }
+        State::DivAssign => {
+            match current {
+                c if is_whitespace(c) => {
+                    reread = true;
+                    is_writable = true;
+                }
+                _ => return Err(Error::IncorrectOperator(location.clone())),
+            };
+        }
+        State::ModAssign => {
+            match current {
+                c if is_whitespace(c) => {
+                    reread = true;
+                    is_writable = true;
+                }
+                _ => return Err(Error::IncorrectOperator(location.clone())),
+            };
+        }
+        State::ShlAssign => {
+            match current {
+                c if is_whitespace(c) => {
+                    reread = true;
+                    is_writable = true;
+                }
+                _ => return Err(Error::IncorrectOperator(location.clone())),
+            };
+        }
+        State::ShrAssign => {
+            match current {
+                c if is_whitespace(c) => {
+                    reread = true;
+                    is_writable = true;
+                }
+                _ => return Err(Error::IncorrectOperator(location.clone())),
+            };
+        }
+        State::BitAndAssign => match current {
+            c if is_whitespace(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectOperator(location.clone())),
+        },
+        State::BitOrAssign => match current {
+            c if is_whitespace(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectOperator(location.clone())),
+        },
+        State::Comma => {
+            match current {
+                ' ' | '\n' | '\t' => state = State::Whitespace,
+                _ => return Err(Error::IncorrectOperator(location.clone())),
+            };
+        }
+        State::Arrow => {
+            match current {
+                c if is_whitespace(c) => {
+                    reread = true;
+                    is_writable = true;
+                }
+                _ => return Err(Error::IncorrectOperator(location.clone())),
+            };
+        }
+        State::Letter('a') => {
+            match current {
+                's' => {
+                    buff.push(current);
+                    state = State::AsmS
+                }
+                'u' => {
+                    buff.push(current);
+                    state = State::AutoU;
+                }
+                c if c.is_alphanumeric() || c == '_' => {
+                    buff.push(current);
+                    state = State::Identifier(c)
+                }
+                c if is_whitespace(c) || is_separator(c) => {
+                    reread = true;
+                    is_writable = true;
+                }
+                _ => return Err(Error::IncorrectOperator(location.clone())),
+            };
+        }
+        State::AsmS => {
+            match current {
+                'm' => {
+                    buff.push(current);
+                    state = State::KeywordEnd
+                }
+                c if c.is_alphanumeric() => {
+                    buff.push(current);
+                    state = State::Identifier(c)
+                }
+                c if is_whitespace(c) || is_separator(c) => {
+                    reread = true;
+                    is_writable = true;
+                }
+                _ => return Err(Error::IncorrectOperator(location.clone())),
+            };
+        }
+        State::AutoU => {
+            match current {
+                't' => {
+                    buff.push(current);
+                    state = State::AutoT;
+                }
+                c if c.is_alphanumeric() => {
+                    buff.push(current);
+                    state = State::Identifier(c);
+                }
+                c if is_whitespace(c) || is_separator(c) => {
+                    reread = true;
+                    is_writable = true;
+                }
+                _ => return Err(Error::IncorrectOperator(location.clone())),
+            };
+        }
+        State::AutoT => {
+            match current {
+                'o' => {
+                    buff.push(current);
+                    state = State::KeywordEnd
+                }
+                c if c.is_alphanumeric() || c == '_' => state = State::Identifier(c),
+                ' ' | '\n' | '\t' => state = State::Whitespace,
+
+                _ => return Err(Error::IncorrectOperator(location.clone())),
+            };
+        }
+        State::Letter('b') => match current {
+            'o' => {
+                buff.push(current);
+                state = State::BoolO
+            }
+            'r' => {
+                buff.push(current);
+                state = State::BreakR
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            _ => {
+                state = State::Character(current);
+            }
+        },
+        State::BoolO => match current {
+            'o' => {
+                state = State::BoolO2;
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                state = State::Identifier(c);
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => {
+                state = State::Character(current);
+            }
+        },
+        State::BoolO2 => {
+            match current {
+                'l' => {
+                    buff.push(current);
+                    state = State::KeywordEnd
+                }
+                c if c.is_alphanumeric() => state = State::Letter(c),
+                c if is_whitespace(c) || is_separator(c) => {
+                    reread = true;
+                    is_writable = true;
+                }
+                _ => return Err(Error::IncorrectKeyword(location.clone())),
+            };
+        }
+        State::BreakR => match current {
+            'e' => {
+                buff.push(current);
+                state = State::BreakE
+            }
+            c if c.is_alphanumeric() => state = State::Identifier(c),
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::BreakE => match current {
+            'a' => {
+                buff.push(current);
+                state = State::BreakA
+            }
+            c if c.is_alphanumeric() => state = State::Letter(c),
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::BreakA => match current {
+            'k' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() => state = State::Letter(c),
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::Letter('c') => match current {
+            'a' => {
+                buff.push(current);
+                state = State::CaA
+            }
+            'h' => {
+                buff.push(current);
+                state = State::CharH
+            }
+            'o' => {
+                buff.push(current);
+                state = State::CoO
+            }
+            c if c.is_alphanumeric() => state = State::Letter(current),
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::CaA => match current {
+            's' => {
+                buff.push(current);
+                state = State::CaseS
+            }
+            't' => {
+                buff.push(current);
+                state = State::CatchT
+            }
+            c if c.is_alphanumeric() => state = State::Identifier(c),
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::CaseS => match current {
+            'e' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() => state = State::Identifier(c),
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::CatchT => match current {
+            'c' => {
+                buff.push(current);
+                state = State::CatchC
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::CatchC => match current {
+            'h' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::CharH => match current {
+            'a' => {
+                buff.push(current);
+                state = State::CharA
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::CharA => match current {
+            'r' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ClassL => match current {
+            'a' => {
+                buff.push(current);
+                state = State::ClassA
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ClassA => match current {
+            's' => {
+                buff.push(current);
+                state = State::ClassS
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ClassS => match current {
+            's' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::CoO => match current {
+            'm' => {
+                buff.push(current);
+                state = State::ComplM
+            }
+            'n' => {
+                buff.push(current);
+                state = State::ConN
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ComplM => match current {
+            'p' => {
+                buff.push(current);
+                state = State::ComplP
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ComplP => match current {
+            'l' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ConN => match current {
+            'c' => {
+                buff.push(current);
+                state = State::ConceptC
+            }
+            's' => {
+                buff.push(current);
+                state = State::ConstS
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ConceptC => match current {
+            'e' => {
+                buff.push(current);
+                state = State::ConceptE
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ConceptE => match current {
+            'p' => {
+                buff.push(current);
+                state = State::ConceptP
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ConceptP => match current {
+            't' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ConstS => match current {
+            't' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::Letter('d') => match current {
+            'e' => {
+                buff.push(current);
+                state = State::DeE
+            }
+            'o' => {
+                buff.push(current);
+                state = State::DoO
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::DeE => match current {
+            'f' => {
+                buff.push(current);
+                state = State::DefaultF
+            }
+            'l' => {
+                buff.push(current);
+                state = State::DeleteE
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::DefaultF => match current {
+            'a' => {
+                buff.push(current);
+                state = State::DefaultF
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::DefaultA => match current {
+            'u' => {
+                buff.push(current);
+                state = State::DefaultU
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::DefaultU => match current {
+            'l' => {
+                buff.push(current);
+                state = State::DefaultL
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::DefaultL => match current {
+            't' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::DeleteL => match current {
+            'e' => {
+                buff.push(current);
+                state = State::DeleteE
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::DeleteE => match current {
+            't' => {
+                buff.push(current);
+                state = State::DeleteT
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::DeleteT => match current {
+            'e' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::DoO => match current {
+            'u' => {
+                buff.push(current);
+                state = State::DoubleU
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::DoubleU => match current {
+            'b' => {
+                buff.push(current);
+                state = State::DoubleB
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::DoubleB => match current {
+            'l' => {
+                buff.push(current);
+                state = State::DoubleL
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::DoubleL => match current {
+            'e' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::Letter('e') => match current {
+            'l' => {
+                buff.push(current);
+                state = State::ElseL
+            }
+            'n' => {
+                buff.push(current);
+                state = State::EnumN
+            }
+            'x' => {
+                buff.push(current);
+                state = State::ExX
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ElseL => match current {
+            's' => {
+                buff.push(current);
+                state = State::ElseS
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ElseS => match current {
+            'e' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::EnumN => match current {
+            'u' => {
+                buff.push(current);
+                state = State::EnumU
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::EnumU => match current {
+            'm' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        // State::EnumM => {
+        //     match current {
+        //
+        //         c if c.is_alphanumeric() || c == '_' => { buff.push(current); state = State::Identifier(c) },
+        //         c if is_whitespace(c) || is_separator(c) => {
+        //             reread = true;
+        //             is_writable = true;
+        //         }
+        //         _ => return Err(Error::IncorrectKeyword(location.clone()))
+        //     }
+        // }
+        // State::ExX => {
+        //     match current {
+        //         c if c.is_alphanumeric() || c == '_' => { buff.push(current); state = State::Identifier(c) },
+        //         c if is_whitespace(c) || is_separator(c) => {
+        //             reread = true;
+        //             is_writable = true;
+        //         }
+        //         _ => return Err(Error::IncorrectKeyword(location.clone()))
+        //     }
+        // }
+        // State::ExportP => {
+        //     match current {
+        //         c if c.is_alphanumeric() || c == '_' => { buff.push(current); state = State::Identifier(c) },
+        //         c if is_whitespace(c) || is_separator(c) => {
+        //             reread = true;
+        //             is_writable = true;
+        //         }
+        //         _ => return Err(Error::IncorrectKeyword(location.clone()))
+        //     }
+        // }
+        // State::ExportO => {
+        //     match current {
+        //         c if c.is_alphanumeric() || c == '_' => { buff.push(current); state = State::Identifier(c) },
+        //         c if is_whitespace(c) || is_separator(c) => {
+        //             reread = true;
+        //             is_writable = true;
+        //         }
+        //         _ => return Err(Error::IncorrectKeyword(location.clone()))
+        //     }
+        // }
+        // State::ExportR => {
+        //     match current {
+        //         c if c.is_alphanumeric() || c == '_' => { buff.push(current); state = State::Identifier(c) },
+        //         c if is_whitespace(c) || is_separator(c) => {
+        //             reread = true;
+        //             is_writable = true;
+        //         }
+        //         _ => return Err(Error::IncorrectKeyword(location.clone()))
+        //     }
+        // }
+        // State::ExportT => {
+        //     match current {
+        //         c if c.is_alphanumeric() || c == '_' => { buff.push(current); state = State::Identifier(c) },
+        //         c if is_whitespace(c) || is_separator(c) => {
+        //             reread = true;
+        //             is_writable = true;
+        //         }
+        //         _ => return Err(Error::IncorrectKeyword(location.clone()))
+        //     }
+        // }
+        // State::ExternT => {
+        //     match current {
+        //         c if c.is_alphanumeric() || c == '_' => { buff.push(current); state = State::Identifier(c) },
+        //         c if is_whitespace(c) || is_separator(c) => {
+        //             reread = true;
+        //             is_writable = true;
+        //         }
+        //         _ => return Err(Error::IncorrectKeyword(location.clone()))
+        //     }
+        // }
+        // State::ExternE => {
+        //     match current {
+        //         c if c.is_alphanumeric() || c == '_' => { buff.push(current); state = State::Identifier(c) },
+        //         c if is_whitespace(c) || is_separator(c) => {
+        //             reread = true;
+        //             is_writable = true;
+        //         }
+        //         _ => return Err(Error::IncorrectKeyword(location.clone()))
+        //     }
+        // }
+        // State::ExternR => {
+        //     match current {
+        //         c if c.is_alphanumeric() || c == '_' => { buff.push(current); state = State::Identifier(c) },
+        //         c if is_whitespace(c) || is_separator(c) => {
+        //             reread = true;
+        //             is_writable = true;
+        //         }
+        //         _ => return Err(Error::IncorrectKeyword(location.clone()))
+        //     }
+        // }
+        // State::ExternN => {
+        //     match current {
+        //         c if c.is_alphanumeric() || c == '_' => { buff.push(current); state = State::Identifier(c) },
+        //         c if is_whitespace(c) || is_separator(c) => {
+        //             reread = true;
+        //             is_writable = true;
+        //         }
+        //         _ => return Err(Error::IncorrectKeyword(location.clone()))
+        //     }
+        // }
+        State::Letter('f') => match current {
+            'a' => {
+                buff.push(current);
+                state = State::FalseA
+            }
+            'l' => {
+                buff.push(current);
+                state = State::FloatL
+            }
+            'o' => {
+                buff.push(current);
+                state = State::ForO
+            }
+            'r' => {
+                buff.push(current);
+                state = State::FriendR
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::FalseA => match current {
+            'l' => {
+                buff.push(current);
+                state = State::FalseL
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::FalseL => match current {
+            's' => {
+                buff.push(current);
+                state = State::FalseS
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::FalseS => match current {
+            'e' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::FloatL => match current {
+            'o' => {
+                buff.push(current);
+                state = State::FloatO
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::FloatO => match current {
+            'a' => {
+                buff.push(current);
+                state = State::FloatA
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::FloatA => match current {
+            't' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ForO => match current {
+            'r' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::FriendR => match current {
+            'i' => {
+                buff.push(current);
+                state = State::FriendI
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::FriendI => match current {
+            'e' => {
+                buff.push(current);
+                state = State::FriendE
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::FriendE => match current {
+            'n' => {
+                buff.push(current);
+                state = State::FriendN
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::FriendN => match current {
+            'd' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::Letter('g') => match current {
+            'o' => {
+                buff.push(current);
+                state = State::GotoO
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::GotoO => match current {
+            't' => {
+                buff.push(current);
+                state = State::GotoT
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::GotoT => match current {
+            'o' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::Letter('i') => match current {
+            'f' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            'n' => {
+                buff.push(current);
+                state = State::InN
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::IfF => match current {
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::InN => match current {
+            't' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            'l' => {
+                buff.push(current);
+                state = State::InlineL
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::InlineL => match current {
+            'i' => {
+                buff.push(current);
+                state = State::InlineI
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::InlineI => match current {
+            'n' => {
+                buff.push(current);
+                state = State::InlineN
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::InlineN => match current {
+            'e' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::Letter('l') => match current {
+            'o' => {
+                buff.push(current);
+                state = State::LongO
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::LongO => match current {
+            'n' => {
+                buff.push(current);
+                state = State::LongN
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::LongN => match current {
+            'g' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::Letter('m') => match current {
+            'u' => {
+                buff.push(current);
+                state = State::MutableU
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::MutableU => match current {
+            't' => {
+                buff.push(current);
+                state = State::MutableT
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::MutableT => match current {
+            'a' => {
+                buff.push(current);
+                state = State::MutableA
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::MutableA => match current {
+            'b' => {
+                buff.push(current);
+                state = State::MutableB
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::MutableB => match current {
+            'l' => {
+                buff.push(current);
+                state = State::MutableL
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::MutableL => match current {
+            'e' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::Letter('n') => match current {
+            'a' => {
+                buff.push(current);
+                state = State::NamespaceA
+            }
+            'e' => {
+                buff.push(current);
+                state = State::NewE
+            }
+            'u' => {
+                buff.push(current);
+                state = State::NullptrU
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::NamespaceA => match current {
+            'm' => {
+                buff.push(current);
+                state = State::NamespaceM
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::NamespaceM => match current {
+            'e' => {
+                buff.push(current);
+                state = State::NamespaceE
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::NamespaceE => match current {
+            's' => {
+                buff.push(current);
+                state = State::NamespaceS
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::NamespaceS => match current {
+            'p' => {
+                buff.push(current);
+                state = State::NamespaceP
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::NamespaceP => match current {
+            'a' => {
+                buff.push(current);
+                state = State::NamespaceA2
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::NamespaceA2 => match current {
+            'c' => {
+                buff.push(current);
+                state = State::NamespaceC
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::NamespaceC => match current {
+            'e' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::NewE => match current {
+            'w' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::NullptrU => match current {
+            'l' => {
+                buff.push(current);
+                state = State::NullptrL
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::NullptrL => match current {
+            'l' => {
+                buff.push(current);
+                state = State::NullptrL2
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::NullptrL2 => match current {
+            'p' => {
+                buff.push(current);
+                state = State::NullptrP
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::NullptrP => match current {
+            't' => {
+                buff.push(current);
+                state = State::NullptrT
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::NullptrT => match current {
+            'r' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::Letter('o') => match current {
+            'p' => {
+                buff.push(current);
+                state = State::OperatorP
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::OperatorP => match current {
+            'e' => {
+                buff.push(current);
+                state = State::OperatorE
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::OperatorE => match current {
+            'r' => {
+                buff.push(current);
+                state = State::OperatorR
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::OperatorR => match current {
+            'a' => {
+                buff.push(current);
+                state = State::OperatorA
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::OperatorA => match current {
+            't' => {
+                buff.push(current);
+                state = State::OperatorT
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::OperatorT => match current {
+            'o' => {
+                buff.push(current);
+                state = State::OperatorO
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::OperatorO => match current {
+            'r' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::Letter('p') => match current {
+            'r' => {
+                buff.push(current);
+                state = State::PrR
+            }
+            'u' => {
+                buff.push(current);
+                state = State::PublicU
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::PrR => match current {
+            'o' => {
+                buff.push(current);
+                state = State::ProtectedO
+            }
+            'i' => {
+                buff.push(current);
+                state = State::PrivateI
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::PrivateI => match current {
+            'v' => {
+                buff.push(current);
+                state = State::PrivateV
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::PrivateV => match current {
+            'a' => {
+                buff.push(current);
+                state = State::PrivateA
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::PrivateA => match current {
+            't' => {
+                buff.push(current);
+                state = State::PrivateT
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::PrivateT => match current {
+            'e' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ProtectedO => match current {
+            't' => {
+                buff.push(current);
+                state = State::ProtectedT
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ProtectedT => match current {
+            'e' => {
+                buff.push(current);
+                state = State::ProtectedC
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ProtectedE => match current {
+            'c' => {
+                buff.push(current);
+                state = State::ProtectedC
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ProtectedC => match current {
+            't' => {
+                buff.push(current);
+                state = State::ProtectedT2
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ProtectedT2 => match current {
+            'e' => {
+                buff.push(current);
+                state = State::ProtectedE2
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ProtectedE2 => match current {
+            'd' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::PublicU => match current {
+            'b' => {
+                buff.push(current);
+                state = State::PublicB
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::PublicB => match current {
+            'l' => {
+                buff.push(current);
+                state = State::PublicL
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::PublicL => match current {
+            'i' => {
+                buff.push(current);
+                state = State::PublicI
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::PublicI => match current {
+            'c' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::Letter('r') => match current {
+            'e' => {
+                buff.push(current);
+                state = State::ReturnE
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ReturnE => match current {
+            't' => {
+                buff.push(current);
+                state = State::ReturnT
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ReturnT => match current {
+            'u' => {
+                buff.push(current);
+                state = State::ReturnU
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ReturnU => match current {
+            'r' => {
+                buff.push(current);
+                state = State::ReturnR
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ReturnR => match current {
+            'n' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::Letter('s') => match current {
+            'h' => {
+                buff.push(current);
+                state = State::ShortH
+            }
+            'i' => {
+                buff.push(current);
+                state = State::SiI
+            }
+            't' => {
+                buff.push(current);
+                state = State::StT
+            }
+            'w' => {
+                buff.push(current);
+                state = State::SwitchW
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ShortH => match current {
+            'o' => {
+                buff.push(current);
+                state = State::ShortO
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ShortO => match current {
+            'r' => {
+                buff.push(current);
+                state = State::ShortR
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ShortR => match current {
+            't' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::SiI => match current {
+            'g' => {
+                buff.push(current);
+                state = State::SignedG
+            }
+            'z' => {
+                buff.push(current);
+                state = State::SizeofZ
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::SignedG => match current {
+            'n' => {
+                buff.push(current);
+                state = State::SignedN
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::SignedN => match current {
+            'e' => {
+                buff.push(current);
+                state = State::SignedE
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::SignedE => match current {
+            'd' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::SizeofZ => match current {
+            'e' => {
+                buff.push(current);
+                state = State::SizeofE
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::SizeofE => match current {
+            'o' => {
+                buff.push(current);
+                state = State::SizeofO
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::SizeofO => match current {
+            'f' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::StT => match current {
+            'a' => {
+                buff.push(current);
+                state = State::StaticA
+            }
+            'r' => {
+                buff.push(current);
+                state = State::StructR
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::StaticA => match current {
+            't' => {
+                buff.push(current);
+                state = State::StaticT
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::StaticT => match current {
+            'i' => {
+                buff.push(current);
+                state = State::StaticI
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::StaticI => match current {
+            'c' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::StructR => match current {
+            'u' => {
+                buff.push(current);
+                state = State::StructU
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::StructU => match current {
+            'c' => {
+                buff.push(current);
+                state = State::StructC
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::StructC => match current {
+            't' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::SwitchW => match current {
+            'i' => {
+                buff.push(current);
+                state = State::SwitchI
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::SwitchI => match current {
+            't' => {
+                buff.push(current);
+                state = State::SwitchT
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::SwitchT => match current {
+            'c' => {
+                buff.push(current);
+                state = State::SwitchC
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::SwitchC => match current {
+            'h' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::Letter('t') => match current {
+            'e' => {
+                buff.push(current);
+                state = State::TemplateE
+            }
+            'h' => {
+                buff.push(current);
+                state = State::ThH
+            }
+            'r' => {
+                buff.push(current);
+                state = State::TrR
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::TemplateE => match current {
+            'm' => {
+                buff.push(current);
+                state = State::TemplateM
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::TemplateM => match current {
+            'p' => {
+                buff.push(current);
+                state = State::TemplateP
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::TemplateP => match current {
+            'l' => {
+                buff.push(current);
+                state = State::TemplateL
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::TemplateL => match current {
+            'a' => {
+                buff.push(current);
+                state = State::TemplateA
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::TemplateA => match current {
+            't' => {
+                buff.push(current);
+                state = State::TemplateT
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::TemplateT => match current {
+            'e' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ThH => match current {
+            'i' => {
+                buff.push(current);
+                state = State::ThisI
+            }
+            'r' => {
+                buff.push(current);
+                state = State::ThrowR
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ThisI => match current {
+            's' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ThrowR => match current {
+            'o' => {
+                buff.push(current);
+                state = State::ThrowO
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::ThrowO => match current {
+            'w' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        // State::ThrowW => {
+        //     match current {
+        //         c if c.is_alphanumeric() || c == '_' => { buff.push(current); state = State::Identifier(c) },
+        //         c if is_whitespace(c) || is_separator(c) => {
+        //             reread = true;
+        //             is_writable = true;
+        //         }
+        //         _ => return Err(Error::IncorrectKeyword(location.clone()))
+        //     }
+        // }
+        State::TrR => match current {
+            'y' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            'u' => {
+                buff.push(current);
+                state = State::TrueU
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::TrueU => match current {
+            'e' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::Letter('u') => match current {
+            'n' => {
+                buff.push(current);
+                state = State::UnN
+            }
+            's' => {
+                buff.push(current);
+                state = State::UsingS
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::UnN => match current {
+            'i' => {
+                buff.push(current);
+                state = State::UnionI
+            }
+            's' => {
+                buff.push(current);
+                state = State::UnsignedS
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::UnionI => match current {
+            'o' => {
+                buff.push(current);
+                state = State::UnionO
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::UnionO => match current {
+            'n' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        // State::UnionN => {
+        //     match current {
+        //         c if c.is_alphanumeric() || c == '_' => { buff.push(current); state = State::Identifier(c) },
+        //         c if is_whitespace(c) || is_separator(c) => {
+        //             reread = true;
+        //             is_writable = true;
+        //         }
+        //         _ => return Err(Error::IncorrectKeyword(location.clone()))
+        //     }
+        // }
+        State::UnsignedS => match current {
+            'i' => {
+                buff.push(current);
+                state = State::UnsignedI
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::UnsignedI => match current {
+            'g' => {
+                buff.push(current);
+                state = State::UnsignedG
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::UnsignedG => match current {
+            'n' => {
+                buff.push(current);
+                state = State::UnsignedN
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::UnsignedN => match current {
+            'e' => {
+                buff.push(current);
+                state = State::UnsignedE
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::UnsignedE => match current {
+            'd' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        // State::UnsignedD => {
+        //     match current {
+        //         c if c.is_alphanumeric() || c == '_' => { buff.push(current); state = State::Identifier(c) },
+        //         c if is_whitespace(c) || is_separator(c) => {
+        //             reread = true;
+        //             is_writable = true;
+        //         }
+        //         _ => return Err(Error::IncorrectKeyword(location.clone()))
+        //     }
+        // }
+        State::UsingS => match current {
+            'i' => {
+                buff.push(current);
+                state = State::UsingI
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::UsingI => match current {
+            'n' => {
+                buff.push(current);
+                state = State::UsingN
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::UsingN => match current {
+            'g' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        // State::UsingG => {
+        //     match current {
+        //         c if c.is_alphanumeric() || c == '_' => { buff.push(current); state = State::Identifier(c) },
+        //         c if is_whitespace(c) || is_separator(c) => {
+        //             reread = true;
+        //             is_writable = true;
+        //         }
+        //         _ => return Err(Error::IncorrectKeyword(location.clone()))
+        //     }
+        // }
+        State::Letter('v') => match current {
+            'i' => {
+                buff.push(current);
+                state = State::VirtualI
+            }
+            'o' => {
+                buff.push(current);
+                state = State::VoidO
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::VirtualI => match current {
+            'r' => {
+                buff.push(current);
+                state = State::VirtualR
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::VirtualR => match current {
+            't' => {
+                buff.push(current);
+                state = State::VirtualT
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::VirtualT => match current {
+            'u' => {
+                buff.push(current);
+                state = State::VirtualU
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::VirtualU => match current {
+            'a' => {
+                buff.push(current);
+                state = State::VirtualA
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::VirtualA => match current {
+            'l' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        // State::VirtualL => {
+        //     match current {
+        //         c if c.is_alphanumeric() || c == '_' => { buff.push(current); state = State::Identifier(c) },
+        //         c if is_whitespace(c) || is_separator(c) => {
+        //             reread = true;
+        //             is_writable = true;
+        //         }
+        //         _ => return Err(Error::IncorrectKeyword(location.clone()))
+        //     }
+        // }
+        State::VoidO => match current {
+            'i' => {
+                buff.push(current);
+                state = State::VoidI
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::VoidI => match current {
+            'd' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        // State::VoidD => {
+        //     match current {
+        //         c if c.is_alphanumeric() || c == '_' => { buff.push(current); state = State::Identifier(c) },
+        //         c if is_whitespace(c) || is_separator(c) => {
+        //             reread = true;
+        //             is_writable = true;
+        //         }
+        //         _ => return Err(Error::IncorrectKeyword(location.clone()))
+        //     }
+        // }
+        State::Letter('w') => match current {
+            'h' => {
+                buff.push(current);
+                state = State::WhileH
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::WhileH => match current {
+            'h' => {
+                buff.push(current);
+                state = State::WhileI
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::WhileI => match current {
+            'l' => {
+                buff.push(current);
+                state = State::WhileL
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::WhileL => match current {
+            'e' => {
+                buff.push(current);
+                state = State::KeywordEnd
+            }
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        // State::WhileE => {
+        //     match current {
+        //         c if c.is_alphanumeric() || c == '_' => { buff.push(current); state = State::Identifier(c) },
+        //         c if is_whitespace(c) || is_separator(c) => {
+        //             reread = true;
+        //             is_writable = true;
+        //         }
+        //         _ => return Err(Error::IncorrectKeyword(location.clone()))
+        //     }
+        // }
+        State::KeywordEnd => match current {
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+
+        State::Separator(_) => match current {
+            c if is_whitespace(c) => {
+                reread = true;
+                is_writable = true;
+                state = State::Whitespace
+            }
+            c if is_separator(c) => {
+                reread = true;
+                is_writable = true;
+                state = State::Separator(current)
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::Identifier(_) => match current {
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectIdentifier(location.clone())),
+        },
+        State::Character('"') => {
+            buff.push(current);
+            state = State::StringLiteral(current);
+        }
+        State::StringLiteral('"') => match current {
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::StringLiteral(_) => match current {
+            '"' => state = State::StringLiteral('"'),
+            _ => {
+                buff.push(current);
+                state = State::StringLiteral(current);
+            }
+        },
+        State::Number('e') | State::Number('E') => match current {
+            c if c.is_numeric() || c == '_' => {
+                buff.push(c);
+                state = State::NumberAfterExponent(c);
+            }
+            '-' | '+' => {
+                buff.push(current);
+                state = State::NumberAfterExponentWithSign(current);
+            }
+            ' ' | '\n' | '\t' | '(' | ')' | '[' | ']' | '{' | '}' | ';' => {
+                buff.push(current);
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::NumberAfterExponent('-') | State::NumberAfterExponent('+') => match current {
+            c if c.is_numeric() => {
+                buff.push(c);
+                state = State::NumberAfterExponent(c);
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                buff.push(current);
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectConstant(location.clone())),
+        },
+        State::NumberAfterExponent(_) => match current {
+            c if c.is_numeric() => {
+                buff.push(c);
+                state = State::NumberAfterExponent(c);
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                buff.push(current);
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectConstant(location.clone())),
+        },
+        State::NumberAfterExponentWithSign(_) => match current {
+            c if c.is_numeric() => {
+                buff.push(c);
+                state = State::NumberAfterExponentWithSign(c);
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectConstant(location.clone())),
+        },
+        State::NumberAfterDot(_) => match current {
+            c if c.is_numeric() => {
+                buff.push(c);
+                state = State::NumberAfterDot(c);
+            }
+            'e' | 'E' => {
+                buff.push(current);
+                state = State::NumberAfterExponent(current);
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                buff.push(current);
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectConstant(location.clone())),
+        },
+        State::Number('.') => match current {
+            c if c.is_numeric() => {
+                buff.push(c);
+                state = State::NumberAfterDot(c);
+            }
+            'e' | 'E' => {
+                buff.push(current);
+                state = State::NumberAfterExponent(current);
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                buff.push(current);
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectConstant(location.clone())),
+        },
+
+        // State::Number('-') | State::Number('+') => match current {
+        //     c if c.is_numeric() => {
+        //         buff.push(c);
+        //         state = State::Number(c);
+        //     }
+        //     ' ' | '\n' | '\t' | '(' | ')' | '[' | ']' | '{' | '}' | ';' => {
+        //         buff.push(current);
+        //         reread = true;
+        //         is_writable = true;
+        //     }
+        //     _ => return Err(Error::IncorrectConstant(location.clone())),
+        // },
+        State::Number(_) => match current {
+            c if c.is_numeric() || c == '.' || c == '_' || c == 'e' || c == 'E' => {
+                buff.push(c);
+                state = State::Number(c);
+            }
+            c if is_whitespace(c) || is_separator(c) => {
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        State::Letter(_) => match current {
+            c if c.is_alphanumeric() || c == '_' => {
+                buff.push(current);
+                state = State::Identifier(c)
+            }
+            ' ' | '\n' | '\t' | '(' | ')' | '[' | ']' | '{' | '}' | ';' => {
+                buff.push(current);
+                reread = true;
+                is_writable = true;
+            }
+            _ => return Err(Error::IncorrectKeyword(location.clone())),
+        },
+        _ => {}
+    }
+    Ok(Move {
+        state,
+        is_writable,
+        reread,
+    })
+}
+
+/// What `count_tokens` does with one character in one state
+#[derive(Debug, Clone, PartialEq)]
+pub enum Probe {
+    /// The character joined the token and the scanner moved to `to`,
+    /// `flush` when that finished the token
+    Moved {
+        to: String,
+        flush: bool,
+    },
+    /// The token ended before the character, which starts the next one
+    Ended,
+    Failed,
+}
+
+/// Steps every state `count_tokens` can reach from `Whitespace` in the middle
+/// of a token on each of `chars`. States are named by their `Debug` output
+pub fn probe(chars: &[char]) -> Vec<(String, char, Probe)> {
+    walk(chars).0
+}
+
+/// Class of the token flushed from each state `count_tokens` can reach on
+/// `chars`, by variant name so `Letter(l)` in a transition list finds `Letter('x')`
+pub fn token_classes(chars: &[char]) -> std::collections::BTreeMap<String, TokenType> {
+    walk(chars)
+        .1
+        .iter()
+        .map(|state| {
+            (
+                variant(&format!("{:?}", state)).to_owned(),
+                token_type(state),
+            )
+        })
+        .collect()
+}
+
+/// Name of a state without the character it carries, `Letter('x')` is `Letter`
+pub fn variant(name: &str) -> &str {
+    name.split('(').next().unwrap_or(name)
+}
+
+/// `probe` and every state it reached, the ones a token is flushed in included
+fn walk(chars: &[char]) -> (Vec<(String, char, Probe)>, Vec<State>) {
+    let mut result = vec![];
+    let mut seen = vec![format!("{:?}", State::Whitespace)];
+    let mut reached = seen.clone();
+    let mut states = vec![State::Whitespace];
+    let mut queue = vec![State::Whitespace];
+    while !queue.is_empty() {
+        let state = queue.remove(0);
+        let name = format!("{:?}", state);
+        for &c in chars {
+            let mut location = Location {
+                line: 0,
+                column: 0,
+                char: c,
+            };
+            let probe = match transition(state.clone(), c, &mut location, &mut String::new()) {
+                Ok(step) if step.reread => Probe::Ended,
+                Ok(step) => {
+                    let to = format!("{:?}", step.state);
+                    if !reached.contains(&to) {
+                        reached.push(to.clone());
+                        states.push(step.state.clone());
+                    }
+                    if !step.is_writable && !seen.contains(&to) {
+                        seen.push(to.clone());
+                        queue.push(step.state);
+                    }
+                    Probe::Moved {
+                        to,
+                        flush: step.is_writable,
+                    }
+                }
+                Err(_) => Probe::Failed,
+            };
+            result.push((name.clone(), c, probe));
+        }
+    }
+    (result, states)
+}
+
+/// Class of the token flushed from `state`
+fn token_type(state: &State) -> TokenType {
+    match state {
+        State::Identifier(_) | State::Letter(_) | State::Underscore | State::Preprocessor => {
+            TokenType::Identifier
+        }
+        State::KeywordEnd | State::DoO => TokenType::Keyword,
+        State::Number(_)
+        | State::NumberAfterDot(_)
+        | State::NumberAfterExponent(_)
+        | State::NumberAfterExponentWithSign(_) => TokenType::ConstValue,
+        State::Separator(_) => TokenType::Separator,
+        State::StringLiteral(_) => TokenType::StringLiteral,
+        State::OperatorEnd => TokenType::Operator,
+        _ => TokenType::Operator,
+    }
+}
+
+/// What one call of `Stepper::step` did
+#[derive(Clone, Debug)]
+pub struct Step {
+    pub current: char,
+    pub location: Location,
+    /// `probe` names states by their `Debug` output
+    pub from: State,
+    pub to: State,
+    /// The buffer after the step, before a flush clears it
+    pub buffer: String,
+    /// The character is read again by the next step
+    pub reread: bool,
+    /// `is_writable` was set and the buffer became this token
+    pub flushed: Option<Token>,
+}
+
+/// `count_tokens` one character at a time
+pub struct Stepper {
+    text: String,
+    current_idx: usize,
+    state: State,
+    buff: String,
+    location: Location,
+    start: Location,
+    pub tokens: Vec<Token>,
+}
+
+impl Stepper {
+    pub fn new(text: String) -> Stepper {
+        let location = Location {
+            line: 0,
+            column: 0,
+            char: ' ',
+        };
+        Stepper {
+            text,
+            current_idx: 0,
+            state: State::Whitespace,
+            buff: String::new(),
+            start: location.clone(),
+            location,
+            tokens: vec![],
+        }
+    }
+
+    /// Reads the next character, `None` at the end of the text
+    pub fn step(&mut self) -> Option<Result<Step, Error>> {
+        if self.current_idx >= self.text.len() {
+            return None;
+        }
+        let current = self.text.as_bytes()[self.current_idx] as char;
+        // A character that is read again must not move the location twice
+        let before = self.location.clone();
+        self.location.char = current;
+        self.location.column += 1;
+        // if current == '\n' {
+        //     location.column = 0;
+        //     location.line += 1;
+        // }
+        if self.buff.is_empty() {
+            self.start = self.location.clone();
+        }
+        let at = self.location.clone();
+        let consumed = self.buff.len();
+        let from = self.state.clone();
+        let step = match transition(
+            self.state.clone(),
+            current,
+            &mut self.location,
+            &mut self.buff,
+        ) {
+            Ok(step) => step,
+            Err(e) => return Some(Err(e)),
+        };
+        self.state = step.state;
+        if step.reread {
+            // The character starts the next token, some states have pushed it already
+            self.buff.truncate(consumed);
+            self.current_idx -= 1;
+            self.location = before;
+        }
+        // println!(
+        //     "{:?} : {:?}, {} buff: {:?}",
+        //     current, state, step.is_writable, buff
+        // );
+        self.current_idx += 1;
+
+        let mut result = Step {
+            current,
+            location: at,
+            from,
+            to: self.state.clone(),
+            buffer: self.buff.clone(),
+            reread: step.reread,
+            flushed: None,
+        };
+        if step.is_writable {
+            let token = Token {
+                token_type: token_type(&self.state),
+                token: self.buff.clone(),
+                location: self.start.clone(),
+            };
+            self.state = State::Whitespace;
+            self.tokens.push(token.clone());
+            result.flushed = Some(token);
+            self.buff.clear();
+        }
+        Some(Ok(result))
+    }
+}
+
+pub fn count_tokens(text: String) -> Result<Vec<Token>, Error> {
+    let mut stepper = Stepper::new(text);
+    while let Some(step) = stepper.step() {
+        step?;
+    }
+    Ok(stepper.tokens)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn located(text: &str) -> Vec<(usize, usize)> {
+        count_tokens(text.to_owned())
+            .unwrap()
+            .iter()
+            .map(|t| (t.location.line, t.location.column))
+            .collect()
+    }
+
+    #[test]
+    fn locations_point_at_the_first_character() {
+        assert_eq!(
+            located("int y = 3;\ny += x;"),
+            vec![
+                (0, 1),
+                (0, 5),
+                (0, 7),
+                (0, 9),
+                (0, 10),
+                (1, 1),
+                (1, 3),
+                (1, 6),
+                (1, 7)
+            ]
+        );
+    }
+
+    #[test]
+    fn logical_operators_are_one_token() {
+        let tokens = count_tokens("a && b || c & d;".to_owned()).unwrap();
+        let texts: Vec<&str> = tokens.iter().map(|t| t.token.as_str()).collect();
+        assert_eq!(texts, ["a", "&&", "b", "||", "c", "&", "d", ";"]);
+        assert_eq!(tokens[1].token_type, TokenType::Operator);
+    }
+
+    #[test]
+    fn errors_report_the_offending_character() {
+        let error = count_tokens("int a = 1;\nab@".to_owned()).unwrap_err();
+        assert_eq!(error.to_string(), "Identifier error at 1:3 (@)");
+    }
+
+    #[test]
+    fn steps_record_the_states_they_move_between() {
+        let mut stepper = Stepper::new("x;".to_owned());
+        let mut trace = vec![];
+        while let Some(step) = stepper.step() {
+            let step = step.unwrap();
+            trace.push(format!(
+                "{} {:?} -> {:?} {:?} {} {}",
+                step.current,
+                step.from,
+                step.to,
+                step.buffer,
+                step.reread,
+                step.flushed.map_or(String::new(), |t| t.token)
+            ));
+        }
+        assert_eq!(
+            trace,
+            [
+                "x Whitespace -> Letter('x') \"x\" false ",
+                "; Letter('x') -> Letter('x') \"x\" true x",
+                "; Whitespace -> Separator(';') \";\" false ;"
+            ]
+        );
+    }
+}
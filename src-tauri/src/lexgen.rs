@@ -0,0 +1,498 @@
+use crate::cleanup::remove_comments;
+use crate::descriptors::create_descriptors;
+use crate::dfa::Dfa;
+use crate::grammar::format_table;
+use crate::keywords::{count_tokens, Location, Token, TokenType};
+use crate::minimize::Machine;
+use crate::nfa::{parse_regex, Nfa};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+#[derive(Debug)]
+pub enum Error {
+    /// Line of the spec and what is wrong with it
+    Spec(usize, String),
+    /// Where no rule matches
+    NoMatch(Location),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Spec(line, what) => write!(f, "Spec error at line {}: {}", line, what),
+            Self::NoMatch(l) => {
+                write!(f, "Lexer error at {}:{} ({})", l.line, l.column, l.char)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Action {
+    Emit(TokenType),
+    Skip,
+}
+
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pub name: String,
+    pub priority: i64,
+    pub action: Action,
+    pub regex: String,
+    /// 1-based line of the spec
+    pub line: usize,
+}
+
+fn token_type(name: &str) -> Option<TokenType> {
+    match name {
+        "Keyword" => Some(TokenType::Keyword),
+        "Identifier" => Some(TokenType::Identifier),
+        "Operator" => Some(TokenType::Operator),
+        "ConstValue" => Some(TokenType::ConstValue),
+        "StringLiteral" => Some(TokenType::StringLiteral),
+        "Separator" => Some(TokenType::Separator),
+        _ => None,
+    }
+}
+
+/// First word of `text` and what follows it
+fn word(text: &str) -> (&str, &str) {
+    let text = text.trim_start();
+    let end = text.find(char::is_whitespace).unwrap_or(text.len());
+    (&text[..end], &text[end..])
+}
+
+/// One rule per line: `name priority emit <TokenType> regex` or
+/// `name priority skip regex`. The regex is the rest of the line, spaces
+/// in it are written `\s`. Empty lines and `//` comments are ignored
+pub fn parse_spec(text: &str) -> Result<Vec<Rule>, Error> {
+    let mut rules: Vec<Rule> = vec![];
+    for (idx, line) in text.lines().enumerate() {
+        let number = idx + 1;
+        if line.trim().is_empty() || line.trim_start().starts_with("//") {
+            continue;
+        }
+        let error = |what: String| Error::Spec(number, what);
+        let (name, rest) = word(line);
+        let (priority, rest) = word(rest);
+        let priority: i64 = priority
+            .parse()
+            .map_err(|_| error(format!("priority {:?} isn't a number", priority)))?;
+        let (action, rest) = match word(rest) {
+            ("skip", rest) => (Action::Skip, rest),
+            ("emit", rest) => {
+                let (class, rest) = word(rest);
+                match token_type(class) {
+                    Some(class) => (Action::Emit(class), rest),
+                    None => {
+                        return Err(error(format!(
+                            "unknown token class {:?}, expected Keyword, Identifier, \
+                             Operator, ConstValue, StringLiteral or Separator",
+                            class
+                        )))
+                    }
+                }
+            }
+            (other, _) => return Err(error(format!("expected emit or skip, found {:?}", other))),
+        };
+        let regex = rest.trim();
+        if regex.is_empty() {
+            return Err(error(format!("rule {} has no regex", name)));
+        }
+        let parsed = parse_regex(regex).map_err(|e| error(e.to_string()))?;
+        let nfa = Nfa::from_regex(&parsed, name);
+        let start = nfa.closure(&BTreeSet::from([nfa.start]));
+        if nfa.accepting.keys().any(|s| start.contains(s)) {
+            return Err(error(format!("rule {} matches the empty string", name)));
+        }
+        if rules.iter().any(|r| r.name == name) {
+            return Err(error(format!("rule {} is defined twice", name)));
+        }
+        rules.push(Rule {
+            name: name.to_owned(),
+            priority,
+            action,
+            regex: regex.to_owned(),
+            line: number,
+        });
+    }
+    if rules.is_empty() {
+        return Err(Error::Spec(0, "no rules".to_owned()));
+    }
+    Ok(rules)
+}
+
+/// Table driven scanner: the minimal DFA of all rules, its states labelled
+/// with the rule they accept for
+pub struct Scanner {
+    pub rules: Vec<Rule>,
+    pub machine: Machine,
+    /// States of the NFA, the DFA and the minimal DFA
+    pub sizes: (usize, usize, usize),
+}
+
+impl Scanner {
+    /// NFA of every rule joined by ε-transitions, highest priority first so
+    /// it names the DFA states it shares with the others, then the subset
+    /// construction and Hopcroft's minimisation
+    pub fn build(mut rules: Vec<Rule>) -> Scanner {
+        // Stable, equal priorities keep the order of the spec
+        rules.sort_by_key(|r| -r.priority);
+        let parts = rules
+            .iter()
+            .map(|r| Nfa::from_regex(&parse_regex(&r.regex).unwrap(), &r.name))
+            .collect();
+        let nfa = Nfa::union(parts);
+        let (dfa, _) = Dfa::from_nfa(&nfa);
+        let machine = Machine::from_dfa(&dfa);
+        let (block, _) = machine.hopcroft();
+        let (minimal, _) = machine.merge(&block);
+        Scanner {
+            sizes: (nfa.states, dfa.sets.len(), minimal.names.len()),
+            rules,
+            machine: minimal,
+        }
+    }
+
+    fn action(&self, rule: &str) -> &Action {
+        &self.rules.iter().find(|r| r.name == rule).unwrap().action
+    }
+
+    /// Length in bytes of the longest prefix of `text` some rule matches, and the rule
+    pub fn longest_match(&self, text: &str) -> Option<(usize, &str)> {
+        let mut state = self.machine.start;
+        let mut last = None;
+        for (idx, c) in text.char_indices() {
+            state = match self.machine.step(state, c) {
+                Some(next) => next,
+                None => break,
+            };
+            if let Some(rule) = &self.machine.labels[state] {
+                last = Some((idx + c.len_utf8(), rule.as_str()));
+            }
+        }
+        last
+    }
+
+    /// Splits `text` into tokens by longest match, counting lines and
+    /// columns the way `count_tokens` does
+    pub fn scan(&self, text: &str) -> Result<Vec<Token>, Error> {
+        let mut tokens = vec![];
+        let mut location = Location {
+            line: 0,
+            column: 0,
+            char: ' ',
+        };
+        let mut rest = text;
+        while let Some(first) = rest.chars().next() {
+            let start = Location {
+                line: location.line,
+                column: location.column + 1,
+                char: first,
+            };
+            let (length, rule) = match self.longest_match(rest) {
+                Some(found) => found,
+                None => return Err(Error::NoMatch(start)),
+            };
+            let (token, tail) = rest.split_at(length);
+            for c in token.chars() {
+                location.column += 1;
+                location.char = c;
+                if c == '\n' {
+                    location.column = 0;
+                    location.line += 1;
+                }
+            }
+            if let Action::Emit(token_type) = self.action(rule) {
+                tokens.push(Token {
+                    token_type: token_type.clone(),
+                    token: token.to_owned(),
+                    location: start,
+                });
+            }
+            rest = tail;
+        }
+        Ok(tokens)
+    }
+
+    /// Rust source of the same scanner: the tables of `Machine::to_rust` and
+    /// a `tokenize` producing `keywords::Token`s
+    pub fn to_rust(&self) -> String {
+        let mut out = String::from("use crate::keywords::{Location, Token, TokenType};\n\n");
+        out.push_str(&self.machine.to_rust());
+        out.push_str(
+            "\n/// Token class each rule emits, `None` for the rules that skip their match\n\
+             fn emits(rule: &str) -> Option<TokenType> {\n    match rule {\n",
+        );
+        for rule in &self.rules {
+            if let Action::Emit(token_type) = &rule.action {
+                out.push_str(&format!(
+                    "        {:?} => Some(TokenType::{:?}),\n",
+                    rule.name, token_type
+                ));
+            }
+        }
+        out.push_str("        _ => None,\n    }\n}\n\n");
+        out.push_str(
+            "/// Splits `text` into tokens by longest match, the error is where no rule matches\n\
+             pub fn tokenize(text: &str) -> Result<Vec<Token>, Location> {\n    \
+                 let mut tokens = vec![];\n    \
+                 let mut location = Location {\n        \
+                     line: 0,\n        \
+                     column: 0,\n        \
+                     char: ' ',\n    \
+                 };\n    \
+                 let mut rest = text;\n    \
+                 while let Some(first) = rest.chars().next() {\n        \
+                     let start = Location {\n            \
+                         line: location.line,\n            \
+                         column: location.column + 1,\n            \
+                         char: first,\n        \
+                     };\n        \
+                     let (length, rule) = match longest_match(rest) {\n            \
+                         Some((length, rule)) if length > 0 => (length, rule),\n            \
+                         _ => return Err(start),\n        \
+                     };\n        \
+                     let (token, tail) = rest.split_at(length);\n        \
+                     for c in token.chars() {\n            \
+                         location.column += 1;\n            \
+                         location.char = c;\n            \
+                         if c == '\\n' {\n                \
+                             location.column = 0;\n                \
+                             location.line += 1;\n            \
+                         }\n        \
+                     }\n        \
+                     if let Some(token_type) = emits(rule) {\n            \
+                         tokens.push(Token {\n                \
+                             token_type,\n                \
+                             token: token.to_owned(),\n                \
+                             location: start,\n            \
+                         });\n        \
+                     }\n        \
+                     rest = tail;\n    \
+                 }\n    \
+                 Ok(tokens)\n\
+             }\n",
+        );
+        out
+    }
+}
+
+fn describe(token: &Token) -> String {
+    format!(
+        "{:?} {:?} {}:{}",
+        token.token_type, token.token, token.location.line, token.location.column
+    )
+}
+
+fn format_tokens(tokens: &[Token]) -> String {
+    let mut rows = vec![vec![
+        "#".to_owned(),
+        "class".to_owned(),
+        "token".to_owned(),
+        "at".to_owned(),
+    ]];
+    for (idx, token) in tokens.iter().enumerate() {
+        rows.push(vec![
+            idx.to_string(),
+            format!("{:?}", token.token_type),
+            format!("{:?}", token.token),
+            format!("{}:{}", token.location.line, token.location.column),
+        ]);
+    }
+    format!("\n{}", format_table(&rows))
+}
+
+/// Tokens that differ from what `count_tokens` produces, position by position
+fn compare(generated: &[Token], program: String) -> String {
+    let expected = match count_tokens(program) {
+        Ok(tokens) => tokens,
+        Err(e) => return format!("\nkeywords.rs: {}", e),
+    };
+    let mut rows = vec![vec![
+        "#".to_owned(),
+        "generated".to_owned(),
+        "keywords.rs".to_owned(),
+    ]];
+    for idx in 0..generated.len().max(expected.len()) {
+        let (a, b) = (generated.get(idx), expected.get(idx));
+        let same = match (a, b) {
+            (Some(a), Some(b)) => a.token_type == b.token_type && a.token == b.token,
+            _ => false,
+        };
+        if !same {
+            rows.push(vec![
+                idx.to_string(),
+                a.map_or("-".to_owned(), describe),
+                b.map_or("-".to_owned(), describe),
+            ]);
+        }
+    }
+    if rows.len() == 1 {
+        format!("\nsame {} tokens", expected.len())
+    } else {
+        format!(
+            "\n{} tokens, keywords.rs {}, {} differ\n{}",
+            generated.len(),
+            expected.len(),
+            rows.len() - 1,
+            format_table(&rows)
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LexgenReport {
+    pub rules: String,
+    /// Sizes of the automata and the minimal DFA's table
+    pub automaton: String,
+    pub tokens: String,
+    /// Differences with the tokens of `count_tokens`
+    pub comparison: String,
+    pub descriptors: String,
+    pub rust: String,
+    pub error: String,
+}
+
+impl LexgenReport {
+    fn error(error: String) -> LexgenReport {
+        LexgenReport {
+            rules: String::new(),
+            automaton: String::new(),
+            tokens: String::new(),
+            comparison: String::new(),
+            descriptors: String::new(),
+            rust: String::new(),
+            error,
+        }
+    }
+}
+
+/// Builds the scanner of `spec` and runs it on `program` with its comments
+/// removed, as the analysis does before `count_tokens`
+pub fn generate(spec: &str, program: &str) -> LexgenReport {
+    let rules = match parse_spec(spec) {
+        Ok(rules) => rules,
+        Err(e) => return LexgenReport::error(e.to_string()),
+    };
+    let scanner = Scanner::build(rules);
+    let mut rows = vec![vec![
+        "line".to_owned(),
+        "priority".to_owned(),
+        "rule".to_owned(),
+        "action".to_owned(),
+        "regex".to_owned(),
+    ]];
+    for rule in &scanner.rules {
+        let action = match &rule.action {
+            Action::Emit(token_type) => format!("emit {:?}", token_type),
+            Action::Skip => "skip".to_owned(),
+        };
+        rows.push(vec![
+            rule.line.to_string(),
+            rule.priority.to_string(),
+            rule.name.clone(),
+            action,
+            rule.regex.clone(),
+        ]);
+    }
+    let (nfa, dfa, minimal) = scanner.sizes;
+    let mut report = LexgenReport {
+        rules: format!("\n{}", format_table(&rows)),
+        automaton: format!(
+            "\nNFA {} states, DFA {} states, minimal DFA {} states{}",
+            nfa,
+            dfa,
+            minimal,
+            scanner.machine.to_table()
+        ),
+        rust: scanner.to_rust(),
+        ..LexgenReport::error(String::new())
+    };
+    if program.is_empty() {
+        return report;
+    }
+    let program = remove_comments(program.to_owned());
+    match scanner.scan(&program) {
+        Ok(tokens) => {
+            report.tokens = format_tokens(&tokens);
+            report.comparison = compare(&tokens, program);
+            report.descriptors =
+                create_descriptors(tokens).map_or(String::new(), |table| table.descriptors);
+        }
+        Err(e) => report.error = e.to_string(),
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = "// C subset\n\
+        ws 0 skip [\\s\\n\\t]+\n\
+        kw 2 emit Keyword int|return\n\
+        id 1 emit Identifier [a-z_][a-z0-9_]*\n\
+        num 1 emit ConstValue \\d+\n\
+        op 1 emit Operator =|==|\\+\n\
+        sep 1 emit Separator [;(){}]";
+
+    fn error(spec: &str) -> String {
+        parse_spec(spec).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn reports_the_line_of_a_bad_rule() {
+        assert_eq!(
+            error("ws 0 skip \\s\nid high emit Identifier x"),
+            "Spec error at line 2: priority \"high\" isn't a number"
+        );
+        assert_eq!(
+            error("x 0 emit Word x"),
+            "Spec error at line 1: unknown token class \"Word\", expected Keyword, Identifier, Operator, ConstValue, StringLiteral or Separator"
+        );
+        assert_eq!(
+            error("x 0 skip a*"),
+            "Spec error at line 1: rule x matches the empty string"
+        );
+        assert_eq!(
+            error("x 0 skip a\nx 0 skip b"),
+            "Spec error at line 2: rule x is defined twice"
+        );
+        assert_eq!(
+            error("x 0 skip é"),
+            "Spec error at line 1: Regex error at 0: 'é' is outside of the alphabet"
+        );
+        assert_eq!(error("// nothing"), "Spec error at line 0: no rules");
+    }
+
+    #[test]
+    fn longest_match_then_priority() {
+        let scanner = Scanner::build(parse_spec(SPEC).unwrap());
+        assert_eq!(scanner.longest_match("int x"), Some((3, "kw")));
+        assert_eq!(scanner.longest_match("integer"), Some((7, "id")));
+        assert_eq!(scanner.longest_match("== 1"), Some((2, "op")));
+        assert_eq!(scanner.longest_match("#"), None);
+    }
+
+    #[test]
+    fn scans_with_the_locations_of_count_tokens() {
+        let scanner = Scanner::build(parse_spec(SPEC).unwrap());
+        let tokens = scanner.scan("int x;\nx = 42;").unwrap();
+        assert_eq!(tokens.len(), 7);
+        assert_eq!(tokens[5].token, "42");
+        assert_eq!(tokens[5].token_type, TokenType::ConstValue);
+        assert_eq!(
+            scanner.scan("int x;\n  #").unwrap_err().to_string(),
+            "Lexer error at 1:3 (#)"
+        );
+    }
+
+    #[test]
+    fn generated_tokens_match_keywords_rs() {
+        let report = generate(SPEC, "int main() { return 0; }");
+        assert_eq!(report.error, "");
+        assert_eq!(report.comparison, "\nsame 9 tokens");
+        assert!(report.rust.contains("pub fn tokenize"));
+    }
+}
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+
+#[derive(Debug)]
+pub enum Error {
+    UnknownIdentifier(String),
+    UnknownLabel(String),
+    /// `break` or `continue` outside of a loop
+    Misplaced(String),
+    /// Assignment to something that isn't a variable, element or dereference
+    NotAssignable,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::UnknownIdentifier(name) => {
+                write!(f, "Translation error: unknown identifier {}", name)
+            }
+            Self::UnknownLabel(name) => write!(f, "Translation error: unknown label {}", name),
+            Self::Misplaced(what) => write!(f, "Translation error: {} outside of a loop", what),
+            Self::NotAssignable => f.write_str("Translation error: left side is not assignable"),
+        }
+    }
+}
+
+/// Jumps out of a loop or switch. Their target is not known yet, so they are
+/// emitted with a placeholder and patched
+#[derive(Default)]
+pub struct Loop {
+    pub breaks: Vec<usize>,
+    pub continues: Vec<usize>,
+}
+
+/// Enclosing loops, labels and `goto`s of the function being translated, shared by
+/// the postfix and quadruple translators. Positions index the translator's output
+#[derive(Default)]
+pub struct Jumps {
+    loops: Vec<Loop>,
+    labels: BTreeMap<String, usize>,
+    gotos: Vec<(usize, String)>,
+}
+
+impl Jumps {
+    pub fn enter(&mut self) {
+        self.loops.push(Loop::default());
+    }
+
+    pub fn leave(&mut self) -> Loop {
+        self.loops.pop().unwrap()
+    }
+
+    /// Leaves a switch and returns its breaks. `continue` inside a switch belongs to the enclosing loop
+    pub fn leave_switch(&mut self) -> Result<Vec<usize>, Error> {
+        let l = self.leave();
+        match self.loops.last_mut() {
+            Some(outer) => outer.continues.extend(l.continues),
+            None if !l.continues.is_empty() => return Err(Error::Misplaced("continue".to_owned())),
+            None => {}
+        }
+        Ok(l.breaks)
+    }
+
+    pub fn break_at(&mut self, at: usize) -> Result<(), Error> {
+        match self.loops.last_mut() {
+            Some(l) => {
+                l.breaks.push(at);
+                Ok(())
+            }
+            None => Err(Error::Misplaced("break".to_owned())),
+        }
+    }
+
+    pub fn continue_at(&mut self, at: usize) -> Result<(), Error> {
+        match self.loops.last_mut() {
+            Some(l) => {
+                l.continues.push(at);
+                Ok(())
+            }
+            None => Err(Error::Misplaced("continue".to_owned())),
+        }
+    }
+
+    pub fn goto(&mut self, at: usize, label: &str) {
+        self.gotos.push((at, label.to_owned()));
+    }
+
+    pub fn label(&mut self, label: &str, at: usize) {
+        self.labels.insert(label.to_owned(), at);
+    }
+
+    /// Jump and target of every `goto` at the end of a function, the labels are forgotten
+    pub fn resolve(&mut self) -> Result<Vec<(usize, usize)>, Error> {
+        let labels = std::mem::take(&mut self.labels);
+        std::mem::take(&mut self.gotos)
+            .into_iter()
+            .map(|(at, label)| match labels.get(&label) {
+                Some(target) => Ok((at, *target)),
+                None => Err(Error::UnknownLabel(label)),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn continue_in_a_switch_goes_to_the_loop() {
+        let mut jumps = Jumps::default();
+        jumps.enter();
+        jumps.enter();
+        jumps.break_at(1).unwrap();
+        jumps.continue_at(2).unwrap();
+        assert_eq!(jumps.leave_switch().unwrap(), [1]);
+        let l = jumps.leave();
+        assert_eq!(l.continues, [2]);
+        assert!(l.breaks.is_empty());
+    }
+
+    #[test]
+    fn break_needs_a_loop() {
+        let mut jumps = Jumps::default();
+        assert_eq!(
+            jumps.break_at(0).unwrap_err().to_string(),
+            "Translation error: break outside of a loop"
+        );
+        jumps.enter();
+        jumps.continue_at(0).unwrap();
+        assert!(jumps.leave_switch().is_err());
+    }
+
+    #[test]
+    fn resolves_gotos_per_function() {
+        let mut jumps = Jumps::default();
+        jumps.goto(3, "out");
+        jumps.label("out", 7);
+        assert_eq!(jumps.resolve().unwrap(), [(3, 7)]);
+        jumps.goto(9, "out");
+        assert_eq!(
+            jumps.resolve().unwrap_err().to_string(),
+            "Translation error: unknown label out"
+        );
+    }
+}
@@ -0,0 +1,486 @@
+use crate::grammar::{
+    format_input, format_productions, format_stack, format_table, parse_error, terminal_for,
+    Grammar, Production, END, EPSILON, MAX_STEPS,
+};
+use crate::keywords::Token;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug)]
+pub enum Method {
+    Lr0,
+    Slr1,
+    Lalr1,
+}
+
+/// Production and dot position
+type Item = (usize, usize);
+
+#[derive(Clone, PartialEq, Debug)]
+enum Action {
+    Shift(usize),
+    Reduce(usize),
+    Accept,
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Action::Shift(state) => write!(f, "s{}", state),
+            Action::Reduce(p) => write!(f, "r{}", p),
+            Action::Accept => f.write_str("acc"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// Nonterminals that derive no string of terminals, LALR(1) lookaheads are undefined for them
+    Nonproductive(Vec<String>),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Nonproductive(names) => {
+                write!(f, "Grammar error: nonproductive {}", names.join(", "))
+            }
+        }
+    }
+}
+
+fn nonproductive(grammar: &Grammar) -> Error {
+    let mut productive: BTreeSet<&String> = BTreeSet::new();
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for p in &grammar.productions {
+            if p.body
+                .iter()
+                .all(|x| grammar.is_terminal(x) || productive.contains(x))
+            {
+                changed |= productive.insert(&p.head);
+            }
+        }
+    }
+    Error::Nonproductive(
+        grammar
+            .nonterminals
+            .iter()
+            .filter(|x| !productive.contains(x))
+            .cloned()
+            .collect(),
+    )
+}
+
+struct Automaton {
+    /// Grammar productions followed by `S' -> S`
+    productions: Vec<Production>,
+    states: Vec<BTreeSet<Item>>,
+    transitions: BTreeMap<(usize, String), usize>,
+}
+
+impl Automaton {
+    fn augmented(&self) -> usize {
+        self.productions.len() - 1
+    }
+
+    fn next_symbol(&self, (p, dot): Item) -> Option<&String> {
+        self.productions[p].body.get(dot)
+    }
+
+    fn closure(&self, grammar: &Grammar, mut items: BTreeSet<Item>) -> BTreeSet<Item> {
+        let mut queue: Vec<Item> = items.iter().cloned().collect();
+        while let Some(item) = queue.pop() {
+            let Some(symbol) = self.next_symbol(item) else {
+                continue;
+            };
+            if grammar.is_terminal(symbol) {
+                continue;
+            }
+            for (q, p) in self.productions.iter().enumerate() {
+                if &p.head == symbol && items.insert((q, 0)) {
+                    queue.push((q, 0));
+                }
+            }
+        }
+        items
+    }
+
+    fn build(grammar: &Grammar) -> Automaton {
+        let mut productions = grammar.productions.clone();
+        productions.push(Production {
+            head: format!("{}'", grammar.start),
+            body: vec![grammar.start.clone()],
+        });
+        let mut automaton = Automaton {
+            productions,
+            states: vec![],
+            transitions: BTreeMap::new(),
+        };
+        let start = automaton.closure(grammar, BTreeSet::from([(automaton.augmented(), 0)]));
+        automaton.states.push(start);
+        let symbols: Vec<String> = grammar
+            .terminals
+            .iter()
+            .chain(grammar.nonterminals.iter())
+            .cloned()
+            .collect();
+        let mut idx = 0;
+        while idx < automaton.states.len() {
+            for symbol in &symbols {
+                let kernel: BTreeSet<Item> = automaton.states[idx]
+                    .iter()
+                    .filter(|item| automaton.next_symbol(**item) == Some(symbol))
+                    .map(|(p, dot)| (*p, dot + 1))
+                    .collect();
+                if kernel.is_empty() {
+                    continue;
+                }
+                let state = automaton.closure(grammar, kernel);
+                let target = match automaton.states.iter().position(|x| x == &state) {
+                    Some(target) => target,
+                    None => {
+                        automaton.states.push(state);
+                        automaton.states.len() - 1
+                    }
+                };
+                automaton.transitions.insert((idx, symbol.clone()), target);
+            }
+            idx += 1;
+        }
+        automaton
+    }
+
+    /// LALR(1) lookaheads: the canonical LR(1) collection with states of equal core merged
+    fn lalr_lookaheads(
+        &self,
+        grammar: &Grammar,
+    ) -> Result<BTreeMap<(usize, Item), BTreeSet<String>>, Error> {
+        let first = grammar.first_sets();
+        let closure = |mut items: BTreeSet<(usize, usize, String)>| {
+            let mut queue: Vec<(usize, usize, String)> = items.iter().cloned().collect();
+            while let Some((p, dot, lookahead)) = queue.pop() {
+                let Some(symbol) = self.next_symbol((p, dot)) else {
+                    continue;
+                };
+                if grammar.is_terminal(symbol) {
+                    continue;
+                }
+                let mut rest = self.productions[p].body[dot + 1..].to_vec();
+                rest.push(lookahead);
+                let follow = grammar.first_of(&rest, &first);
+                for (q, production) in self.productions.iter().enumerate() {
+                    if &production.head != symbol {
+                        continue;
+                    }
+                    for b in follow.iter().filter(|x| *x != EPSILON) {
+                        if items.insert((q, 0, b.clone())) {
+                            queue.push((q, 0, b.clone()));
+                        }
+                    }
+                }
+            }
+            items
+        };
+        let core = |state: &BTreeSet<(usize, usize, String)>| -> BTreeSet<Item> {
+            state.iter().map(|(p, dot, _)| (*p, *dot)).collect()
+        };
+
+        let mut states = vec![closure(BTreeSet::from([(
+            self.augmented(),
+            0,
+            END.to_owned(),
+        )]))];
+        let mut idx = 0;
+        while idx < states.len() {
+            let symbols: BTreeSet<String> = states[idx]
+                .iter()
+                .filter_map(|(p, dot, _)| self.next_symbol((*p, *dot)).cloned())
+                .collect();
+            for symbol in symbols {
+                let kernel = states[idx]
+                    .iter()
+                    .filter(|(p, dot, _)| self.next_symbol((*p, *dot)) == Some(&symbol))
+                    .map(|(p, dot, lookahead)| (*p, dot + 1, lookahead.clone()))
+                    .collect();
+                let state = closure(kernel);
+                if !states.contains(&state) {
+                    states.push(state);
+                }
+            }
+            idx += 1;
+        }
+
+        let mut lookaheads: BTreeMap<(usize, Item), BTreeSet<String>> = BTreeMap::new();
+        for state in &states {
+            let merged = self
+                .states
+                .iter()
+                .position(|x| x == &core(state))
+                .ok_or_else(|| nonproductive(grammar))?;
+            for (p, dot, lookahead) in state {
+                lookaheads
+                    .entry((merged, (*p, *dot)))
+                    .or_default()
+                    .insert(lookahead.clone());
+            }
+        }
+        Ok(lookaheads)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LrReport {
+    pub productions: String,
+    /// Item sets of the automaton
+    pub states: String,
+    /// ACTION and GOTO side by side
+    pub table: String,
+    pub conflicts: String,
+    /// Stack, input and action for every step of the parse
+    pub trace: String,
+    /// Parse error, empty when the program was accepted
+    pub error: String,
+}
+
+/// Builds the LR automaton and parsing table of `grammar` using `method`, then parses `tokens`
+pub fn lr(grammar: &Grammar, method: Method, tokens: &[Token]) -> Result<LrReport, Error> {
+    let automaton = Automaton::build(grammar);
+    let follow = grammar.follow_sets(&grammar.first_sets());
+    let lookaheads = if method == Method::Lalr1 {
+        automaton.lalr_lookaheads(grammar)?
+    } else {
+        BTreeMap::new()
+    };
+    let mut columns = grammar.terminals.clone();
+    columns.push(END.to_owned());
+
+    let mut action: BTreeMap<(usize, String), Vec<Action>> = BTreeMap::new();
+    let mut add = |state: usize, symbol: &String, a: Action| {
+        let cell = action.entry((state, symbol.clone())).or_default();
+        if !cell.contains(&a) {
+            cell.push(a);
+        }
+    };
+    for (idx, state) in automaton.states.iter().enumerate() {
+        for &(p, dot) in state {
+            match automaton.next_symbol((p, dot)) {
+                Some(symbol) if grammar.is_terminal(symbol) => {
+                    let target = automaton.transitions[&(idx, symbol.clone())];
+                    add(idx, symbol, Action::Shift(target));
+                }
+                Some(_) => {}
+                None if p == automaton.augmented() => add(idx, &END.to_owned(), Action::Accept),
+                None => {
+                    let on: Vec<String> = match method {
+                        Method::Lr0 => columns.clone(),
+                        Method::Slr1 => follow[&automaton.productions[p].head]
+                            .iter()
+                            .cloned()
+                            .collect(),
+                        Method::Lalr1 => match lookaheads.get(&(idx, (p, dot))) {
+                            Some(set) => set.iter().cloned().collect(),
+                            None => return Err(nonproductive(grammar)),
+                        },
+                    };
+                    for symbol in &on {
+                        add(idx, symbol, Action::Reduce(p));
+                    }
+                }
+            }
+        }
+    }
+
+    let states: String = automaton
+        .states
+        .iter()
+        .enumerate()
+        .map(|(idx, state)| {
+            let items: String = state
+                .iter()
+                .map(|&(p, dot)| {
+                    let production = &automaton.productions[p];
+                    let mut body = production.body.clone();
+                    body.insert(dot, "•".to_owned());
+                    let mut item = format!("\n  {} -> {}", production.head, body.join(" "));
+                    if let Some(set) = lookaheads.get(&(idx, (p, dot))) {
+                        let set: Vec<&str> = set.iter().map(|x| x.as_str()).collect();
+                        item.push_str(&format!(", {}", set.join("/")));
+                    }
+                    item
+                })
+                .collect();
+            format!("\nI{}{}", idx, items)
+        })
+        .collect();
+
+    let mut rows = vec![std::iter::once(String::new())
+        .chain(columns.iter().cloned())
+        .chain(grammar.nonterminals.iter().cloned())
+        .collect::<Vec<String>>()];
+    for idx in 0..automaton.states.len() {
+        let mut row = vec![idx.to_string()];
+        for symbol in &columns {
+            row.push(match action.get(&(idx, symbol.clone())) {
+                Some(cell) => cell
+                    .iter()
+                    .map(|a| a.to_string())
+                    .collect::<Vec<String>>()
+                    .join(","),
+                None => String::new(),
+            });
+        }
+        for symbol in &grammar.nonterminals {
+            row.push(match automaton.transitions.get(&(idx, symbol.clone())) {
+                Some(target) => target.to_string(),
+                None => String::new(),
+            });
+        }
+        rows.push(row);
+    }
+
+    let conflicts: String = action
+        .iter()
+        .filter(|(_, cell)| cell.len() > 1)
+        .map(|((state, symbol), cell)| {
+            let reduces = cell.iter().filter(|a| matches!(a, Action::Reduce(_))).count();
+            let kind = if reduces == cell.len() {
+                "reduce/reduce"
+            } else {
+                "shift/reduce"
+            };
+            let cell: Vec<String> = cell.iter().map(|a| a.to_string()).collect();
+            format!("\n{} in state {} on {}: {}", kind, state, symbol, cell.join(", "))
+        })
+        .collect();
+
+    let mut trace = vec![vec![
+        "stack".to_owned(),
+        "input".to_owned(),
+        "action".to_owned(),
+    ]];
+    let mut stack: Vec<String> = vec!["0".to_owned()];
+    let mut states_stack: Vec<usize> = vec![0];
+    let mut pos = 0;
+    let error = loop {
+        let state = *states_stack.last().unwrap();
+        let lookahead = match tokens.get(pos) {
+            Some(t) => terminal_for(t, &grammar.terminals),
+            None => END.to_owned(),
+        };
+        let mut row = vec![format_stack(&stack), format_input(tokens, pos)];
+        if trace.len() > MAX_STEPS {
+            break format!("Parse stopped after {} steps", MAX_STEPS);
+        }
+        match action.get(&(state, lookahead)).and_then(|cell| cell.first()) {
+            Some(Action::Shift(target)) => {
+                row.push(format!("shift {}", target));
+                stack.push(tokens[pos].token.clone());
+                stack.push(target.to_string());
+                states_stack.push(*target);
+                pos += 1;
+            }
+            Some(Action::Reduce(p)) => {
+                let production = &automaton.productions[*p];
+                row.push(format!("reduce {}", production));
+                for _ in 0..production.body.len() {
+                    stack.truncate(stack.len() - 2);
+                    states_stack.pop();
+                }
+                let top = *states_stack.last().unwrap();
+                let target = automaton.transitions[&(top, production.head.clone())];
+                stack.push(production.head.clone());
+                stack.push(target.to_string());
+                states_stack.push(target);
+            }
+            Some(Action::Accept) => {
+                row.push("accept".to_owned());
+                trace.push(row);
+                break String::new();
+            }
+            None => {
+                row.push("error".to_owned());
+                trace.push(row);
+                let expected: Vec<String> = columns
+                    .iter()
+                    .filter(|x| action.contains_key(&(state, (*x).clone())))
+                    .cloned()
+                    .collect();
+                break parse_error(tokens, pos, &expected.join(" or "));
+            }
+        }
+        trace.push(row);
+    };
+
+    Ok(LrReport {
+        productions: format_productions(grammar),
+        states,
+        table: format_table(&rows),
+        conflicts,
+        trace: format_table(&trace),
+        error,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grammar::parse_grammar;
+    use crate::keywords::count_tokens;
+
+    const EXPRESSIONS: &str = "E -> E + T | T
+T -> T * F | F
+F -> '(' E ')' | Identifier";
+
+    fn run(grammar: &str, method: Method, program: &str) -> Result<LrReport, Error> {
+        let grammar = parse_grammar(grammar).unwrap();
+        let tokens = count_tokens(program.to_owned()).unwrap();
+        lr(&grammar, method, &tokens)
+    }
+
+    #[test]
+    fn slr_and_lalr_parse_the_expression_grammar() {
+        for method in [Method::Slr1, Method::Lalr1] {
+            let report = run(EXPRESSIONS, method, "a + b * (c + d)").unwrap();
+            assert_eq!(report.conflicts, "");
+            assert_eq!(report.error, "");
+            assert!(report.trace.contains("accept"));
+        }
+    }
+
+    #[test]
+    fn lr0_reports_conflicts() {
+        let report = run(EXPRESSIONS, Method::Lr0, "a").unwrap();
+        assert!(report.conflicts.contains("shift/reduce"));
+    }
+
+    #[test]
+    fn lalr_lookaheads_are_listed_with_the_items() {
+        let report = run(EXPRESSIONS, Method::Lalr1, "a").unwrap();
+        assert!(report.states.contains("E' -> • E, $"));
+    }
+
+    #[test]
+    fn reports_the_unexpected_token() {
+        let report = run(EXPRESSIONS, Method::Slr1, "a + * b").unwrap();
+        assert_eq!(
+            report.error,
+            "Syntax error at 0:5: expected ( or Identifier, found *"
+        );
+    }
+
+    #[test]
+    fn lalr_rejects_a_nonproductive_grammar() {
+        let error = run("S -> A N\nA -> x\nN -> N z", Method::Lalr1, "x")
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "Grammar error: nonproductive S, N");
+    }
+
+    #[test]
+    fn nothing_can_follow_a_nonproductive_prefix() {
+        let report = run("S -> A N\nA -> x ';'\nN -> N z", Method::Slr1, "x;").unwrap();
+        assert_eq!(report.error, "Syntax error: unexpected end of program");
+    }
+}
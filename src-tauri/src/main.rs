@@ -1,10 +1,58 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod analysis;
+mod automaton;
+mod automaton_diff;
+mod automaton_export;
+mod automaton_import;
+mod cfg;
+mod cleanup;
+mod consistency;
+mod descriptors;
+mod dfa;
+mod diagram;
+mod grammar;
+mod keywords;
+mod lexgen;
+mod lowering;
+mod lr;
+mod minimize;
+mod nfa;
+mod optimize;
+mod project;
+mod quads;
+mod rpn;
+mod semantic;
+mod simulate;
+mod syntax;
+mod tree;
+mod vm;
+mod watch;
+
+use analysis::{Analysis, Output};
+use automaton::AutomatonReport;
+use automaton_diff::DiffReport;
+use automaton_export::Format;
+use consistency::ConsistencyReport;
+use dfa::DfaReport;
+use grammar::Ll1Report;
+use lexgen::LexgenReport;
+use lr::{LrReport, Method};
+use minimize::MinimizeReport;
+use nfa::NfaReport;
+use project::Project;
 use serde::{Deserialize, Serialize};
+use simulate::SimulationReport;
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::SystemTime;
+use tauri::Manager;
+use vm::Machine;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Clean {
     base: String,
 }
@@ -15,18 +63,667 @@ fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
+/// State shared between commands for the lifetime of the window
+#[derive(Default)]
+pub struct Session {
+    /// Directory of the last opened file, used as the dialog starting point
+    last_dir: Mutex<Option<PathBuf>>,
+    /// File the source is saved to by `save`
+    current: Mutex<Option<PathBuf>>,
+    /// Source was edited since it was last opened or saved
+    dirty: Mutex<bool>,
+    /// Modification time of `current` as last seen by us, see `watch`
+    modified: Mutex<Option<SystemTime>>,
+    /// Modification time of the last change `watch` reported, seen once the
+    /// frontend has reloaded or dismissed it
+    reported: Mutex<Option<SystemTime>>,
+    /// Stack machine loaded by `vm_load`
+    machine: Mutex<Option<Machine>>,
+    /// Set by `vm_stop` to end the `vm_run` in progress
+    stop: AtomicBool,
+}
+
+impl Session {
+    fn remember(&self, path: &Path) {
+        if let Some(dir) = path.parent() {
+            *self.last_dir.lock().unwrap() = Some(dir.to_path_buf());
+        }
+    }
+
+    /// Records that `current` is in sync with the editor so the watcher doesn't report it
+    fn stamp(&self) {
+        let current = self.current.lock().unwrap();
+        *self.modified.lock().unwrap() = current
+            .as_ref()
+            .and_then(|path| fs::metadata(path).and_then(|m| m.modified()).ok());
+    }
+}
+
+/// What a file dialog asks for
+enum Pick<'a> {
+    File,
+    Folder,
+    /// A file to save to, `name` is the suggested file name
+    Save(&'a str),
+}
+
+/// Asks for a path, starting in the directory of the last opened file. `None` means
+/// the dialog was cancelled
+fn pick_file(session: &Session, filters: &[(&str, &[&str])], pick: Pick) -> Option<PathBuf> {
+    let mut dialog = tauri::api::dialog::blocking::FileDialogBuilder::new();
+    for (name, extensions) in filters {
+        dialog = dialog.add_filter(name, extensions);
+    }
+    if let Some(dir) = session.last_dir.lock().unwrap().as_ref() {
+        dialog = dialog.set_directory(dir);
+    }
+    match pick {
+        Pick::File => dialog.pick_file(),
+        Pick::Folder => dialog.pick_folder(),
+        Pick::Save(name) => dialog.set_file_name(name).save_file(),
+    }
+}
+
+/// `path` as typed, or one picked in a dialog when it is empty
+fn typed_or_picked(
+    path: &str,
+    session: &Session,
+    filters: &[(&str, &[&str])],
+    pick: Pick,
+) -> Option<PathBuf> {
+    if path.is_empty() {
+        pick_file(session, filters, pick)
+    } else {
+        Some(PathBuf::from(path))
+    }
+}
+
+/// Asks for a file and writes `text` to it. Returns the path written, `None` if cancelled
+fn save_picked(name: &str, text: String, session: &Session) -> Result<Option<String>, String> {
+    match pick_file(session, &[], Pick::Save(name)) {
+        Some(path) => {
+            fs::write(&path, text).map_err(|e| format!("{}: {}", path.display(), e))?;
+            session.remember(&path);
+            Ok(Some(path.display().to_string()))
+        }
+        None => Ok(None),
+    }
+}
+
+/// Reads `path`, or asks for a file when it is empty. `None` means the dialog was cancelled
 #[tauri::command(async)]
-fn read(_path: &str) -> Clean {
-    let path = tauri::api::dialog::blocking::FileDialogBuilder::new()
-        .set_directory("/home/chiffa/Dev/Projects/")
-        .pick_file();
-    let base = fs::read_to_string(path.unwrap()).unwrap_or("Error".to_owned());
-    Clean { base }
+fn read(path: &str, session: tauri::State<Session>) -> Result<Option<Clean>, String> {
+    let Some(path) = typed_or_picked(path, &session, &[], Pick::File) else {
+        return Ok(None);
+    };
+    let base = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    session.remember(&path);
+    *session.current.lock().unwrap() = Some(path);
+    *session.dirty.lock().unwrap() = false;
+    session.stamp();
+    Ok(Some(Clean { base }))
+}
+
+fn write(path: &Path, text: String, output: Output, session: &Session) -> Result<String, String> {
+    let contents = analysis::render(text, output)?;
+    fs::write(path, contents).map_err(|e| format!("{}: {}", path.display(), e))?;
+    session.remember(path);
+    if output == Output::Source {
+        *session.current.lock().unwrap() = Some(path.to_path_buf());
+        *session.dirty.lock().unwrap() = false;
+    }
+    session.stamp();
+    Ok(path.display().to_string())
+}
+
+/// Writes the source back to the opened file. Returns the path written, `None` if cancelled
+#[tauri::command(async)]
+fn save(text: String, session: tauri::State<Session>) -> Result<Option<String>, String> {
+    let current = session.current.lock().unwrap().clone();
+    match current {
+        Some(path) => write(&path, text, Output::Source, &session).map(Some),
+        None => save_as(text, Output::Source, session),
+    }
+}
+
+/// Asks for a file and writes `output` of the program to it. `None` means the dialog was cancelled
+#[tauri::command(async)]
+fn save_as(
+    text: String,
+    output: Output,
+    session: tauri::State<Session>,
+) -> Result<Option<String>, String> {
+    let name = session
+        .current
+        .lock()
+        .unwrap()
+        .as_ref()
+        .and_then(|path| path.file_stem())
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or("program".to_owned());
+    let name = match output {
+        Output::Source => format!("{}.c", name),
+        Output::Cleaned => format!("{}.clean.c", name),
+        Output::Pseudocode => format!("{}.pseudo.txt", name),
+        Output::TreeDot => format!("{}.ast.dot", name),
+        Output::TreeText => format!("{}.ast.txt", name),
+        Output::Rpn => format!("{}.rpn.txt", name),
+        Output::Quads => format!("{}.quads.txt", name),
+        Output::Optimized => format!("{}.opt.txt", name),
+        Output::CfgDot => format!("{}.cfg.dot", name),
+        Output::CfgSvg => format!("{}.cfg.svg", name),
+    };
+    match pick_file(&session, &[], Pick::Save(&name)) {
+        Some(path) => write(&path, text, output, &session).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Asks for a file and writes `text` to it, used for tables and traces
+#[tauri::command(async)]
+fn export(
+    text: String,
+    name: String,
+    session: tauri::State<Session>,
+) -> Result<Option<String>, String> {
+    save_picked(&name, text, &session)
+}
+
+#[tauri::command]
+fn set_dirty(dirty: bool, session: tauri::State<Session>) {
+    *session.dirty.lock().unwrap() = dirty;
+}
+
+/// The change `watch` reported was reloaded or dismissed, don't report it again
+#[tauri::command]
+fn file_change_handled(session: tauri::State<Session>) {
+    let reported = *session.reported.lock().unwrap();
+    *session.modified.lock().unwrap() = reported;
+}
+
+/// Analyses a directory, asking for one when `path` is empty. `None` means the dialog was cancelled
+#[tauri::command(async)]
+fn open_project(path: &str, session: tauri::State<Session>) -> Result<Option<Project>, String> {
+    let Some(root) = typed_or_picked(path, &session, &[], Pick::Folder) else {
+        return Ok(None);
+    };
+    let project = project::open_project(&root)?;
+    *session.last_dir.lock().unwrap() = Some(root);
+    Ok(Some(project))
+}
+
+#[tauri::command(async)]
+fn analyze(text: String) -> Analysis {
+    analysis::analyze(text)
+}
+
+/// Builds the LL(1) table for `grammar` and parses the program with it
+#[tauri::command(async)]
+fn ll1(grammar: String, text: String) -> Result<Ll1Report, String> {
+    let grammar = grammar::parse_grammar(&grammar).map_err(|e| e.to_string())?;
+    let tokens = analysis::tokenize(text)?;
+    Ok(grammar::ll1(&grammar, &tokens))
+}
+
+/// Builds the LR automaton for `grammar` and parses the program with it
+#[tauri::command(async)]
+fn lr(grammar: String, method: Method, text: String) -> Result<LrReport, String> {
+    let grammar = grammar::parse_grammar(&grammar).map_err(|e| e.to_string())?;
+    let tokens = analysis::tokenize(text)?;
+    lr::lr(&grammar, method, &tokens).map_err(|e| e.to_string())
+}
+
+/// Translates the program to reverse Polish notation and loads it into a fresh machine
+#[tauri::command]
+fn vm_load(text: String, session: tauri::State<Session>) -> Result<vm::State, String> {
+    let (rpn, symbols) = analysis::translate(text)?;
+    let machine = Machine::new(rpn, symbols.identifiers);
+    let state = machine.state();
+    session.stop.store(true, Ordering::Relaxed);
+    *session.machine.lock().unwrap() = Some(machine);
+    Ok(state)
+}
+
+#[tauri::command]
+fn vm_step(session: tauri::State<Session>) -> Result<vm::State, String> {
+    match session.machine.lock().unwrap().as_mut() {
+        Some(machine) => {
+            machine.step();
+            Ok(machine.state())
+        }
+        None => Err("No program loaded".to_owned()),
+    }
+}
+
+/// Runs until the machine halts, reaches one of `breakpoints` or `vm_stop` is called.
+/// The machine is unlocked between slices of `vm::SLICE` steps so the other commands don't wait
+#[tauri::command(async)]
+fn vm_run(breakpoints: Vec<usize>, session: tauri::State<Session>) -> Result<vm::State, String> {
+    session.stop.store(false, Ordering::Relaxed);
+    let mut started = false;
+    loop {
+        let mut machine = session.machine.lock().unwrap();
+        let Some(machine) = machine.as_mut() else {
+            return Err("No program loaded".to_owned());
+        };
+        let done = if started {
+            machine.resume(&breakpoints)
+        } else {
+            machine.run(&breakpoints)
+        };
+        if done || session.stop.load(Ordering::Relaxed) {
+            return Ok(machine.state());
+        }
+        started = true;
+    }
+}
+
+#[tauri::command]
+fn vm_stop(session: tauri::State<Session>) {
+    session.stop.store(true, Ordering::Relaxed);
+}
+
+/// Reads a transition list or a lexer spec from `path`, or asks for one when it is empty.
+/// Unlike `read` this leaves the program file alone
+#[tauri::command(async)]
+fn read_automaton(path: &str, session: tauri::State<Session>) -> Result<Option<String>, String> {
+    let Some(path) = typed_or_picked(path, &session, &[], Pick::File) else {
+        return Ok(None);
+    };
+    let text = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    session.remember(&path);
+    Ok(Some(text))
+}
+
+#[tauri::command(async)]
+fn check_automaton(text: String) -> AutomatonReport {
+    automaton::check(&text)
+}
+
+/// Compares a transition list with the transitions `count_tokens` implements
+#[tauri::command(async)]
+fn check_lexer(text: String) -> ConsistencyReport {
+    consistency::check(&text)
+}
+
+/// Structural diff of two transition lists and whether they accept the same language
+#[tauri::command(async)]
+fn diff_automata(old: String, new: String) -> DiffReport {
+    automaton_diff::compare(&old, &new)
+}
+
+/// Transition list of the automaton `count_tokens` implements
+#[tauri::command(async)]
+fn lexer_automaton() -> String {
+    consistency::lexer_automaton().to_text()
+}
+
+/// Asks for a file and writes the automaton described by `text` to it in `format`
+#[tauri::command(async)]
+fn export_automaton(
+    text: String,
+    format: Format,
+    session: tauri::State<Session>,
+) -> Result<Option<String>, String> {
+    let contents = automaton_export::export(&text, format)?;
+    let name = format!("automaton.{}", format.extension());
+    save_picked(&name, contents, &session)
+}
+
+/// Reads a draw.io diagram, asking for one when `path` is empty, and returns
+/// it as a transition list. `None` means the dialog was cancelled
+#[tauri::command(async)]
+fn import_drawio(path: &str, session: tauri::State<Session>) -> Result<Option<String>, String> {
+    let filters: &[(&str, &[&str])] = &[("draw.io", &["drawio", "xml"])];
+    let Some(path) = typed_or_picked(path, &session, filters, Pick::File) else {
+        return Ok(None);
+    };
+    let xml = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    session.remember(&path);
+    automaton_import::import_text(&xml).map(Some)
+}
+
+/// Thompson NFA of a regular expression as a table, DOT and SVG
+#[tauri::command(async)]
+fn regex_to_nfa(regex: String) -> NfaReport {
+    nfa::regex_to_nfa(&regex)
+}
+
+/// DFA of a regular expression by the subset construction, with its trace
+#[tauri::command(async)]
+fn regex_to_dfa(regex: String) -> DfaReport {
+    dfa::regex_to_dfa(&regex)
+}
+
+/// Compares the DFA of a regular expression with the automaton `count_tokens` implements
+#[tauri::command(async)]
+fn compare_dfa_with_lexer(regex: String) -> Result<String, String> {
+    dfa::compare_with_lexer(&regex)
+}
+
+/// Minimises the automaton described by a transition list
+#[tauri::command(async)]
+fn minimize_automaton(text: String) -> MinimizeReport {
+    minimize::minimize_text(&text)
+}
+
+/// Minimises the DFA of a regular expression
+#[tauri::command(async)]
+fn minimize_regex(regex: String) -> MinimizeReport {
+    minimize::minimize_regex(&regex)
+}
+
+/// Builds the scanner a lexer spec describes and runs it on `program`
+#[tauri::command(async)]
+fn generate_lexer(spec: String, program: String) -> LexgenReport {
+    lexgen::generate(&spec, &program)
+}
+
+/// Runs `count_tokens` on `text` one character at a time
+#[tauri::command(async)]
+fn simulate_lexer(text: String) -> SimulationReport {
+    simulate::simulate(&text)
+}
+
+/// `automata_project analyze <file>` prints the analysis without starting the UI,
+/// `automata_project automaton <file>` checks a transition list,
+/// `automata_project consistency <file>` compares it with the lexer,
+/// `automata_project diff <old> <new>` compares two of them,
+/// `automata_project export <file> drawio|dot|mermaid` converts one,
+/// `automata_project import <file.drawio>` reads one from a diagram,
+/// `automata_project lexer` prints the one `count_tokens` implements,
+/// `automata_project nfa <regex>` prints the Thompson NFA of a regular expression,
+/// `automata_project dfa <regex>` its DFA with the subset construction trace,
+/// `automata_project minimize <file>` minimises a transition list,
+/// `automata_project lexgen <spec>` prints the Rust scanner a lexer spec describes,
+/// `automata_project lexgen <spec> <program>` the tokens it finds in a program and
+/// `automata_project simulate <file>` every step the lexer takes on a program
+fn run_cli(args: &[String]) -> Option<i32> {
+    match args {
+        [_, cmd, path] if cmd == "simulate" => match fs::read_to_string(path) {
+            Ok(text) => {
+                let report = simulate::simulate(&text);
+                println!("{}", report.trace.trim_start());
+                if !report.error.is_empty() {
+                    eprintln!("{}", report.error);
+                    return Some(1);
+                }
+                Some(0)
+            }
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                Some(1)
+            }
+        },
+        [_, cmd, spec, rest @ ..] if cmd == "lexgen" && rest.len() <= 1 => {
+            let program = match rest.first().map(fs::read_to_string) {
+                Some(Ok(program)) => program,
+                Some(Err(e)) => {
+                    eprintln!("{}: {}", rest[0], e);
+                    return Some(1);
+                }
+                None => String::new(),
+            };
+            match fs::read_to_string(spec) {
+                Ok(spec) => {
+                    let report = lexgen::generate(&spec, &program);
+                    if !report.error.is_empty() {
+                        eprintln!("{}", report.error);
+                        return Some(1);
+                    }
+                    if program.is_empty() {
+                        print!("{}", report.rust);
+                    } else {
+                        print!(
+                            "лексемы{}\nсверка с keywords.rs{}\n",
+                            report.tokens, report.comparison
+                        );
+                    }
+                    Some(0)
+                }
+                Err(e) => {
+                    eprintln!("{}: {}", spec, e);
+                    Some(1)
+                }
+            }
+        }
+        [_, cmd, path] if cmd == "minimize" => match fs::read_to_string(path) {
+            Ok(text) => {
+                let report = minimize::minimize_text(&text);
+                if !report.error.is_empty() {
+                    eprintln!("{}", report.error);
+                    return Some(1);
+                }
+                print!(
+                    "объединённые{}\nХопкрофт{}\nзаполнение таблицы{}\nминимальный ДКА{}\n",
+                    report.merged, report.hopcroft, report.table_filling, report.table
+                );
+                Some(0)
+            }
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                Some(1)
+            }
+        },
+        [_, cmd, regex] if cmd == "dfa" => {
+            let report = dfa::regex_to_dfa(regex);
+            if !report.error.is_empty() {
+                eprintln!("{}", report.error);
+                return Some(1);
+            }
+            println!("{}\n{}", report.trace.trim_start(), report.table);
+            Some(0)
+        }
+        [_, cmd, regex] if cmd == "nfa" => {
+            let report = nfa::regex_to_nfa(regex);
+            if !report.error.is_empty() {
+                eprintln!("{}", report.error);
+                return Some(1);
+            }
+            println!("{}", report.table.trim_start());
+            Some(0)
+        }
+        [_, cmd, path] if cmd == "import" => match fs::read_to_string(path)
+            .map_err(|e| format!("{}: {}", path, e))
+            .and_then(|xml| automaton_import::import_text(&xml))
+        {
+            Ok(text) => {
+                print!("{}", text);
+                Some(0)
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                Some(1)
+            }
+        },
+        [_, cmd] if cmd == "lexer" => {
+            print!("{}", consistency::lexer_automaton().to_text());
+            Some(0)
+        }
+        [_, cmd, path, format] if cmd == "export" => {
+            let format = match format.as_str() {
+                "drawio" => Format::Drawio,
+                "dot" => Format::Dot,
+                "mermaid" => Format::Mermaid,
+                _ => {
+                    eprintln!("Unknown format {}, expected drawio, dot or mermaid", format);
+                    return Some(1);
+                }
+            };
+            match fs::read_to_string(path)
+                .map_err(|e| format!("{}: {}", path, e))
+                .and_then(|text| automaton_export::export(&text, format))
+            {
+                Ok(out) => {
+                    print!("{}", out);
+                    Some(0)
+                }
+                Err(e) => {
+                    eprintln!("{}", e);
+                    Some(1)
+                }
+            }
+        }
+        [_, cmd, old, new] if cmd == "diff" => {
+            match (fs::read_to_string(old), fs::read_to_string(new)) {
+                (Ok(old), Ok(new)) => {
+                    let report = automaton_diff::compare(&old, &new);
+                    if !report.error.is_empty() {
+                        eprintln!("{}", report.error);
+                        return Some(1);
+                    }
+                    print!(
+                        "состояния{}\nпереходы{}\nдопускающие{}\nэквивалентность{}\n",
+                        report.states, report.transitions, report.accepting, report.equivalence
+                    );
+                    Some(0)
+                }
+                (Err(e), _) => {
+                    eprintln!("{}: {}", old, e);
+                    Some(1)
+                }
+                (_, Err(e)) => {
+                    eprintln!("{}: {}", new, e);
+                    Some(1)
+                }
+            }
+        }
+        [_, cmd, path] if cmd == "automaton" => match fs::read_to_string(path) {
+            Ok(text) => {
+                let report = automaton::check(&text);
+                if !report.error.is_empty() {
+                    eprintln!("{}", report.error);
+                    return Some(1);
+                }
+                print!(
+                    "состояния{}\nпереходы{}\nзамечания{}\n",
+                    report.states, report.transitions, report.issues
+                );
+                Some(if report.issues.is_empty() { 0 } else { 1 })
+            }
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                Some(1)
+            }
+        },
+        [_, cmd, path] if cmd == "consistency" => match fs::read_to_string(path) {
+            Ok(text) => {
+                let report = consistency::check(&text);
+                if !report.error.is_empty() {
+                    eprintln!("{}", report.error);
+                    return Some(1);
+                }
+                print!("{}\n{}", report.summary, report.differences);
+                Some(0)
+            }
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                Some(1)
+            }
+        },
+        [_, cmd, path] if cmd == "analyze" => match fs::read_to_string(path) {
+            Ok(text) => {
+                let result = analysis::analyze(text);
+                print!("{}", result.report());
+                if result.error.is_empty() {
+                    Some(0)
+                } else {
+                    eprintln!("{}", result.error);
+                    Some(1)
+                }
+            }
+            Err(e) => {
+                eprintln!("{}: {}", path, e);
+                Some(1)
+            }
+        },
+        _ => None,
+    }
+}
+
+/// Release builds on Windows run without a console, so `run_cli` writes to the one
+/// it was started from. `cmd` doesn't wait for such programs and prints its prompt
+/// before the output
+#[cfg(windows)]
+fn attach_console() {
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn AttachConsole(process_id: u32) -> i32;
+    }
+    const ATTACH_PARENT_PROCESS: u32 = u32::MAX;
+    // Fails when there is a console already or the parent has none, both are fine
+    unsafe {
+        AttachConsole(ATTACH_PARENT_PROCESS);
+    }
 }
 
 fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    #[cfg(windows)]
+    if args.len() > 1 {
+        attach_console();
+    }
+    if let Some(code) = run_cli(&args) {
+        std::process::exit(code);
+    }
     tauri::Builder::default()
-        .invoke_handler(tauri::generate_handler![greet, read])
+        .manage(Session::default())
+        .setup(|app| {
+            watch::spawn(app.handle());
+            Ok(())
+        })
+        .on_window_event(|event| {
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event.event() {
+                let window = event.window().clone();
+                if !*window.state::<Session>().dirty.lock().unwrap() {
+                    return;
+                }
+                api.prevent_close();
+                let parent = window.clone();
+                tauri::api::dialog::confirm(
+                    Some(&parent),
+                    "Unsaved changes",
+                    "The program has unsaved changes. Close anyway?",
+                    move |close| {
+                        if close {
+                            *window.state::<Session>().dirty.lock().unwrap() = false;
+                            let _ = window.close();
+                        }
+                    },
+                );
+            }
+        })
+        .invoke_handler(tauri::generate_handler![
+            greet,
+            read,
+            analyze,
+            save,
+            save_as,
+            export,
+            set_dirty,
+            file_change_handled,
+            open_project,
+            ll1,
+            lr,
+            vm_load,
+            vm_step,
+            vm_run,
+            vm_stop,
+            read_automaton,
+            check_automaton,
+            check_lexer,
+            diff_automata,
+            lexer_automaton,
+            export_automaton,
+            import_drawio,
+            regex_to_nfa,
+            regex_to_dfa,
+            compare_dfa_with_lexer,
+            minimize_automaton,
+            minimize_regex,
+            generate_lexer,
+            simulate_lexer
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -0,0 +1,682 @@
+use crate::automaton::{alphabet, format_chars, parse, Automaton, CharClass, Transition};
+use crate::dfa::Dfa;
+use crate::grammar::format_table;
+use crate::keywords::{token_classes, variant};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Complete enough DFA to minimise. Characters no state tells apart share a
+/// column, a missing move goes to the implicit dead state
+#[derive(Debug, Clone)]
+pub struct Machine {
+    pub names: Vec<String>,
+    pub start: usize,
+    /// What each state accepts, states with different labels never merge
+    pub labels: Vec<Option<String>>,
+    pub columns: Vec<Vec<char>>,
+    /// Target by state and column
+    pub moves: Vec<Vec<Option<usize>>>,
+}
+
+/// Groups `alphabet` into columns by the targets each character has in
+/// every state, characters no state moves on are left out
+fn columns(targets: &[BTreeMap<char, usize>]) -> (Vec<Vec<char>>, Vec<Vec<Option<usize>>>) {
+    let mut columns: Vec<(Vec<Option<usize>>, Vec<char>)> = vec![];
+    for c in alphabet() {
+        let column: Vec<Option<usize>> = targets.iter().map(|m| m.get(&c).cloned()).collect();
+        if column.iter().all(|t| t.is_none()) {
+            continue;
+        }
+        match columns.iter_mut().find(|(t, _)| *t == column) {
+            Some((_, chars)) => chars.push(c),
+            None => columns.push((column, vec![c])),
+        }
+    }
+    let moves = (0..targets.len())
+        .map(|state| columns.iter().map(|(t, _)| t[state]).collect())
+        .collect();
+    (columns.into_iter().map(|(_, chars)| chars).collect(), moves)
+}
+
+impl Machine {
+    /// States reachable from the start, the first matching transition wins as
+    /// in `Automaton::step`. Accepting states named after a state of
+    /// `count_tokens` are labelled with the class of the token it flushes,
+    /// the others share one label. Also returns the unreachable states left out
+    pub fn from_automaton(automaton: &Automaton) -> (Machine, Vec<String>) {
+        let reachable = automaton.reachable();
+        let names: Vec<String> = automaton
+            .states
+            .iter()
+            .filter(|s| reachable.contains(*s))
+            .cloned()
+            .collect();
+        let unreachable = automaton
+            .states
+            .iter()
+            .filter(|s| !reachable.contains(*s))
+            .cloned()
+            .collect();
+        let index: BTreeMap<&str, usize> = names
+            .iter()
+            .enumerate()
+            .map(|(idx, name)| (name.as_str(), idx))
+            .collect();
+        let mut targets = vec![BTreeMap::new(); names.len()];
+        for t in &automaton.transitions {
+            let (from, to) = match (index.get(t.from.as_str()), index.get(t.to.as_str())) {
+                (Some(from), Some(to)) => (*from, *to),
+                _ => continue,
+            };
+            for c in t.class.chars() {
+                targets[from].entry(c).or_insert(to);
+            }
+        }
+        let (columns, moves) = columns(&targets);
+        let classes = token_classes(&alphabet());
+        let labels = names
+            .iter()
+            .map(|s| {
+                automaton.accepting.contains(s).then(|| {
+                    classes
+                        .get(variant(s))
+                        .map_or("accept".to_owned(), |class| format!("{:?}", class))
+                })
+            })
+            .collect();
+        (
+            Machine {
+                start: index[automaton.start.as_str()],
+                names,
+                labels,
+                columns,
+                moves,
+            },
+            unreachable,
+        )
+    }
+
+    /// States `D0`, `D1`... labelled with what they accept
+    pub fn from_dfa(dfa: &Dfa) -> Machine {
+        let (columns, moves) = columns(&dfa.moves);
+        Machine {
+            names: (0..dfa.sets.len()).map(crate::dfa::name).collect(),
+            start: 0,
+            labels: (0..dfa.sets.len())
+                .map(|s| dfa.accepting.get(&s).cloned())
+                .collect(),
+            columns,
+            moves,
+        }
+    }
+
+    /// Next state on `c`, `None` rejects
+    pub fn step(&self, state: usize, c: char) -> Option<usize> {
+        let column = self.columns.iter().position(|chars| chars.contains(&c))?;
+        self.moves[state][column]
+    }
+
+    /// Target with the dead state made explicit as `names.len()`
+    fn target(&self, state: usize, column: usize) -> usize {
+        let dead = self.names.len();
+        if state == dead {
+            return dead;
+        }
+        self.moves[state][column].unwrap_or(dead)
+    }
+
+    /// Blocks of the starting partition: one per label, the dead state
+    /// goes with the states that don't accept
+    fn initial(&self) -> Vec<usize> {
+        let mut labels: Vec<Option<&String>> = vec![];
+        let mut block = vec![];
+        for label in self.labels.iter().chain([&None]) {
+            let label = label.as_ref();
+            match labels.iter().position(|l| *l == label) {
+                Some(idx) => block.push(idx),
+                None => {
+                    labels.push(label);
+                    block.push(labels.len() - 1);
+                }
+            }
+        }
+        block
+    }
+
+    fn describe(&self, states: &[usize]) -> String {
+        const SHOWN: usize = 6;
+        let dead = self.names.len();
+        let mut names: Vec<&str> = states
+            .iter()
+            .take(SHOWN)
+            .map(|s| if *s == dead { "dead" } else { &self.names[*s] })
+            .collect();
+        if states.len() > SHOWN {
+            names.push("...");
+        }
+        format!("{{{}}} ({})", names.join(", "), states.len())
+    }
+
+    /// Hopcroft's partition refinement. Returns the block of every state,
+    /// the dead state last, and the splits in the order they happened
+    pub fn hopcroft(&self) -> (Vec<usize>, Vec<String>) {
+        let count = self.names.len() + 1;
+        let mut block = self.initial();
+        let mut blocks: Vec<Vec<usize>> = vec![];
+        for (state, b) in block.iter().enumerate() {
+            if *b == blocks.len() {
+                blocks.push(vec![]);
+            }
+            blocks[*b].push(state);
+        }
+        // States moving into each state, by column
+        let mut inverse = vec![vec![vec![]; count]; self.columns.len()];
+        for state in 0..count {
+            for column in 0..self.columns.len() {
+                inverse[column][self.target(state, column)].push(state);
+            }
+        }
+        let mut pending: BTreeSet<(usize, usize)> = BTreeSet::new();
+        for b in 0..blocks.len() {
+            for column in 0..self.columns.len() {
+                pending.insert((b, column));
+            }
+        }
+        let mut trace = vec![];
+        while let Some((splitter, column)) = pending.pop_first() {
+            let mut into: BTreeSet<usize> = BTreeSet::new();
+            for target in &blocks[splitter] {
+                into.extend(&inverse[column][*target]);
+            }
+            let touched: BTreeSet<usize> = into.iter().map(|s| block[*s]).collect();
+            for b in touched {
+                let (inside, outside): (Vec<usize>, Vec<usize>) =
+                    blocks[b].iter().partition(|s| into.contains(s));
+                if outside.is_empty() {
+                    continue;
+                }
+                trace.push(format!(
+                    "{} on {} into {}: {} and {}",
+                    self.describe(&blocks[b]),
+                    format_chars(&self.columns[column]),
+                    self.describe(&blocks[splitter]),
+                    self.describe(&inside),
+                    self.describe(&outside)
+                ));
+                let new = blocks.len();
+                for s in &outside {
+                    block[*s] = new;
+                }
+                blocks[b] = inside;
+                blocks.push(outside);
+                for c in 0..self.columns.len() {
+                    if pending.contains(&(b, c)) || blocks[new].len() < blocks[b].len() {
+                        pending.insert((new, c));
+                    } else {
+                        pending.insert((b, c));
+                    }
+                }
+            }
+        }
+        (block, trace)
+    }
+
+    /// The table-filling algorithm: pairs with different labels are marked
+    /// first, then every pair moving into a marked pair, round after round.
+    /// Returns the block of every state, the dead state last, and the rounds
+    pub fn table_filling(&self) -> (Vec<usize>, Vec<String>) {
+        let count = self.names.len() + 1;
+        let initial = self.initial();
+        // Round a pair was marked in, the lower state first
+        let mut marked: Vec<Vec<Option<usize>>> = (0..count).map(|i| vec![None; i]).collect();
+        let mut trace = vec![];
+        let mut total = 0;
+        for j in 0..count {
+            for i in 0..j {
+                if initial[i] != initial[j] {
+                    marked[j][i] = Some(0);
+                    total += 1;
+                }
+            }
+        }
+        trace.push(format!(
+            "round 0: {} pairs differ in what they accept",
+            total
+        ));
+        let mut round = 0;
+        loop {
+            round += 1;
+            let mut changed = 0;
+            for j in 0..count {
+                for i in 0..j {
+                    if marked[j][i].is_some() {
+                        continue;
+                    }
+                    let distinguished = (0..self.columns.len()).find(|column| {
+                        let (a, b) = (self.target(i, *column), self.target(j, *column));
+                        a != b && marked[a.max(b)][a.min(b)].is_some_and(|r| r < round)
+                    });
+                    if distinguished.is_some() {
+                        marked[j][i] = Some(round);
+                        changed += 1;
+                    }
+                }
+            }
+            if changed == 0 {
+                break;
+            }
+            trace.push(format!("round {}: {} pairs marked", round, changed));
+        }
+
+        // Unmarked pairs are equivalent, each state joins the block of the first one it pairs with
+        let mut block: Vec<usize> = (0..count).collect();
+        for j in 0..count {
+            if let Some(i) = (0..j).find(|i| marked[j][*i].is_none()) {
+                block[j] = block[i];
+            }
+        }
+        if count <= TRIANGLE_LIMIT {
+            trace.push(self.triangle(&marked));
+        }
+        (block, trace)
+    }
+
+    /// The classic triangular table, each cell is the round the pair was
+    /// marked in and empty for equivalent pairs
+    fn triangle(&self, marked: &[Vec<Option<usize>>]) -> String {
+        let count = marked.len();
+        let name = |s: usize| {
+            if s == self.names.len() {
+                "dead".to_owned()
+            } else {
+                self.names[s].clone()
+            }
+        };
+        let mut rows = vec![];
+        for (j, cells) in marked.iter().enumerate().skip(1) {
+            let mut row = vec![name(j)];
+            row.extend(
+                cells[..j]
+                    .iter()
+                    .map(|r| r.map_or(String::new(), |r| r.to_string())),
+            );
+            row.extend((j..count - 1).map(|_| String::new()));
+            rows.push(row);
+        }
+        let mut footer = vec!["".to_owned()];
+        footer.extend((0..count - 1).map(name));
+        rows.push(footer);
+        format_table(&rows)
+    }
+
+    /// One state per block, named after its first state. Blocks equivalent to
+    /// the dead state are dropped, the start is kept even when it is one
+    pub fn merge(&self, block: &[usize]) -> (Machine, Vec<Vec<usize>>) {
+        let dead = self.names.len();
+        let mut members: Vec<Vec<usize>> = vec![];
+        let mut index: BTreeMap<usize, usize> = BTreeMap::new();
+        for state in 0..dead {
+            if block[state] == block[dead] && state != self.start {
+                continue;
+            }
+            match index.get(&block[state]) {
+                Some(idx) => members[*idx].push(state),
+                None => {
+                    index.insert(block[state], members.len());
+                    members.push(vec![state]);
+                }
+            }
+        }
+        let moves = members
+            .iter()
+            .map(|m| {
+                (0..self.columns.len())
+                    .map(|column| {
+                        let target = self.target(m[0], column);
+                        if target == dead || block[target] == block[dead] {
+                            None
+                        } else {
+                            Some(index[&block[target]])
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let machine = Machine {
+            names: members.iter().map(|m| self.names[m[0]].clone()).collect(),
+            start: index[&block[self.start]],
+            labels: members.iter().map(|m| self.labels[m[0]].clone()).collect(),
+            columns: self.columns.clone(),
+            moves,
+        };
+        (machine, members)
+    }
+
+    /// Transition table, `->` marks the start and `*` accepting states
+    pub fn to_table(&self) -> String {
+        let mut header = vec!["".to_owned(), "state".to_owned(), "accepts".to_owned()];
+        header.extend(self.columns.iter().map(|chars| format_chars(chars)));
+        let mut rows = vec![header];
+        for (idx, name) in self.names.iter().enumerate() {
+            let mark = match (idx == self.start, self.labels[idx].is_some()) {
+                (true, true) => "->*",
+                (true, false) => "->",
+                (false, true) => "*",
+                (false, false) => "",
+            };
+            let mut row = vec![
+                mark.to_owned(),
+                name.clone(),
+                self.labels[idx].clone().unwrap_or_default(),
+            ];
+            row.extend(
+                self.moves[idx]
+                    .iter()
+                    .map(|t| t.map_or("-".to_owned(), |t| self.names[t].clone())),
+            );
+            rows.push(row);
+        }
+        format!("\n{}", format_table(&rows))
+    }
+
+    pub fn to_automaton(&self) -> Automaton {
+        let mut transitions = vec![];
+        for (from, moves) in self.moves.iter().enumerate() {
+            let mut targets: Vec<(usize, Vec<char>)> = vec![];
+            for (column, to) in moves.iter().enumerate() {
+                let to = match to {
+                    Some(to) => *to,
+                    None => continue,
+                };
+                match targets.iter_mut().find(|(t, _)| *t == to) {
+                    Some((_, chars)) => chars.extend(&self.columns[column]),
+                    None => targets.push((to, self.columns[column].clone())),
+                }
+            }
+            for (to, chars) in targets {
+                transitions.push(Transition {
+                    from: self.names[from].clone(),
+                    class: CharClass::from_chars(&chars),
+                    to: self.names[to].clone(),
+                    line: 0,
+                });
+            }
+        }
+        Automaton {
+            start: self.names[self.start].clone(),
+            states: self.names.clone(),
+            accepting: self
+                .names
+                .iter()
+                .zip(&self.labels)
+                .filter(|(_, l)| l.is_some())
+                .map(|(s, _)| s.clone())
+                .collect(),
+            transitions,
+        }
+    }
+
+    /// Rust source of a table driven recogniser for the DFA: `step` and
+    /// `longest_match`, which returns how many bytes of the text the longest
+    /// accepted prefix takes and what it accepts
+    pub fn to_rust(&self) -> String {
+        let mut out = format!(
+            "// Generated from a minimised DFA with {} states\n\n\
+             pub const START: usize = {};\n\
+             /// Marks a missing transition in `MOVES`\n\
+             pub const REJECT: usize = usize::MAX;\n\n",
+            self.names.len(),
+            self.start
+        );
+        out.push_str("/// What each state accepts\n");
+        out.push_str(&format!(
+            "pub const ACCEPTS: [Option<&str>; {}] = [\n",
+            self.names.len()
+        ));
+        for (name, label) in self.names.iter().zip(&self.labels) {
+            out.push_str(&format!("    {:?}, // {}\n", label.as_deref(), name));
+        }
+        out.push_str("];\n\n");
+
+        out.push_str("/// Column of `MOVES` a character uses\n");
+        out.push_str("pub fn column(c: char) -> Option<usize> {\n    match c {\n");
+        for (column, chars) in self.columns.iter().enumerate() {
+            out.push_str(&format!(
+                "        {} => Some({}),\n",
+                pattern(chars),
+                column
+            ));
+        }
+        out.push_str("        _ => None,\n    }\n}\n\n");
+
+        out.push_str(&format!(
+            "pub const MOVES: [[usize; {}]; {}] = [\n",
+            self.columns.len(),
+            self.names.len()
+        ));
+        for (name, moves) in self.names.iter().zip(&self.moves) {
+            let targets: Vec<String> = moves
+                .iter()
+                .map(|t| t.map_or("REJECT".to_owned(), |t| t.to_string()))
+                .collect();
+            out.push_str(&format!("    [{}], // {}\n", targets.join(", "), name));
+        }
+        out.push_str("];\n\n");
+
+        out.push_str(
+            "pub fn step(state: usize, c: char) -> Option<usize> {\n    \
+                 let target = MOVES[state][column(c)?];\n    \
+                 (target != REJECT).then_some(target)\n\
+             }\n\n\
+             pub fn longest_match(text: &str) -> Option<(usize, &'static str)> {\n    \
+                 let mut state = START;\n    \
+                 let mut last = ACCEPTS[state].map(|a| (0, a));\n    \
+                 for (idx, c) in text.char_indices() {\n        \
+                     state = match step(state, c) {\n            \
+                         Some(next) => next,\n            \
+                         None => break,\n        \
+                     };\n        \
+                     if let Some(accepts) = ACCEPTS[state] {\n            \
+                         last = Some((idx + c.len_utf8(), accepts));\n        \
+                     }\n    \
+                 }\n    \
+                 last\n\
+             }\n",
+        );
+        out
+    }
+}
+
+/// Pairs up to this many states, the dead one included, are shown as a table
+const TRIANGLE_LIMIT: usize = 30;
+
+/// Match pattern for `chars`, consecutive characters become ranges
+pub fn pattern(chars: &[char]) -> String {
+    let mut parts = vec![];
+    let mut idx = 0;
+    while idx < chars.len() {
+        let mut end = idx;
+        while end + 1 < chars.len() && chars[end + 1] as u32 == chars[end] as u32 + 1 {
+            end += 1;
+        }
+        if end > idx {
+            parts.push(format!("{:?}..={:?}", chars[idx], chars[end]));
+        } else {
+            parts.push(format!("{:?}", chars[idx]));
+        }
+        idx = end + 1;
+    }
+    parts.join(" | ")
+}
+
+/// Canonical form of a partition to compare the two algorithms with
+fn canonical(block: &[usize]) -> BTreeSet<Vec<usize>> {
+    let mut blocks: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    for (state, b) in block.iter().enumerate() {
+        blocks.entry(*b).or_default().push(state);
+    }
+    blocks.into_values().collect()
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MinimizeReport {
+    /// States each remaining state stands for and what was dropped
+    pub merged: String,
+    pub hopcroft: String,
+    pub table_filling: String,
+    pub table: String,
+    /// Transition list of the minimised DFA
+    pub text: String,
+    /// Rust source of a recogniser driven by the minimised table
+    pub rust: String,
+    pub error: String,
+}
+
+impl MinimizeReport {
+    fn error(error: String) -> MinimizeReport {
+        MinimizeReport {
+            merged: String::new(),
+            hopcroft: String::new(),
+            table_filling: String::new(),
+            table: String::new(),
+            text: String::new(),
+            rust: String::new(),
+            error,
+        }
+    }
+}
+
+/// Minimises with both algorithms, reports from Hopcroft's result and
+/// whether table filling agrees with it
+pub fn minimize(machine: &Machine, unreachable: &[String]) -> MinimizeReport {
+    let (hopcroft, splits) = machine.hopcroft();
+    let (filled, rounds) = machine.table_filling();
+    let (minimal, members) = machine.merge(&hopcroft);
+
+    let mut merged = format!(
+        "\n{} states -> {} states",
+        machine.names.len(),
+        minimal.names.len()
+    );
+    if canonical(&hopcroft) != canonical(&filled) {
+        merged.push_str("\ntable filling found a different partition");
+    }
+    for (name, states) in minimal.names.iter().zip(&members) {
+        if states.len() > 1 {
+            let names: Vec<&str> = states.iter().map(|s| machine.names[*s].as_str()).collect();
+            merged.push_str(&format!("\n{} = {}", name, names.join(", ")));
+        }
+    }
+    let kept: BTreeSet<usize> = members.iter().flatten().cloned().collect();
+    let dropped: Vec<&str> = (0..machine.names.len())
+        .filter(|s| !kept.contains(s))
+        .map(|s| machine.names[s].as_str())
+        .collect();
+    if !dropped.is_empty() {
+        merged.push_str(&format!("\nnever accept, dropped: {}", dropped.join(", ")));
+    }
+    if !unreachable.is_empty() {
+        merged.push_str(&format!(
+            "\nunreachable, dropped: {}",
+            unreachable.join(", ")
+        ));
+    }
+
+    MinimizeReport {
+        merged,
+        hopcroft: splits.iter().map(|l| format!("\n{}", l)).collect(),
+        table_filling: rounds.iter().map(|l| format!("\n{}", l)).collect(),
+        table: minimal.to_table(),
+        text: minimal.to_automaton().to_text(),
+        rust: minimal.to_rust(),
+        error: String::new(),
+    }
+}
+
+/// Minimises the automaton described by a transition list
+pub fn minimize_text(text: &str) -> MinimizeReport {
+    match parse(text) {
+        Ok((automaton, _)) => {
+            let (machine, unreachable) = Machine::from_automaton(&automaton);
+            minimize(&machine, &unreachable)
+        }
+        Err(e) => MinimizeReport::error(e.to_string()),
+    }
+}
+
+/// Minimises the subset construction DFA of `regex`
+pub fn minimize_regex(regex: &str) -> MinimizeReport {
+    match crate::dfa::build(regex) {
+        Ok((dfa, _)) => minimize(&Machine::from_dfa(&dfa), &[]),
+        Err(e) => MinimizeReport::error(e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn textbook_example_minimises_to_four_states() {
+        let (dfa, _) = crate::dfa::build("(a|b)*abb").unwrap();
+        let machine = Machine::from_dfa(&dfa);
+        let (hopcroft, _) = machine.hopcroft();
+        let (filled, _) = machine.table_filling();
+        assert_eq!(canonical(&hopcroft), canonical(&filled));
+        let (minimal, _) = machine.merge(&hopcroft);
+        assert_eq!(minimal.names.len(), 4);
+        let report = minimize_regex("(a|b)*abb");
+        assert!(report
+            .merged
+            .starts_with("\n5 states -> 4 states\nD0 = D0, D2"));
+    }
+
+    #[test]
+    fn drops_unreachable_and_merges_equivalent_states() {
+        let report = minimize_text("S->a->A\nS->b->B\nA->a->A\nB->a->B\nU->a->A");
+        assert_eq!(
+            report.merged,
+            "\n3 states -> 2 states\nA = A, B\nunreachable, dropped: U"
+        );
+        assert_eq!(report.error, "");
+        assert!(report.rust.contains("pub fn longest_match"));
+    }
+
+    #[test]
+    fn table_filling_records_the_round_pairs_are_marked_in() {
+        let (machine, _) = Machine::from_automaton(&parse("S->a->A\nA->a->B").unwrap().0);
+        let (_, rounds) = machine.table_filling();
+        assert!(rounds.iter().any(|r| r.starts_with("round 1:")));
+    }
+
+    #[test]
+    fn token_classes_of_the_lexer_never_merge() {
+        let lexer = crate::consistency::lexer_automaton();
+        let (machine, _) = Machine::from_automaton(&lexer);
+        let (block, _) = machine.hopcroft();
+        // The last state is the implicit dead one
+        for blocks in canonical(&block) {
+            let labels: BTreeSet<&Option<String>> = blocks
+                .iter()
+                .filter_map(|s| machine.labels.get(*s))
+                .collect();
+            assert_eq!(labels.len(), 1, "{}", machine.names[blocks[0]]);
+        }
+        let state = |name: &str| machine.names.iter().position(|s| s == name).unwrap();
+        assert_ne!(block[state("Separator(';')")], block[state("Incr")]);
+        assert_eq!(machine.labels[state("Mul")].as_deref(), Some("Operator"));
+        assert_eq!(
+            machine.labels[state("Separator(';')")].as_deref(),
+            Some("Separator")
+        );
+    }
+
+    #[test]
+    fn reports_errors_of_either_input() {
+        assert_eq!(
+            minimize_text("// nothing").error,
+            "Automaton has no transitions"
+        );
+        assert_eq!(minimize_regex("(").error, "Regex error at 1: expected ')'");
+    }
+}
@@ -0,0 +1,482 @@
+use crate::automaton::{alphabet, CharClass};
+use crate::diagram::Diagram;
+use crate::grammar::format_table;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+pub const EPSILON: &str = "ε";
+
+#[derive(Debug)]
+pub enum Error {
+    /// Character position in the expression and what is wrong there
+    Syntax(usize, String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Syntax(pos, what) => write!(f, "Regex error at {}: {}", pos, what),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Regex {
+    /// Matches the empty string, an empty alternative like the right side of `a|`
+    Empty,
+    Class(CharClass),
+    Concat(Box<Regex>, Box<Regex>),
+    Alt(Box<Regex>, Box<Regex>),
+    Star(Box<Regex>),
+    Plus(Box<Regex>),
+    Optional(Box<Regex>),
+}
+
+/// Recursive descent over
+/// `alt := concat ('|' concat)*`, `concat := repeat*`,
+/// `repeat := atom ('*' | '+' | '?')*`, `atom := '(' alt ')' | '[' set ']' | '.' | '\' c | c`
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).cloned()
+    }
+
+    fn error<T>(&self, what: &str) -> Result<T, Error> {
+        Err(Error::Syntax(self.pos, what.to_owned()))
+    }
+
+    fn alt(&mut self) -> Result<Regex, Error> {
+        let mut left = self.concat()?;
+        while self.peek() == Some('|') {
+            self.pos += 1;
+            let right = self.concat()?;
+            left = Regex::Alt(Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn concat(&mut self) -> Result<Regex, Error> {
+        let mut result: Option<Regex> = None;
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let next = self.repeat()?;
+            result = Some(match result {
+                Some(left) => Regex::Concat(Box::new(left), Box::new(next)),
+                None => next,
+            });
+        }
+        Ok(result.unwrap_or(Regex::Empty))
+    }
+
+    fn repeat(&mut self) -> Result<Regex, Error> {
+        let mut atom = self.atom()?;
+        while let Some(c) = self.peek() {
+            atom = match c {
+                '*' => Regex::Star(Box::new(atom)),
+                '+' => Regex::Plus(Box::new(atom)),
+                '?' => Regex::Optional(Box::new(atom)),
+                _ => break,
+            };
+            self.pos += 1;
+        }
+        Ok(atom)
+    }
+
+    fn atom(&mut self) -> Result<Regex, Error> {
+        let c = match self.peek() {
+            Some(c) => c,
+            None => return self.error("unexpected end"),
+        };
+        self.pos += 1;
+        let class = match c {
+            '(' => {
+                let inner = self.alt()?;
+                if self.peek() != Some(')') {
+                    return self.error("expected ')'");
+                }
+                self.pos += 1;
+                return Ok(inner);
+            }
+            '[' => self.set()?,
+            '.' => {
+                let chars: Vec<char> = alphabet().into_iter().filter(|c| *c != '\n').collect();
+                CharClass::from_chars(&chars)
+            }
+            '\\' => match self.peek() {
+                Some(e) => {
+                    self.pos += 1;
+                    escape_class(e)
+                }
+                None => return self.error("'\\' at the end"),
+            },
+            '*' | '+' | '?' => {
+                return Err(Error::Syntax(
+                    self.pos - 1,
+                    format!("nothing to repeat with '{}'", c),
+                ))
+            }
+            ')' => return Err(Error::Syntax(self.pos - 1, "unmatched ')'".to_owned())),
+            c => CharClass::from_chars(&[c]),
+        };
+        Ok(Regex::Class(class))
+    }
+
+    /// Inside `[...]`, `^` first negates over `alphabet`
+    fn set(&mut self) -> Result<CharClass, Error> {
+        let start = self.pos;
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.pos += 1;
+        }
+        let mut text = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(Error::Syntax(start - 1, "unclosed '['".to_owned())),
+                Some(']') if !text.is_empty() => break,
+                Some('\\') => {
+                    text.push('\\');
+                    self.pos += 1;
+                    match self.peek() {
+                        Some(c) => text.push(c),
+                        None => return self.error("'\\' at the end"),
+                    }
+                }
+                Some(c) => text.push(c),
+            }
+            self.pos += 1;
+        }
+        self.pos += 1;
+        let class = CharClass::parse_set(&text).map_err(|e| Error::Syntax(start, e))?;
+        if negated {
+            let rest: Vec<char> = alphabet()
+                .into_iter()
+                .filter(|c| !class.contains(*c))
+                .collect();
+            if rest.is_empty() {
+                return Err(Error::Syntax(start, "class matches nothing".to_owned()));
+            }
+            Ok(CharClass::from_chars(&rest))
+        } else {
+            Ok(class)
+        }
+    }
+}
+
+/// `\d`, `\w`, `\s` (a space, as in the transition files), `\n`, `\t`,
+/// anything else stands for itself
+fn escape_class(c: char) -> CharClass {
+    match c {
+        'd' => CharClass::from_chars(&('0'..='9').collect::<Vec<char>>()),
+        'w' => {
+            let chars: Vec<char> = alphabet()
+                .into_iter()
+                .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+                .collect();
+            CharClass::from_chars(&chars)
+        }
+        's' => CharClass::from_chars(&[' ']),
+        'n' => CharClass::from_chars(&['\n']),
+        't' => CharClass::from_chars(&['\t']),
+        c => CharClass::from_chars(&[c]),
+    }
+}
+
+/// Every character has to be in `alphabet`, the DFA and the checks built on
+/// the expression only go through those
+pub fn parse_regex(text: &str) -> Result<Regex, Error> {
+    let mut parser = Parser {
+        chars: text.chars().collect(),
+        pos: 0,
+    };
+    let chars = alphabet();
+    if let Some(pos) = parser.chars.iter().position(|c| !chars.contains(c)) {
+        return Err(Error::Syntax(
+            pos,
+            format!("'{}' is outside of the alphabet", parser.chars[pos]),
+        ));
+    }
+    let regex = parser.alt()?;
+    if parser.pos < parser.chars.len() {
+        return parser.error("unmatched ')'");
+    }
+    Ok(regex)
+}
+
+/// Transition on a class of characters, `None` is an ε-transition
+#[derive(Debug, Clone)]
+pub struct Edge {
+    pub from: usize,
+    pub label: Option<CharClass>,
+    pub to: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Nfa {
+    /// States are `0..states`
+    pub states: usize,
+    pub start: usize,
+    /// Accepting state and the name of what it accepts, the token class for a lexer
+    pub accepting: BTreeMap<usize, String>,
+    pub edges: Vec<Edge>,
+}
+
+impl Nfa {
+    fn state(&mut self) -> usize {
+        self.states += 1;
+        self.states - 1
+    }
+
+    fn edge(&mut self, from: usize, label: Option<CharClass>, to: usize) {
+        self.edges.push(Edge { from, label, to });
+    }
+
+    /// Thompson's construction: every fragment has one entry and one exit
+    fn fragment(&mut self, regex: &Regex) -> (usize, usize) {
+        match regex {
+            Regex::Empty => {
+                let (s, e) = (self.state(), self.state());
+                self.edge(s, None, e);
+                (s, e)
+            }
+            Regex::Class(class) => {
+                let (s, e) = (self.state(), self.state());
+                self.edge(s, Some(class.clone()), e);
+                (s, e)
+            }
+            Regex::Concat(a, b) => {
+                let (a1, a2) = self.fragment(a);
+                let (b1, b2) = self.fragment(b);
+                self.edge(a2, None, b1);
+                (a1, b2)
+            }
+            Regex::Alt(a, b) => {
+                let s = self.state();
+                let (a1, a2) = self.fragment(a);
+                let (b1, b2) = self.fragment(b);
+                let e = self.state();
+                self.edge(s, None, a1);
+                self.edge(s, None, b1);
+                self.edge(a2, None, e);
+                self.edge(b2, None, e);
+                (s, e)
+            }
+            Regex::Star(a) | Regex::Plus(a) | Regex::Optional(a) => {
+                let s = self.state();
+                let (a1, a2) = self.fragment(a);
+                let e = self.state();
+                self.edge(s, None, a1);
+                self.edge(a2, None, e);
+                if !matches!(regex, Regex::Plus(_)) {
+                    self.edge(s, None, e);
+                }
+                if !matches!(regex, Regex::Optional(_)) {
+                    self.edge(a2, None, a1);
+                }
+                (s, e)
+            }
+        }
+    }
+
+    /// NFA accepting `regex`, its accepting state is named `name`
+    pub fn from_regex(regex: &Regex, name: &str) -> Nfa {
+        let mut nfa = Nfa {
+            states: 0,
+            start: 0,
+            accepting: BTreeMap::new(),
+            edges: vec![],
+        };
+        let (start, end) = nfa.fragment(regex);
+        nfa.start = start;
+        nfa.accepting.insert(end, name.to_owned());
+        nfa
+    }
+
+    /// A fresh start with ε-transitions into each of `parts`, numbered in order
+    pub fn union(parts: Vec<Nfa>) -> Nfa {
+        let mut nfa = Nfa {
+            states: 1,
+            start: 0,
+            accepting: BTreeMap::new(),
+            edges: vec![],
+        };
+        for part in parts {
+            let offset = nfa.states;
+            nfa.edge(0, None, part.start + offset);
+            for e in part.edges {
+                nfa.edge(e.from + offset, e.label, e.to + offset);
+            }
+            for (state, name) in part.accepting {
+                nfa.accepting.insert(state + offset, name);
+            }
+            nfa.states += part.states;
+        }
+        nfa
+    }
+
+    /// States reachable from `states` by ε-transitions alone, `states` included
+    pub fn closure(&self, states: &BTreeSet<usize>) -> BTreeSet<usize> {
+        let mut closure = states.clone();
+        let mut queue: Vec<usize> = states.iter().cloned().collect();
+        while let Some(state) = queue.pop() {
+            for e in &self.edges {
+                if e.from == state && e.label.is_none() && closure.insert(e.to) {
+                    queue.push(e.to);
+                }
+            }
+        }
+        closure
+    }
+
+    /// States reached from `states` on `c`, before taking the ε-closure
+    pub fn step(&self, states: &BTreeSet<usize>, c: char) -> BTreeSet<usize> {
+        self.edges
+            .iter()
+            .filter(|e| states.contains(&e.from))
+            .filter(|e| e.label.as_ref().is_some_and(|l| l.contains(c)))
+            .map(|e| e.to)
+            .collect()
+    }
+
+    pub fn diagram(&self) -> Diagram {
+        Diagram {
+            names: (0..self.states).map(|s| s.to_string()).collect(),
+            start: self.start,
+            accepting: self.accepting.keys().cloned().collect(),
+            edges: self
+                .edges
+                .iter()
+                .map(|e| {
+                    let label = e
+                        .label
+                        .as_ref()
+                        .map_or(EPSILON.to_owned(), |l| l.text.clone());
+                    (e.from, e.to, label)
+                })
+                .collect(),
+            highlight: None,
+        }
+    }
+
+    pub fn to_table(&self) -> String {
+        let mut rows = vec![vec!["from".to_owned(), "on".to_owned(), "to".to_owned()]];
+        for e in &self.edges {
+            rows.push(vec![
+                e.from.to_string(),
+                e.label
+                    .as_ref()
+                    .map_or(EPSILON.to_owned(), |l| l.text.clone()),
+                e.to.to_string(),
+            ]);
+        }
+        let accepting: Vec<String> = self
+            .accepting
+            .iter()
+            .map(|(state, name)| format!("{} ({})", state, name))
+            .collect();
+        format!(
+            "\nstart {}, accepting {}\n{}",
+            self.start,
+            accepting.join(", "),
+            format_table(&rows)
+        )
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct NfaReport {
+    pub table: String,
+    pub dot: String,
+    pub svg: String,
+    pub error: String,
+}
+
+/// Builds the Thompson NFA of `regex`
+pub fn regex_to_nfa(regex: &str) -> NfaReport {
+    match parse_regex(regex) {
+        Ok(parsed) => {
+            let nfa = Nfa::from_regex(&parsed, regex);
+            let diagram = nfa.diagram();
+            NfaReport {
+                table: nfa.to_table(),
+                dot: diagram.to_dot(),
+                svg: diagram.to_svg(),
+                error: String::new(),
+            }
+        }
+        Err(e) => NfaReport {
+            table: String::new(),
+            dot: String::new(),
+            svg: String::new(),
+            error: e.to_string(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(regex: &str) -> String {
+        parse_regex(regex).unwrap_err().to_string()
+    }
+
+    fn accepts(nfa: &Nfa, word: &str) -> bool {
+        let mut states = nfa.closure(&BTreeSet::from([nfa.start]));
+        for c in word.chars() {
+            states = nfa.closure(&nfa.step(&states, c));
+        }
+        states.iter().any(|s| nfa.accepting.contains_key(s))
+    }
+
+    #[test]
+    fn reports_where_the_expression_is_wrong() {
+        assert_eq!(error("(ab"), "Regex error at 3: expected ')'");
+        assert_eq!(error("ab)"), "Regex error at 2: unmatched ')'");
+        assert_eq!(error("a|*"), "Regex error at 2: nothing to repeat with '*'");
+        assert_eq!(error("[ab"), "Regex error at 0: unclosed '['");
+        assert_eq!(error("a\\"), "Regex error at 2: '\\' at the end");
+    }
+
+    #[test]
+    fn thompson_fragments_have_one_entry_and_exit() {
+        let nfa = Nfa::from_regex(&parse_regex("a|b").unwrap(), "ab");
+        assert_eq!(nfa.states, 6);
+        assert_eq!(nfa.edges.iter().filter(|e| e.label.is_none()).count(), 4);
+        assert_eq!(nfa.accepting, BTreeMap::from([(5, "ab".to_owned())]));
+    }
+
+    #[test]
+    fn accepts_what_the_expression_matches() {
+        let nfa = Nfa::from_regex(&parse_regex("(a|b)*abb").unwrap(), "r");
+        assert!(accepts(&nfa, "abb"));
+        assert!(accepts(&nfa, "babaabb"));
+        assert!(!accepts(&nfa, "abba"));
+        let nfa = Nfa::from_regex(&parse_regex("[^a-y]+\\d?").unwrap(), "r");
+        assert!(accepts(&nfa, "z"));
+        assert!(accepts(&nfa, "z_7"));
+        assert!(!accepts(&nfa, "za"));
+        assert!(!accepts(&nfa, ""));
+    }
+
+    #[test]
+    fn union_numbers_the_parts_after_a_fresh_start() {
+        let a = Nfa::from_regex(&parse_regex("a").unwrap(), "A");
+        let b = Nfa::from_regex(&parse_regex("b").unwrap(), "B");
+        let nfa = Nfa::union(vec![a, b]);
+        assert_eq!(nfa.states, 5);
+        assert_eq!(
+            nfa.accepting,
+            BTreeMap::from([(2, "A".to_owned()), (4, "B".to_owned())])
+        );
+        assert_eq!(nfa.closure(&BTreeSet::from([0])), BTreeSet::from([0, 1, 3]));
+        assert!(nfa
+            .to_table()
+            .starts_with("\nstart 0, accepting 2 (A), 4 (B)\n"));
+    }
+}
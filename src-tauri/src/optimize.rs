@@ -0,0 +1,460 @@
+use crate::cfg;
+use crate::quads::{Code, Operand, Quad};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Passes are repeated until nothing changes, or this many times
+const MAX_PASSES: usize = 10;
+
+/// Operators without side effects whose result only depends on the operands
+const PURE: [&str; 18] = [
+    "+", "-", "*", "/", "%", "&", "|", "^", "<<", ">>", "<", ">", "<=", ">=", "==", "!=", "&&",
+    "||",
+];
+
+/// Unary operators that can be folded, `*` and `&` touch memory
+const PURE_UNARY: [&str; 4] = ["-", "+", "!", "~"];
+
+pub struct Optimized {
+    pub code: Code,
+    /// What was done, grouped by pass
+    pub log: Vec<String>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Number {
+    Int(i64),
+    Float(f64),
+}
+
+impl Number {
+    fn parse(value: &str) -> Option<Number> {
+        let int = match value.strip_prefix("0x") {
+            Some(hex) => i64::from_str_radix(hex, 16).ok(),
+            None => value.parse::<i64>().ok(),
+        };
+        match int {
+            Some(x) => Some(Number::Int(x)),
+            None => value.parse::<f64>().ok().map(Number::Float),
+        }
+    }
+
+    fn as_float(self) -> f64 {
+        match self {
+            Number::Int(x) => x as f64,
+            Number::Float(x) => x,
+        }
+    }
+}
+
+impl std::fmt::Display for Number {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Number::Int(x) => write!(f, "{}", x),
+            Number::Float(x) if x.fract() == 0.0 && x.abs() < 1e15 => write!(f, "{:.1}", x),
+            Number::Float(x) => write!(f, "{}", x),
+        }
+    }
+}
+
+fn is_pure(q: &Quad) -> bool {
+    if q.arg2 == Operand::None {
+        PURE_UNARY.contains(&q.op.as_str()) && q.arg1 != Operand::None
+    } else {
+        PURE.contains(&q.op.as_str())
+    }
+}
+
+fn constant(operand: &Operand) -> Option<Number> {
+    match operand {
+        Operand::Const(_, value) => Number::parse(value),
+        _ => None,
+    }
+}
+
+/// Evaluates a pure quadruple with constant operands, `None` on division by zero
+/// or operators that don't apply to floats
+fn evaluate(op: &str, a: Number, b: Option<Number>) -> Option<Number> {
+    let bool = |x: bool| Number::Int(x as i64);
+    let Some(b) = b else {
+        return match (op, a) {
+            ("-", Number::Int(x)) => Some(Number::Int(x.wrapping_neg())),
+            ("-", Number::Float(x)) => Some(Number::Float(-x)),
+            ("+", a) => Some(a),
+            ("!", a) => Some(bool(a.as_float() == 0.0)),
+            ("~", Number::Int(x)) => Some(Number::Int(!x)),
+            _ => None,
+        };
+    };
+    if let (Number::Int(a), Number::Int(b)) = (a, b) {
+        return Some(match op {
+            "+" => Number::Int(a.wrapping_add(b)),
+            "-" => Number::Int(a.wrapping_sub(b)),
+            "*" => Number::Int(a.wrapping_mul(b)),
+            "/" | "%" if b == 0 => return None,
+            "/" => Number::Int(a.wrapping_div(b)),
+            "%" => Number::Int(a.wrapping_rem(b)),
+            "&" => Number::Int(a & b),
+            "|" => Number::Int(a | b),
+            "^" => Number::Int(a ^ b),
+            "<<" => Number::Int(a.wrapping_shl(b as u32)),
+            ">>" => Number::Int(a.wrapping_shr(b as u32)),
+            "<" => bool(a < b),
+            ">" => bool(a > b),
+            "<=" => bool(a <= b),
+            ">=" => bool(a >= b),
+            "==" => bool(a == b),
+            "!=" => bool(a != b),
+            "&&" => bool(a != 0 && b != 0),
+            "||" => bool(a != 0 || b != 0),
+            _ => return None,
+        });
+    }
+    let (a, b) = (a.as_float(), b.as_float());
+    Some(match op {
+        "+" => Number::Float(a + b),
+        "-" => Number::Float(a - b),
+        "*" => Number::Float(a * b),
+        "/" if b == 0.0 => return None,
+        "/" => Number::Float(a / b),
+        "<" => bool(a < b),
+        ">" => bool(a > b),
+        "<=" => bool(a <= b),
+        ">=" => bool(a >= b),
+        "==" => bool(a == b),
+        "!=" => bool(a != b),
+        "&&" => bool(a != 0.0 && b != 0.0),
+        "||" => bool(a != 0.0 || b != 0.0),
+        _ => return None,
+    })
+}
+
+/// Variable or temporary written by the quadruple
+fn defines(q: &Quad) -> Option<&Operand> {
+    match q.op.as_str() {
+        "[]=" | "store" | "param" | "jz" | "jmp" | "ret" | "nop" => None,
+        _ if q.result == Operand::None => None,
+        _ => Some(&q.result),
+    }
+}
+
+/// Operands read by the quadruple
+fn uses(q: &Quad) -> Vec<&Operand> {
+    let mut uses = vec![&q.arg1, &q.arg2];
+    if q.op == "[]=" || q.op == "store" {
+        uses.push(&q.result);
+    }
+    uses
+}
+
+fn nop() -> Quad {
+    Quad::new("nop", Operand::None, Operand::None, Operand::None)
+}
+
+fn copy(value: Operand, result: Operand) -> Quad {
+    Quad::new("=", value, Operand::None, result)
+}
+
+/// Constant folding and propagation, and common subexpression elimination,
+/// each within a basic block
+fn fold_block(quads: &mut [Quad], start: usize, end: usize, log: &mut Vec<String>) {
+    let mut known: BTreeMap<String, Operand> = BTreeMap::new();
+    let mut available: Vec<((String, Operand, Operand), Operand)> = vec![];
+    for (idx, slot) in quads.iter_mut().enumerate().take(end).skip(start) {
+        let mut q = slot.clone();
+        for arg in [&mut q.arg1, &mut q.arg2] {
+            if let Some(value) = known.get(&arg.to_string()) {
+                *arg = value.clone();
+            }
+        }
+
+        if is_pure(&q) {
+            let b = if q.arg2 == Operand::None {
+                None
+            } else {
+                constant(&q.arg2)
+            };
+            let operands_known = q.arg2 == Operand::None || b.is_some();
+            if let (Some(a), true) = (constant(&q.arg1), operands_known) {
+                if let Some(value) = evaluate(&q.op, a, b) {
+                    log.push(format!("{}: {} folded to {}", idx, q.to_source(), value));
+                    q = copy(Operand::Const(None, value.to_string()), q.result.clone());
+                }
+            }
+        }
+        if q.op == "jz" {
+            if let Some(cond) = constant(&q.arg1) {
+                if cond.as_float() == 0.0 {
+                    log.push(format!("{}: condition is always false", idx));
+                    q = Quad::new("jmp", Operand::None, Operand::None, q.result.clone());
+                } else {
+                    log.push(format!("{}: condition is always true", idx));
+                    q = nop();
+                }
+            }
+        }
+        if is_pure(&q) {
+            let key = (q.op.clone(), q.arg1.clone(), q.arg2.clone());
+            if let Some((_, previous)) = available.iter().find(|(k, _)| *k == key) {
+                if *previous != q.result {
+                    log.push(format!(
+                        "{}: {} reuses {}",
+                        idx,
+                        q.to_source(),
+                        previous.to_source()
+                    ));
+                    q = copy(previous.clone(), q.result.clone());
+                }
+            }
+        }
+
+        if let Some(defined) = defines(&q).cloned() {
+            known.remove(&defined.to_string());
+            available
+                .retain(|((_, a, b), result)| *a != defined && *b != defined && *result != defined);
+            if q.op == "=" && constant(&q.arg1).is_some() {
+                known.insert(defined.to_string(), q.arg1.clone());
+            }
+            if is_pure(&q) && q.arg1 != defined && q.arg2 != defined {
+                available.push(((q.op.clone(), q.arg1.clone(), q.arg2.clone()), defined));
+            }
+        }
+        // Calls and stores through pointers may change any variable
+        if matches!(q.op.as_str(), "call" | "store") {
+            known.retain(|_, _| false);
+            available.retain(|((_, a, b), _)| {
+                !matches!(a, Operand::Ident(..)) && !matches!(b, Operand::Ident(..))
+            });
+        }
+        *slot = q;
+    }
+}
+
+/// First quadruple at or after `idx` that survives compaction
+fn resolve(quads: &[Quad], mut idx: usize) -> usize {
+    while idx < quads.len() && quads[idx].op == "nop" {
+        idx += 1;
+    }
+    idx
+}
+
+/// Unreachable blocks, jumps to the next quadruple and unused temporaries
+fn eliminate(code: &mut Code, log: &mut Vec<String>) {
+    for graph in cfg::graphs(code) {
+        for b in graph.unreachable() {
+            let block = &graph.blocks[b];
+            for idx in block.start..block.end {
+                if code.quads[idx].op != "nop" {
+                    log.push(format!(
+                        "{}: unreachable {}",
+                        idx,
+                        code.quads[idx].to_source()
+                    ));
+                    code.quads[idx] = nop();
+                }
+            }
+        }
+    }
+    for idx in 0..code.quads.len() {
+        if let ("jmp", Operand::Address(target)) =
+            (code.quads[idx].op.as_str(), &code.quads[idx].result)
+        {
+            if resolve(&code.quads, *target) == resolve(&code.quads, idx + 1) {
+                log.push(format!("{}: jump to the next quadruple", idx));
+                code.quads[idx] = nop();
+            }
+        }
+    }
+    let used: BTreeSet<String> = code
+        .quads
+        .iter()
+        .flat_map(|q| uses(q).into_iter().map(|x| x.to_string()))
+        .collect();
+    for idx in 0..code.quads.len() {
+        let q = &code.quads[idx];
+        let Some(Operand::Temp(_)) = defines(q) else {
+            continue;
+        };
+        if used.contains(&q.result.to_string()) {
+            continue;
+        }
+        log.push(format!("{}: {} is never used", idx, q.result));
+        if q.op == "call" {
+            code.quads[idx].result = Operand::None;
+        } else if q.op == "[]" || q.op == "=" || is_pure(q) {
+            code.quads[idx] = nop();
+        }
+    }
+}
+
+/// Drops `nop`s and renumbers jump targets and function starts
+fn compact(code: &mut Code) {
+    let mut new_index = vec![0; code.quads.len() + 1];
+    let mut count = 0;
+    for (idx, q) in code.quads.iter().enumerate() {
+        new_index[idx] = count;
+        if q.op != "nop" {
+            count += 1;
+        }
+    }
+    new_index[code.quads.len()] = count;
+    let remap = |quads: &[Quad], idx: usize| new_index[resolve(quads, idx).min(quads.len())];
+    let quads = code.quads.clone();
+    for q in code.quads.iter_mut() {
+        if let Operand::Address(target) = q.result {
+            q.result = Operand::Address(remap(&quads, target));
+        }
+    }
+    for f in code.functions.iter_mut() {
+        f.start = remap(&quads, f.start);
+    }
+    code.quads.retain(|q| q.op != "nop");
+}
+
+/// Folds constants, eliminates common subexpressions and dead code until
+/// nothing changes. Addresses in the log refer to the code of the pass
+/// that made the change
+pub fn optimize(code: &Code) -> Optimized {
+    let mut code = code.clone();
+    let mut log = vec![];
+    for pass in 1..=MAX_PASSES {
+        let before = code.quads.clone();
+        let mut changes = vec![];
+        for graph in cfg::graphs(&code) {
+            for block in &graph.blocks {
+                fold_block(&mut code.quads, block.start, block.end, &mut changes);
+            }
+        }
+        eliminate(&mut code, &mut changes);
+        compact(&mut code);
+        if code.quads == before {
+            break;
+        }
+        log.push(format!("pass {}", pass));
+        log.extend(changes.into_iter().map(|x| format!("  {}", x)));
+    }
+    Optimized { code, log }
+}
+
+/// The code with jump targets replaced by labels, so unrelated renumbering
+/// doesn't show up in the diff
+pub fn listing(code: &Code) -> Vec<String> {
+    let targets: BTreeSet<usize> = code
+        .quads
+        .iter()
+        .filter_map(|q| match (q.op.as_str(), &q.result) {
+            ("jz" | "jmp", Operand::Address(target)) => Some(*target),
+            _ => None,
+        })
+        .collect();
+    let label = |target: usize| match targets.iter().position(|x| *x == target) {
+        Some(n) => format!("L{}", n),
+        None => format!("@{}", target),
+    };
+    let mut lines = vec![];
+    for (idx, q) in code.quads.iter().enumerate() {
+        if let Some(f) = code.functions.iter().find(|f| f.start == idx) {
+            lines.push(format!("{}:", f.name));
+        }
+        if targets.contains(&idx) {
+            lines.push(format!("{}:", label(idx)));
+        }
+        let line = match (q.op.as_str(), &q.result) {
+            ("jz", Operand::Address(target)) => {
+                format!("if not {} goto {}", q.arg1.to_source(), label(*target))
+            }
+            ("jmp", Operand::Address(target)) => format!("goto {}", label(*target)),
+            _ => q.to_source(),
+        };
+        lines.push(format!("    {}", line));
+    }
+    if targets.contains(&code.quads.len()) {
+        lines.push(format!("{}:", label(code.quads.len())));
+    }
+    lines
+}
+
+/// Line diff of two listings, `-` for removed and `+` for added lines
+pub fn diff(before: &[String], after: &[String]) -> String {
+    let (n, m) = (before.len(), after.len());
+    // Longest common subsequence of the suffixes
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if before[i] == after[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut text = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && before[i] == after[j] {
+            text.push_str(&format!("\n  {}", before[i]));
+            i += 1;
+            j += 1;
+        } else if j == m || (i < n && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            text.push_str(&format!("\n- {}", before[i]));
+            i += 1;
+        } else {
+            text.push_str(&format!("\n+ {}", after[j]));
+            j += 1;
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::descriptors::Symbols;
+    use crate::keywords::count_tokens;
+    use crate::quads::translate;
+    use crate::syntax::parse;
+
+    fn lower(program: &str) -> Code {
+        let tokens = count_tokens(program.to_owned()).unwrap();
+        let symbols = Symbols::new(&tokens);
+        translate(&parse(tokens).unwrap(), &symbols).unwrap()
+    }
+
+    #[test]
+    fn folds_and_propagates_constants() {
+        let optimized = optimize(&lower("int main() { int x = 2 * 3; return x + 1; }"));
+        assert_eq!(
+            listing(&optimized.code),
+            ["main:", "    x = 6", "    return 7"]
+        );
+        assert_eq!(optimized.log[0], "pass 1");
+        assert_eq!(optimized.log[1], "  0: t1 = 2 * 3 folded to 6");
+    }
+
+    #[test]
+    fn reuses_common_subexpressions() {
+        let program =
+            "int main() { int a; int b; int c; int d; c = a * b; d = a * b; return c + d; }";
+        let optimized = optimize(&lower(program));
+        assert!(optimized
+            .log
+            .contains(&"  2: t2 = a * b reuses t1".to_owned()));
+    }
+
+    #[test]
+    fn drops_branches_that_are_never_taken() {
+        let optimized = optimize(&lower(
+            "int main() { int x; if (0) x = 1; x = 2; return x; }",
+        ));
+        assert_eq!(
+            listing(&optimized.code),
+            ["main:", "    x = 2", "    return 2"]
+        );
+    }
+
+    #[test]
+    fn diff_marks_removed_and_added_lines() {
+        let before = ["a".to_owned(), "b".to_owned(), "c".to_owned()];
+        let after = ["a".to_owned(), "d".to_owned(), "c".to_owned()];
+        assert_eq!(diff(&before, &after), "\n  a\n- b\n+ d\n  c");
+    }
+}
@@ -0,0 +1,177 @@
+use crate::analysis::{self, Analysis};
+use crate::keywords::TokenType;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const EXTENSIONS: [&str; 3] = ["c", "cpp", "h"];
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProjectFile {
+    /// Path relative to the project root
+    pub path: String,
+    pub analysis: Analysis,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Usage {
+    /// Index into `Project::files`
+    pub file: usize,
+    /// Line in the comment-stripped listing
+    pub line: usize,
+}
+
+/// Row of a project-wide table
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Entry {
+    pub name: String,
+    pub usages: Vec<Usage>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Project {
+    pub root: String,
+    pub files: Vec<ProjectFile>,
+    /// Identifiers of every file, numbered in order of first appearance
+    pub identifiers: Vec<Entry>,
+    pub consts: Vec<Entry>,
+}
+
+fn collect_sources(dir: &Path, sources: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if hidden {
+            continue;
+        }
+        // Doesn't follow symlinks, one pointing back up the tree would never end
+        if entry.file_type()?.is_dir() {
+            collect_sources(&path, sources)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| EXTENSIONS.contains(&ext.to_string_lossy().as_ref()))
+        {
+            sources.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn add_usage(entries: &mut Vec<Entry>, name: &str, usage: Usage) {
+    match entries.iter_mut().find(|x| x.name == name) {
+        Some(entry) => entry.usages.push(usage),
+        None => entries.push(Entry {
+            name: name.to_owned(),
+            usages: vec![usage],
+        }),
+    }
+}
+
+/// Analyses every `.c`, `.cpp` and `.h` file under `root`. A file that can't
+/// be read is listed with the error instead of its analysis
+pub fn open_project(root: &Path) -> Result<Project, String> {
+    let mut sources = vec![];
+    collect_sources(root, &mut sources).map_err(|e| format!("{}: {}", root.display(), e))?;
+    sources.sort();
+
+    let mut project = Project {
+        root: root.display().to_string(),
+        files: vec![],
+        identifiers: vec![],
+        consts: vec![],
+    };
+    for (file, path) in sources.iter().enumerate() {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) => {
+                project.files.push(ProjectFile {
+                    path: relative.display().to_string(),
+                    analysis: Analysis {
+                        error: format!("{}: {}", path.display(), e),
+                        ..Analysis::default()
+                    },
+                });
+                continue;
+            }
+        };
+        let (analysis, tokens) = analysis::analyze_with_tokens(text);
+        for token in tokens {
+            let usage = Usage {
+                file,
+                line: token.location.line,
+            };
+            match token.token_type {
+                TokenType::Identifier => {
+                    add_usage(&mut project.identifiers, &token.token, usage)
+                }
+                TokenType::ConstValue => add_usage(&mut project.consts, &token.token, usage),
+                _ => {}
+            }
+        }
+        project.files.push(ProjectFile {
+            path: relative.display().to_string(),
+            analysis,
+        });
+    }
+    Ok(project)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merges_tables_of_every_source() {
+        let root = std::env::temp_dir().join(format!("project-test-{}", std::process::id()));
+        fs::create_dir_all(root.join("lib")).unwrap();
+        fs::create_dir_all(root.join(".git")).unwrap();
+        fs::write(root.join("main.c"), "int count = 1;\nshared = count;\n").unwrap();
+        fs::write(root.join("lib/util.h"), "int shared = 2;\n").unwrap();
+        fs::write(root.join("notes.txt"), "int ignored = 3;\n").unwrap();
+        fs::write(root.join(".git/hidden.c"), "int hidden = 4;\n").unwrap();
+
+        let project = open_project(&root).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        let paths: Vec<&str> = project.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["lib/util.h", "main.c"]);
+        let names: Vec<&str> = project
+            .identifiers
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect();
+        assert_eq!(names, ["shared", "count"]);
+        let usages: Vec<(usize, usize)> = project.identifiers[1]
+            .usages
+            .iter()
+            .map(|u| (u.file, u.line))
+            .collect();
+        assert_eq!(usages, [(1, 0), (1, 1)]);
+        let consts: Vec<&str> = project.consts.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(consts, ["2", "1"]);
+    }
+
+    #[test]
+    fn keeps_going_past_unreadable_files_and_symlinked_directories() {
+        let root = std::env::temp_dir().join(format!("project-links-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        fs::write(root.join("bad.c"), [0xff, 0xfe]).unwrap();
+        fs::write(root.join("good.c"), "int x = 1;\n").unwrap();
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(&root, root.join("loop")).unwrap();
+
+        let project = open_project(&root).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        let paths: Vec<&str> = project.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["bad.c", "good.c"]);
+        assert!(project.files[0].analysis.table.is_none());
+        assert!(project.files[0].analysis.error.contains("bad.c: "));
+        assert_eq!(project.files[1].analysis.error, "");
+    }
+}
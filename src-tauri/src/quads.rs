@@ -0,0 +1,587 @@
+use crate::descriptors::Symbols;
+use crate::grammar::format_table;
+use crate::lowering::{Error, Jumps, Loop};
+use crate::syntax::{Declaration, Expr, Item, Program, Stmt};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Operand {
+    None,
+    /// Row in the identifier table
+    Ident(usize, String),
+    /// Row in the constant table, `None` for constants split off by the parser (`x -1`)
+    Const(Option<usize>, String),
+    Str(Option<usize>, String),
+    Temp(usize),
+    /// Jump target, index of a quadruple
+    Address(usize),
+}
+
+impl std::fmt::Display for Operand {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Operand::None => Ok(()),
+            Operand::Ident(idx, _) => write!(f, "id{}", idx),
+            Operand::Const(Some(idx), _) => write!(f, "const{}", idx),
+            Operand::Const(None, value) => f.write_str(value),
+            Operand::Str(Some(idx), _) => write!(f, "str{}", idx),
+            Operand::Str(None, value) => f.write_str(value),
+            Operand::Temp(n) => write!(f, "t{}", n),
+            Operand::Address(target) => write!(f, "@{}", target),
+        }
+    }
+}
+
+impl Operand {
+    /// Source text of the operand, for the readable column
+    pub fn to_source(&self) -> String {
+        match self {
+            Operand::Ident(_, name) => name.clone(),
+            Operand::Const(_, value) | Operand::Str(_, value) => value.clone(),
+            _ => self.to_string(),
+        }
+    }
+}
+
+/// `(op, arg1, arg2, result)`. Besides the C operators `op` is one of
+/// `=`, `[]` (load element), `[]=` (store element: index, value, array),
+/// `store` (through a pointer), `param`, `call` (name, argc, result),
+/// `jz` (jump to `result` if `arg1` is false), `jmp` and `ret`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Quad {
+    pub op: String,
+    pub arg1: Operand,
+    pub arg2: Operand,
+    pub result: Operand,
+}
+
+impl Quad {
+    pub fn new(op: &str, arg1: Operand, arg2: Operand, result: Operand) -> Quad {
+        Quad {
+            op: op.to_owned(),
+            arg1,
+            arg2,
+            result,
+        }
+    }
+
+    /// The quadruple as a C-like statement
+    pub fn to_source(&self) -> String {
+        let (a, b, r) = (
+            self.arg1.to_source(),
+            self.arg2.to_source(),
+            self.result.to_source(),
+        );
+        match self.op.as_str() {
+            "=" => format!("{} = {}", r, a),
+            "[]" => format!("{} = {}[{}]", r, a, b),
+            "[]=" => format!("{}[{}] = {}", r, a, b),
+            "store" => format!("*{} = {}", r, a),
+            "param" => format!("param {}", a),
+            "call" if self.result == Operand::None => format!("call {}, {}", a, b),
+            "call" => format!("{} = call {}, {}", r, a, b),
+            "jz" => format!("if not {} goto {}", a, r),
+            "jmp" => format!("goto {}", r),
+            "ret" => format!("return {}", a).trim_end().to_owned(),
+            op if self.arg2 == Operand::None => format!("{} = {}{}", r, op, a),
+            op => format!("{} = {} {} {}", r, a, op, b),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Function {
+    pub name: String,
+    pub start: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Code {
+    pub quads: Vec<Quad>,
+    pub functions: Vec<Function>,
+}
+
+struct Translator<'a> {
+    symbols: &'a Symbols,
+    quads: Vec<Quad>,
+    temps: usize,
+    jumps: Jumps,
+}
+
+impl Translator<'_> {
+    fn emit(&mut self, op: &str, arg1: Operand, arg2: Operand, result: Operand) {
+        self.quads.push(Quad::new(op, arg1, arg2, result));
+    }
+
+    fn temp(&mut self) -> Operand {
+        self.temps += 1;
+        Operand::Temp(self.temps)
+    }
+
+    fn here(&self) -> usize {
+        self.quads.len()
+    }
+
+    /// Emits `jz cond` or `jmp` with an unknown target and returns its position
+    fn jump(&mut self, cond: Option<Operand>) -> usize {
+        let at = self.here();
+        match cond {
+            Some(cond) => self.emit("jz", cond, Operand::None, Operand::Address(usize::MAX)),
+            None => self.emit(
+                "jmp",
+                Operand::None,
+                Operand::None,
+                Operand::Address(usize::MAX),
+            ),
+        }
+        at
+    }
+
+    fn jump_to(&mut self, target: usize) {
+        self.emit(
+            "jmp",
+            Operand::None,
+            Operand::None,
+            Operand::Address(target),
+        );
+    }
+
+    fn patch(&mut self, at: usize, target: usize) {
+        self.quads[at].result = Operand::Address(target);
+    }
+
+    fn ident(&self, name: &str) -> Result<Operand, Error> {
+        match self.symbols.identifier(name) {
+            Some(idx) => Ok(Operand::Ident(idx, name.to_owned())),
+            None => Err(Error::UnknownIdentifier(name.to_owned())),
+        }
+    }
+
+    fn one(&self) -> Operand {
+        Operand::Const(self.symbols.constant("1"), "1".to_owned())
+    }
+
+    /// Stores `value` into the place denoted by `target`
+    fn assign(&mut self, target: &Expr, value: Operand) -> Result<(), Error> {
+        match target {
+            Expr::Ident(name, _) => {
+                let target = self.ident(name)?;
+                self.emit("=", value, Operand::None, target);
+            }
+            Expr::Index { array, index } => {
+                let array = self.expr(array)?;
+                let index = self.expr(index)?;
+                self.emit("[]=", index, value, array);
+            }
+            Expr::Unary { op, operand } if op == "*" => {
+                let pointer = self.expr(operand)?;
+                self.emit("store", value, Operand::None, pointer);
+            }
+            _ => return Err(Error::NotAssignable),
+        }
+        Ok(())
+    }
+
+    /// Lowers `e` and returns the operand holding its value
+    fn expr(&mut self, e: &Expr) -> Result<Operand, Error> {
+        Ok(match e {
+            Expr::Ident(name, _) => self.ident(name)?,
+            Expr::Const(value, _) => Operand::Const(self.symbols.constant(value), value.clone()),
+            Expr::Str(value, _) => Operand::Str(self.symbols.string(value), value.clone()),
+            // ++x is x = x + 1 and yields x, x++ saves the old value first
+            Expr::Unary { op, operand } | Expr::Postfix { op, operand }
+                if op == "++" || op == "--" =>
+            {
+                let old = self.expr(operand)?;
+                let saved = match e {
+                    Expr::Postfix { .. } => {
+                        let t = self.temp();
+                        self.emit("=", old.clone(), Operand::None, t.clone());
+                        Some(t)
+                    }
+                    _ => None,
+                };
+                let t = self.temp();
+                let one = self.one();
+                self.emit(&op[..1], old, one, t.clone());
+                self.assign(operand, t.clone())?;
+                saved.unwrap_or(t)
+            }
+            Expr::Unary { op, operand } | Expr::Postfix { op, operand } => {
+                let a = self.expr(operand)?;
+                let t = self.temp();
+                self.emit(op, a, Operand::None, t.clone());
+                t
+            }
+            Expr::Binary { op, left, right } => {
+                let a = self.expr(left)?;
+                let b = self.expr(right)?;
+                let t = self.temp();
+                self.emit(op, a, b, t.clone());
+                t
+            }
+            Expr::Assign { op, target, value } => {
+                let mut v = self.expr(value)?;
+                if op != "=" {
+                    let old = self.expr(target)?;
+                    let t = self.temp();
+                    self.emit(&op[..op.len() - 1], old, v, t.clone());
+                    v = t;
+                }
+                self.assign(target, v.clone())?;
+                v
+            }
+            Expr::Call { callee, args, .. } => {
+                let args = args
+                    .iter()
+                    .map(|a| self.expr(a))
+                    .collect::<Result<Vec<Operand>, Error>>()?;
+                let argc = args.len();
+                for a in args {
+                    self.emit("param", a, Operand::None, Operand::None);
+                }
+                let name = self.ident(callee)?;
+                let t = self.temp();
+                self.emit(
+                    "call",
+                    name,
+                    Operand::Const(None, argc.to_string()),
+                    t.clone(),
+                );
+                t
+            }
+            Expr::Index { array, index } => {
+                let a = self.expr(array)?;
+                let i = self.expr(index)?;
+                let t = self.temp();
+                self.emit("[]", a, i, t.clone());
+                t
+            }
+        })
+    }
+
+    fn declaration(&mut self, d: &Declaration) -> Result<(), Error> {
+        for x in &d.declarators {
+            if let Some(init) = &x.init {
+                let value = self.expr(init)?;
+                let target = self.ident(&x.name)?;
+                self.emit("=", value, Operand::None, target);
+            }
+        }
+        Ok(())
+    }
+
+    fn loop_body(&mut self, body: &Stmt, next: Option<usize>) -> Result<Loop, Error> {
+        self.jumps.enter();
+        self.stmt(body)?;
+        let l = self.jumps.leave();
+        let next = next.unwrap_or(self.here());
+        for at in &l.continues {
+            self.patch(*at, next);
+        }
+        Ok(l)
+    }
+
+    fn stmt(&mut self, s: &Stmt) -> Result<(), Error> {
+        match s {
+            Stmt::Block(stmts) => {
+                for s in stmts {
+                    self.stmt(s)?;
+                }
+            }
+            Stmt::Declaration(d) => self.declaration(d)?,
+            Stmt::Expr(e) => {
+                self.expr(e)?;
+            }
+            Stmt::If {
+                cond,
+                then,
+                otherwise,
+            } => {
+                let c = self.expr(cond)?;
+                let to_else = self.jump(Some(c));
+                self.stmt(then)?;
+                match otherwise {
+                    Some(otherwise) => {
+                        let to_end = self.jump(None);
+                        self.patch(to_else, self.here());
+                        self.stmt(otherwise)?;
+                        self.patch(to_end, self.here());
+                    }
+                    None => self.patch(to_else, self.here()),
+                }
+            }
+            Stmt::While { cond, body } => {
+                let start = self.here();
+                let c = self.expr(cond)?;
+                let to_end = self.jump(Some(c));
+                let l = self.loop_body(body, Some(start))?;
+                self.jump_to(start);
+                self.patch(to_end, self.here());
+                for at in l.breaks {
+                    self.patch(at, self.here());
+                }
+            }
+            Stmt::DoWhile { body, cond } => {
+                let start = self.here();
+                let l = self.loop_body(body, None)?;
+                let c = self.expr(cond)?;
+                let to_end = self.jump(Some(c));
+                self.jump_to(start);
+                self.patch(to_end, self.here());
+                for at in l.breaks {
+                    self.patch(at, self.here());
+                }
+            }
+            Stmt::For {
+                init,
+                cond,
+                step,
+                body,
+            } => {
+                if let Some(init) = init {
+                    self.stmt(init)?;
+                }
+                let start = self.here();
+                let to_end = match cond {
+                    Some(cond) => {
+                        let c = self.expr(cond)?;
+                        Some(self.jump(Some(c)))
+                    }
+                    None => None,
+                };
+                let l = self.loop_body(body, None)?;
+                if let Some(step) = step {
+                    self.expr(step)?;
+                }
+                self.jump_to(start);
+                if let Some(to_end) = to_end {
+                    self.patch(to_end, self.here());
+                }
+                for at in l.breaks {
+                    self.patch(at, self.here());
+                }
+            }
+            Stmt::Switch { value, cases } => {
+                let v = self.expr(value)?;
+                let mut to_bodies = vec![];
+                for case in cases {
+                    match &case.value {
+                        Some(c) => {
+                            let c = self.expr(c)?;
+                            let t = self.temp();
+                            self.emit("!=", v.clone(), c, t.clone());
+                            to_bodies.push(Some(self.jump(Some(t))));
+                        }
+                        None => to_bodies.push(None),
+                    }
+                }
+                let to_default = self.jump(None);
+                let mut default = None;
+                self.jumps.enter();
+                for (case, to_body) in cases.iter().zip(to_bodies) {
+                    match to_body {
+                        Some(at) => self.patch(at, self.here()),
+                        None => default = Some(self.here()),
+                    }
+                    for s in &case.body {
+                        self.stmt(s)?;
+                    }
+                }
+                let breaks = self.jumps.leave_switch()?;
+                let end = self.here();
+                self.patch(to_default, default.unwrap_or(end));
+                for at in breaks {
+                    self.patch(at, end);
+                }
+            }
+            Stmt::Return(value) => {
+                let v = match value {
+                    Some(value) => self.expr(value)?,
+                    None => Operand::None,
+                };
+                self.emit("ret", v, Operand::None, Operand::None);
+            }
+            Stmt::Break => {
+                let at = self.jump(None);
+                self.jumps.break_at(at)?;
+            }
+            Stmt::Continue => {
+                let at = self.jump(None);
+                self.jumps.continue_at(at)?;
+            }
+            Stmt::Goto(label) => {
+                let at = self.jump(None);
+                self.jumps.goto(at, label);
+            }
+            Stmt::Label(label) => {
+                let at = self.here();
+                self.jumps.label(label, at);
+            }
+            Stmt::Empty => {}
+        }
+        Ok(())
+    }
+}
+
+/// Lowers the syntax tree to quadruples. Global initialisers come first,
+/// then every function body ending in `ret`
+pub fn translate(program: &Program, symbols: &Symbols) -> Result<Code, Error> {
+    let mut t = Translator {
+        symbols,
+        quads: vec![],
+        temps: 0,
+        jumps: Jumps::default(),
+    };
+    for item in &program.items {
+        if let Item::Declaration(d) = item {
+            t.declaration(d)?;
+        }
+    }
+    let mut functions = vec![];
+    for item in &program.items {
+        let Item::Function(f) = item else {
+            continue;
+        };
+        let Some(body) = &f.body else {
+            continue;
+        };
+        let start = t.here();
+        for s in body {
+            t.stmt(s)?;
+        }
+        t.emit("ret", Operand::None, Operand::None, Operand::None);
+        for (at, target) in t.jumps.resolve()? {
+            t.patch(at, target);
+        }
+        functions.push(Function {
+            name: f.name.clone(),
+            start,
+        });
+    }
+    Ok(Code {
+        quads: t.quads,
+        functions,
+    })
+}
+
+impl Code {
+    /// Numbered table with a C-like rendering of every quadruple
+    pub fn to_table(&self) -> String {
+        let mut rows = vec![vec![
+            "№".to_owned(),
+            "op".to_owned(),
+            "arg1".to_owned(),
+            "arg2".to_owned(),
+            "result".to_owned(),
+            String::new(),
+        ]];
+        for (idx, q) in self.quads.iter().enumerate() {
+            if let Some(f) = self.functions.iter().find(|f| f.start == idx) {
+                rows.push(vec![format!("{}:", f.name)]);
+            }
+            rows.push(vec![
+                idx.to_string(),
+                q.op.clone(),
+                q.arg1.to_string(),
+                q.arg2.to_string(),
+                q.result.to_string(),
+                q.to_source(),
+            ]);
+        }
+        format_table(&rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keywords::count_tokens;
+    use crate::syntax::parse;
+
+    fn lower(program: &str) -> Result<Code, Error> {
+        let tokens = count_tokens(program.to_owned()).unwrap();
+        let symbols = Symbols::new(&tokens);
+        translate(&parse(tokens).unwrap(), &symbols)
+    }
+
+    fn source(program: &str) -> Vec<String> {
+        lower(program)
+            .unwrap()
+            .quads
+            .iter()
+            .map(|q| q.to_source())
+            .collect()
+    }
+
+    #[test]
+    fn postfix_increment_saves_the_old_value() {
+        assert_eq!(
+            source("int main() { int x; int y = 1; x = y ++; }"),
+            [
+                "y = 1",
+                "t1 = y",
+                "t2 = y + 1",
+                "y = t2",
+                "x = t1",
+                "return"
+            ]
+        );
+    }
+
+    #[test]
+    fn loops_jump_back_and_break_out() {
+        assert_eq!(
+            source("int main() { int i = 0; while (i < 3) { if (i == 1) break; i = i + 1; } }"),
+            [
+                "i = 0",
+                "t1 = i < 3",
+                "if not t1 goto @9",
+                "t2 = i == 1",
+                "if not t2 goto @6",
+                "goto @9",
+                "t3 = i + 1",
+                "i = t3",
+                "goto @1",
+                "return"
+            ]
+        );
+    }
+
+    #[test]
+    fn goto_jumps_to_its_label() {
+        assert_eq!(
+            source("int main() { goto end; puts(\"skipped\"); end: return 0; }"),
+            [
+                "goto @3",
+                "param skipped",
+                "t1 = call puts, 1",
+                "return 0",
+                "return"
+            ]
+        );
+        assert_eq!(
+            lower("int main() { goto nowhere; }")
+                .err()
+                .unwrap()
+                .to_string(),
+            "Translation error: unknown label nowhere"
+        );
+    }
+
+    #[test]
+    fn operands_render_as_table_rows() {
+        let table = lower("int main() { int a[2]; a[1] = 5; }")
+            .unwrap()
+            .to_table();
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[1], "main:");
+        assert_eq!(
+            lines[2],
+            "0     | []= | const1 | const2 | id1    | a[1] = 5"
+        );
+        assert_eq!(
+            lines[3].trim_end(),
+            "1     | ret |        |        |        | return"
+        );
+    }
+}
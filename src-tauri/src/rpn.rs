@@ -0,0 +1,524 @@
+use crate::descriptors::Symbols;
+use crate::lowering::{Error, Jumps, Loop};
+use crate::syntax::{Declaration, Expr, Item, Program, Stmt};
+use std::collections::BTreeSet;
+
+/// Element of the postfix (ПОЛИЗ) program
+#[derive(Clone, Debug, PartialEq)]
+pub enum Element {
+    /// Row in the identifier table, pushes a reference to the variable
+    Ident(usize, String),
+    /// Row in the constant table, `None` for constants split off by the parser (`x -1`)
+    Const(Option<usize>, String),
+    Str(Option<usize>, String),
+    /// Arithmetic, comparison, logic and `=`; unary operators are prefixed with `u`
+    Op(String),
+    /// Array element reference: array, index
+    Index,
+    /// Function name and number of arguments
+    Call(String, usize),
+    /// Jump target operand
+    Address(usize),
+    /// УПЛ, jump to the address if the condition is false
+    JumpFalse,
+    /// БП, unconditional jump
+    Jump,
+    Return,
+    /// Drops the value of an expression statement
+    Pop,
+}
+
+impl std::fmt::Display for Element {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Element::Ident(idx, _) => write!(f, "id{}", idx),
+            Element::Const(Some(idx), _) => write!(f, "const{}", idx),
+            Element::Const(None, value) => f.write_str(value),
+            Element::Str(Some(idx), _) => write!(f, "str{}", idx),
+            Element::Str(None, value) => f.write_str(value),
+            Element::Op(op) => f.write_str(op),
+            Element::Index => f.write_str("[]"),
+            Element::Call(name, argc) => write!(f, "{}/{}", name, argc),
+            Element::Address(target) => write!(f, "@{}", target),
+            Element::JumpFalse => f.write_str("УПЛ"),
+            Element::Jump => f.write_str("БП"),
+            Element::Return => f.write_str("ret"),
+            Element::Pop => f.write_str("pop"),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Function {
+    pub name: String,
+    /// Identifier rows of the parameters, in order
+    pub params: Vec<usize>,
+    pub start: usize,
+}
+
+#[derive(Clone, Debug)]
+pub struct Rpn {
+    pub code: Vec<Element>,
+    pub functions: Vec<Function>,
+    /// Identifier rows declared outside of functions
+    pub globals: Vec<usize>,
+    /// Identifier rows declared `float` or `double`, what is stored in them becomes a float
+    pub floats: BTreeSet<usize>,
+}
+
+struct Translator<'a> {
+    symbols: &'a Symbols,
+    code: Vec<Element>,
+    jumps: Jumps,
+    floats: BTreeSet<usize>,
+}
+
+/// `float` and `double` with any qualifiers, pointers to them aren't
+fn is_float(type_name: &str) -> bool {
+    let words: Vec<&str> = type_name.split_whitespace().collect();
+    (words.contains(&"float") || words.contains(&"double")) && !type_name.contains('*')
+}
+
+impl Translator<'_> {
+    fn emit(&mut self, element: Element) {
+        self.code.push(element);
+    }
+
+    /// Emits a jump with an unknown target and returns the position to patch
+    fn jump(&mut self, conditional: bool) -> usize {
+        let at = self.code.len();
+        self.emit(Element::Address(usize::MAX));
+        self.emit(if conditional {
+            Element::JumpFalse
+        } else {
+            Element::Jump
+        });
+        at
+    }
+
+    fn jump_to(&mut self, target: usize) {
+        self.emit(Element::Address(target));
+        self.emit(Element::Jump);
+    }
+
+    fn patch(&mut self, at: usize, target: usize) {
+        self.code[at] = Element::Address(target);
+    }
+
+    fn here(&self) -> usize {
+        self.code.len()
+    }
+
+    fn ident(&self, name: &str) -> Result<Element, Error> {
+        match self.symbols.identifier(name) {
+            Some(idx) => Ok(Element::Ident(idx, name.to_owned())),
+            None => Err(Error::UnknownIdentifier(name.to_owned())),
+        }
+    }
+
+    fn expr(&mut self, e: &Expr) -> Result<(), Error> {
+        match e {
+            Expr::Ident(name, _) => {
+                let element = self.ident(name)?;
+                self.emit(element);
+            }
+            Expr::Const(value, _) => {
+                let element = Element::Const(self.symbols.constant(value), value.clone());
+                self.emit(element);
+            }
+            Expr::Str(value, _) => {
+                let element = Element::Str(self.symbols.string(value), value.clone());
+                self.emit(element);
+            }
+            // ++x becomes x x 1 + =, x++ takes the 1 back off the stored value: x x 1 + = 1 -
+            Expr::Unary { op, operand } | Expr::Postfix { op, operand }
+                if op == "++" || op == "--" =>
+            {
+                self.expr(operand)?;
+                self.expr(operand)?;
+                self.emit(Element::Const(self.symbols.constant("1"), "1".to_owned()));
+                self.emit(Element::Op(op[..1].to_owned()));
+                self.emit(Element::Op("=".to_owned()));
+                if matches!(e, Expr::Postfix { .. }) {
+                    let undo = if op == "++" { "-" } else { "+" };
+                    self.emit(Element::Const(self.symbols.constant("1"), "1".to_owned()));
+                    self.emit(Element::Op(undo.to_owned()));
+                }
+            }
+            Expr::Unary { op, operand } | Expr::Postfix { op, operand } => {
+                self.expr(operand)?;
+                self.emit(Element::Op(format!("u{}", op)));
+            }
+            // a && b becomes a @false УПЛ b 0 != @end БП false: 0 end:,
+            // a || b becomes a @right УПЛ 1 @end БП right: b 0 != end:
+            Expr::Binary { op, left, right } if op == "&&" || op == "||" => {
+                self.expr(left)?;
+                let to_other = self.jump(true);
+                if op == "&&" {
+                    self.truth(right)?;
+                } else {
+                    self.emit(Element::Const(self.symbols.constant("1"), "1".to_owned()));
+                }
+                let to_end = self.jump(false);
+                self.patch(to_other, self.here());
+                if op == "&&" {
+                    self.emit(Element::Const(self.symbols.constant("0"), "0".to_owned()));
+                } else {
+                    self.truth(right)?;
+                }
+                self.patch(to_end, self.here());
+            }
+            Expr::Binary { op, left, right } => {
+                self.expr(left)?;
+                self.expr(right)?;
+                self.emit(Element::Op(op.clone()));
+            }
+            // a += b becomes a a b + =
+            Expr::Assign { op, target, value } => {
+                self.expr(target)?;
+                if op != "=" {
+                    self.expr(target)?;
+                }
+                self.expr(value)?;
+                if op != "=" {
+                    self.emit(Element::Op(op[..op.len() - 1].to_owned()));
+                }
+                self.emit(Element::Op("=".to_owned()));
+            }
+            Expr::Call { callee, args, .. } => {
+                for arg in args {
+                    self.expr(arg)?;
+                }
+                self.emit(Element::Call(callee.clone(), args.len()));
+            }
+            Expr::Index { array, index } => {
+                self.expr(array)?;
+                self.expr(index)?;
+                self.emit(Element::Index);
+            }
+        }
+        Ok(())
+    }
+
+    /// `e` as 0 or 1
+    fn truth(&mut self, e: &Expr) -> Result<(), Error> {
+        self.expr(e)?;
+        self.emit(Element::Const(self.symbols.constant("0"), "0".to_owned()));
+        self.emit(Element::Op("!=".to_owned()));
+        Ok(())
+    }
+
+    fn declaration(&mut self, d: &Declaration) -> Result<(), Error> {
+        for x in &d.declarators {
+            if is_float(&d.var_type) {
+                if let Some(idx) = self.symbols.identifier(&x.name) {
+                    self.floats.insert(idx);
+                }
+            }
+            if let Some(init) = &x.init {
+                let element = self.ident(&x.name)?;
+                self.emit(element);
+                self.expr(init)?;
+                self.emit(Element::Op("=".to_owned()));
+                self.emit(Element::Pop);
+            }
+        }
+        Ok(())
+    }
+
+    /// Loop body with `break`/`continue` collected, `continue` goes to `next`
+    /// unless it is `None`, in which case it's patched to the position after the body
+    fn loop_body(&mut self, body: &Stmt, next: Option<usize>) -> Result<Loop, Error> {
+        self.jumps.enter();
+        self.stmt(body)?;
+        let l = self.jumps.leave();
+        let next = next.unwrap_or(self.here());
+        for at in &l.continues {
+            self.patch(*at, next);
+        }
+        Ok(l)
+    }
+
+    fn stmt(&mut self, s: &Stmt) -> Result<(), Error> {
+        match s {
+            Stmt::Block(stmts) => {
+                for s in stmts {
+                    self.stmt(s)?;
+                }
+            }
+            Stmt::Declaration(d) => self.declaration(d)?,
+            Stmt::Expr(e) => {
+                self.expr(e)?;
+                self.emit(Element::Pop);
+            }
+            // cond @else УПЛ then @end БП else
+            Stmt::If {
+                cond,
+                then,
+                otherwise,
+            } => {
+                self.expr(cond)?;
+                let to_else = self.jump(true);
+                self.stmt(then)?;
+                match otherwise {
+                    Some(otherwise) => {
+                        let to_end = self.jump(false);
+                        self.patch(to_else, self.here());
+                        self.stmt(otherwise)?;
+                        self.patch(to_end, self.here());
+                    }
+                    None => self.patch(to_else, self.here()),
+                }
+            }
+            // start: cond @end УПЛ body @start БП end:
+            Stmt::While { cond, body } => {
+                let start = self.here();
+                self.expr(cond)?;
+                let to_end = self.jump(true);
+                let l = self.loop_body(body, Some(start))?;
+                self.jump_to(start);
+                self.patch(to_end, self.here());
+                for at in l.breaks {
+                    self.patch(at, self.here());
+                }
+            }
+            // start: body cond @end УПЛ @start БП end:
+            Stmt::DoWhile { body, cond } => {
+                let start = self.here();
+                let l = self.loop_body(body, None)?;
+                self.expr(cond)?;
+                let to_end = self.jump(true);
+                self.jump_to(start);
+                self.patch(to_end, self.here());
+                for at in l.breaks {
+                    self.patch(at, self.here());
+                }
+            }
+            // init start: cond @end УПЛ body step @start БП end:
+            Stmt::For {
+                init,
+                cond,
+                step,
+                body,
+            } => {
+                if let Some(init) = init {
+                    self.stmt(init)?;
+                }
+                let start = self.here();
+                let to_end = match cond {
+                    Some(cond) => {
+                        self.expr(cond)?;
+                        Some(self.jump(true))
+                    }
+                    None => None,
+                };
+                let l = self.loop_body(body, None)?;
+                if let Some(step) = step {
+                    self.expr(step)?;
+                    self.emit(Element::Pop);
+                }
+                self.jump_to(start);
+                if let Some(to_end) = to_end {
+                    self.patch(to_end, self.here());
+                }
+                for at in l.breaks {
+                    self.patch(at, self.here());
+                }
+            }
+            // value c1 == @next УПЛ @body1 БП next: ... @default БП body1 ... end:
+            Stmt::Switch { value, cases } => {
+                let mut to_bodies = vec![];
+                for case in cases {
+                    match &case.value {
+                        Some(c) => {
+                            self.expr(value)?;
+                            self.expr(c)?;
+                            self.emit(Element::Op("==".to_owned()));
+                            let to_next = self.jump(true);
+                            to_bodies.push(Some(self.jump(false)));
+                            self.patch(to_next, self.here());
+                        }
+                        None => to_bodies.push(None),
+                    }
+                }
+                let to_default = self.jump(false);
+                let mut default = None;
+                self.jumps.enter();
+                for (case, to_body) in cases.iter().zip(to_bodies) {
+                    match to_body {
+                        Some(at) => self.patch(at, self.here()),
+                        None => default = Some(self.here()),
+                    }
+                    for s in &case.body {
+                        self.stmt(s)?;
+                    }
+                }
+                let breaks = self.jumps.leave_switch()?;
+                let end = self.here();
+                self.patch(to_default, default.unwrap_or(end));
+                for at in breaks {
+                    self.patch(at, end);
+                }
+            }
+            Stmt::Return(value) => {
+                if let Some(value) = value {
+                    self.expr(value)?;
+                }
+                self.emit(Element::Return);
+            }
+            Stmt::Break => {
+                let at = self.jump(false);
+                self.jumps.break_at(at)?;
+            }
+            Stmt::Continue => {
+                let at = self.jump(false);
+                self.jumps.continue_at(at)?;
+            }
+            Stmt::Goto(label) => {
+                let at = self.jump(false);
+                self.jumps.goto(at, label);
+            }
+            Stmt::Label(label) => {
+                let at = self.here();
+                self.jumps.label(label, at);
+            }
+            Stmt::Empty => {}
+        }
+        Ok(())
+    }
+}
+
+/// Translates the syntax tree to postfix notation. Global initialisers come first,
+/// followed by every function body ending in `ret`
+pub fn translate(program: &Program, symbols: &Symbols) -> Result<Rpn, Error> {
+    let mut t = Translator {
+        symbols,
+        code: vec![],
+        jumps: Jumps::default(),
+        floats: BTreeSet::new(),
+    };
+    let mut globals = vec![];
+    for item in &program.items {
+        if let Item::Declaration(d) = item {
+            for x in &d.declarators {
+                match symbols.identifier(&x.name) {
+                    Some(idx) => globals.push(idx),
+                    None => return Err(Error::UnknownIdentifier(x.name.clone())),
+                }
+            }
+            t.declaration(d)?;
+        }
+    }
+    let mut functions = vec![];
+    for item in &program.items {
+        let Item::Function(f) = item else {
+            continue;
+        };
+        let Some(body) = &f.body else {
+            continue;
+        };
+        let params = f
+            .params
+            .iter()
+            .map(|p| match symbols.identifier(&p.name) {
+                Some(idx) => {
+                    if is_float(&p.param_type) {
+                        t.floats.insert(idx);
+                    }
+                    Ok(idx)
+                }
+                None => Err(Error::UnknownIdentifier(p.name.clone())),
+            })
+            .collect::<Result<Vec<usize>, Error>>()?;
+        functions.push(Function {
+            name: f.name.clone(),
+            params,
+            start: t.here(),
+        });
+        for s in body {
+            t.stmt(s)?;
+        }
+        t.emit(Element::Return);
+        for (at, target) in t.jumps.resolve()? {
+            t.patch(at, target);
+        }
+    }
+    Ok(Rpn {
+        code: t.code,
+        functions,
+        globals,
+        floats: t.floats,
+    })
+}
+
+impl Rpn {
+    /// One line, as written in the textbook
+    pub fn to_text(&self) -> String {
+        self.code
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    /// One element per line with its address and function entry points
+    pub fn listing(&self) -> String {
+        let mut listing = String::new();
+        for (idx, element) in self.code.iter().enumerate() {
+            if let Some(f) = self.functions.iter().find(|f| f.start == idx) {
+                listing.push_str(&format!("\n{}:", f.name));
+            }
+            listing.push_str(&format!("\n{:<4}{}", idx, element));
+        }
+        listing
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::translate;
+
+    fn text(program: &str) -> String {
+        translate(program.to_owned()).unwrap().0.to_text()
+    }
+
+    #[test]
+    fn expression_statements_drop_their_value() {
+        assert_eq!(
+            text("int main() { int x = 1; x = x + 2; }"),
+            "id1 const0 = pop id1 id1 const1 + = pop ret"
+        );
+    }
+
+    #[test]
+    fn postfix_increment_leaves_the_old_value() {
+        assert_eq!(
+            text("int main() { int x = 1; int y = x ++; }"),
+            "id1 const0 = pop id2 id1 id1 const0 + = const0 - = pop ret"
+        );
+    }
+
+    #[test]
+    fn while_jumps_back_to_the_condition() {
+        assert_eq!(
+            text("int main() { int i = 0; while (i < 3) i ++; }"),
+            "id1 const0 = pop id1 const1 < @19 УПЛ id1 id1 1 + = 1 - pop @4 БП ret"
+        );
+    }
+
+    #[test]
+    fn logical_operators_skip_the_right_operand() {
+        assert_eq!(
+            text("int main() { int p = 0; int q = p && p; }"),
+            "id1 const0 = pop id2 id1 @13 УПЛ id1 const0 != @14 БП const0 = pop ret"
+        );
+    }
+
+    #[test]
+    fn break_outside_of_a_loop_is_an_error() {
+        assert_eq!(
+            translate("int main() { break; }".to_owned()).err().unwrap(),
+            "Translation error: break outside of a loop"
+        );
+    }
+}
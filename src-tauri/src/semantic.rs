@@ -0,0 +1,676 @@
+use crate::keywords::Location;
+use crate::syntax::{Declaration, Expr, Function, Item, Program, Stmt};
+use std::collections::BTreeMap;
+
+/// Library functions that may be called without a declaration, arguments aren't checked
+const BUILTINS: [&str; 6] = ["printf", "scanf", "puts", "putchar", "getchar", "malloc"];
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Type {
+    Int,
+    Float,
+    Char,
+    Void,
+    Pointer(Box<Type>),
+    Array(Box<Type>),
+}
+
+impl Type {
+    /// `unsigned long int *` and friends, qualifiers are dropped
+    fn parse(name: &str) -> Type {
+        let words: Vec<&str> = name.split_whitespace().collect();
+        let mut ty = if words.contains(&"float") || words.contains(&"double") {
+            Type::Float
+        } else if words.contains(&"char") {
+            Type::Char
+        } else if words.contains(&"void") {
+            Type::Void
+        } else {
+            Type::Int
+        };
+        for _ in words.iter().filter(|x| **x == "*") {
+            ty = Type::Pointer(Box::new(ty));
+        }
+        ty
+    }
+
+    fn is_arithmetic(&self) -> bool {
+        matches!(self, Type::Int | Type::Float | Type::Char)
+    }
+
+    fn is_integer(&self) -> bool {
+        matches!(self, Type::Int | Type::Char)
+    }
+
+    /// Arrays decay to pointers when used as values
+    fn element(&self) -> Option<&Type> {
+        match self {
+            Type::Pointer(base) | Type::Array(base) => Some(base),
+            _ => None,
+        }
+    }
+
+    fn is_scalar(&self) -> bool {
+        self.is_arithmetic() || self.element().is_some()
+    }
+
+    /// Whether a value of type `from` can be stored into `self` without a cast
+    fn accepts(&self, from: &Type) -> bool {
+        match (self, from) {
+            (a, b) if a.is_arithmetic() && b.is_arithmetic() => true,
+            (Type::Pointer(a), b) => match b.element() {
+                Some(b) => **a == *b || **a == Type::Void || *b == Type::Void,
+                None => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Result of arithmetic on two arithmetic operands
+    fn common(a: &Type, b: &Type) -> Type {
+        if *a == Type::Float || *b == Type::Float {
+            Type::Float
+        } else {
+            Type::Int
+        }
+    }
+}
+
+impl std::fmt::Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Type::Int => f.write_str("int"),
+            Type::Float => f.write_str("float"),
+            Type::Char => f.write_str("char"),
+            Type::Void => f.write_str("void"),
+            Type::Pointer(base) => write!(f, "{}*", base),
+            Type::Array(base) => write!(f, "{}[]", base),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Kind {
+    Variable,
+    Parameter,
+    Function { params: Vec<Type>, defined: bool },
+}
+
+#[derive(Clone, Debug)]
+struct Symbol {
+    ty: Type,
+    kind: Kind,
+    location: Location,
+}
+
+#[derive(Debug)]
+pub struct Diagnostic {
+    pub location: Location,
+    pub message: String,
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Semantic error at {}:{}: {}",
+            self.location.line, self.location.column, self.message
+        )
+    }
+}
+
+pub struct Report {
+    /// Every declaration with its scope and type, in order of appearance
+    pub symbols: String,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+struct Scope {
+    name: String,
+    symbols: BTreeMap<String, Symbol>,
+}
+
+struct Checker {
+    scopes: Vec<Scope>,
+    /// Return type of the function being checked
+    returns: Type,
+    table: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Checker {
+    fn error(&mut self, location: Location, message: String) {
+        self.diagnostics.push(Diagnostic { location, message });
+    }
+
+    fn enter(&mut self, name: String) {
+        self.scopes.push(Scope {
+            name,
+            symbols: BTreeMap::new(),
+        });
+    }
+
+    fn leave(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Symbol> {
+        self.scopes.iter().rev().find_map(|s| s.symbols.get(name))
+    }
+
+    fn declare(&mut self, name: &str, mut symbol: Symbol) {
+        let scope = self.scopes.last().unwrap();
+        if let Some(previous) = scope.symbols.get(name) {
+            // A prototype may come before or after the definition with the same signature
+            let compatible = match (&previous.kind, &symbol.kind) {
+                (
+                    Kind::Function {
+                        params: a,
+                        defined: first,
+                    },
+                    Kind::Function {
+                        params: b,
+                        defined: second,
+                    },
+                ) => !(*first && *second) && a == b && previous.ty == symbol.ty,
+                _ => false,
+            };
+            if !compatible {
+                let message = format!(
+                    "{} is already declared at {}:{}",
+                    name, previous.location.line, previous.location.column
+                );
+                self.error(symbol.location, message);
+                return;
+            }
+            // A later prototype must not let the definition be repeated
+            if let (Kind::Function { defined: true, .. }, Kind::Function { defined, .. }) =
+                (&previous.kind, &mut symbol.kind)
+            {
+                *defined = true;
+            }
+        }
+        let kind = match &symbol.kind {
+            Kind::Variable => "variable",
+            Kind::Parameter => "parameter",
+            Kind::Function { .. } => "function",
+        };
+        self.table.push_str(&format!(
+            "\n{} {} {}: {} ({}:{})",
+            scope.name, kind, name, symbol.ty, symbol.location.line, symbol.location.column
+        ));
+        self.scopes
+            .last_mut()
+            .unwrap()
+            .symbols
+            .insert(name.to_owned(), symbol);
+    }
+
+    fn declaration(&mut self, d: &Declaration) {
+        let base = Type::parse(&d.var_type);
+        for x in &d.declarators {
+            let mut ty = base.clone();
+            if let Some(size) = &x.size {
+                let size_type = self.expr(size);
+                if size_type.is_some_and(|t| !t.is_integer()) {
+                    self.error(size.location(), "array size must be an integer".to_owned());
+                }
+                ty = Type::Array(Box::new(ty));
+            }
+            if ty == Type::Void {
+                let message = format!("variable {} has type void", x.name);
+                self.error(x.location.clone(), message);
+            }
+            // The name is visible in its own initialiser, as in C
+            self.declare(
+                &x.name,
+                Symbol {
+                    ty: ty.clone(),
+                    kind: Kind::Variable,
+                    location: x.location.clone(),
+                },
+            );
+            if let Some(init) = &x.init {
+                if let Some(value) = self.expr(init) {
+                    self.assignable(&ty, &value, init.location());
+                }
+            }
+        }
+    }
+
+    fn assignable(&mut self, target: &Type, value: &Type, location: Location) {
+        let string_into_array = matches!(target, Type::Array(base) if **base == Type::Char)
+            && value.element() == Some(&Type::Char);
+        if !target.accepts(value) && !string_into_array {
+            let message = format!("cannot assign {} to {}", value, target);
+            self.error(location, message);
+        }
+    }
+
+    fn function(&mut self, f: &Function) {
+        let ty = Type::parse(&f.return_type);
+        let params: Vec<Type> = f
+            .params
+            .iter()
+            .map(|p| Type::parse(&p.param_type))
+            .collect();
+        self.declare(
+            &f.name,
+            Symbol {
+                ty: ty.clone(),
+                kind: Kind::Function {
+                    params: params.clone(),
+                    defined: f.body.is_some(),
+                },
+                location: f.location.clone(),
+            },
+        );
+        let Some(body) = &f.body else {
+            return;
+        };
+        self.returns = ty;
+        // Parameters share the scope of the outermost block
+        self.enter(f.name.clone());
+        for (p, ty) in f.params.iter().zip(params) {
+            self.declare(
+                &p.name,
+                Symbol {
+                    ty,
+                    kind: Kind::Parameter,
+                    location: p.location.clone(),
+                },
+            );
+        }
+        for s in body {
+            self.stmt(s);
+        }
+        self.leave();
+    }
+
+    fn condition(&mut self, cond: &Expr) {
+        if let Some(ty) = self.expr(cond) {
+            if !ty.is_scalar() {
+                let message = format!("condition of type {} is not a scalar", ty);
+                self.error(cond.location(), message);
+            }
+        }
+    }
+
+    fn block_name(&self, location: Option<Location>) -> String {
+        let parent = &self.scopes.last().unwrap().name;
+        match location {
+            Some(l) => format!("{}/{}:{}", parent, l.line, l.column),
+            None => format!("{}/block", parent),
+        }
+    }
+
+    fn stmt(&mut self, s: &Stmt) {
+        match s {
+            Stmt::Block(stmts) => {
+                let name = self.block_name(first_location(stmts));
+                self.enter(name);
+                for s in stmts {
+                    self.stmt(s);
+                }
+                self.leave();
+            }
+            Stmt::Declaration(d) => self.declaration(d),
+            Stmt::Expr(e) => {
+                self.expr(e);
+            }
+            Stmt::If {
+                cond,
+                then,
+                otherwise,
+            } => {
+                self.condition(cond);
+                self.stmt(then);
+                if let Some(otherwise) = otherwise {
+                    self.stmt(otherwise);
+                }
+            }
+            Stmt::While { cond, body } | Stmt::DoWhile { body, cond } => {
+                self.condition(cond);
+                self.stmt(body);
+            }
+            Stmt::For {
+                init,
+                cond,
+                step,
+                body,
+            } => {
+                let name = self.block_name(cond.as_ref().map(|c| c.location()));
+                self.enter(name);
+                if let Some(init) = init {
+                    self.stmt(init);
+                }
+                if let Some(cond) = cond {
+                    self.condition(cond);
+                }
+                if let Some(step) = step {
+                    self.expr(step);
+                }
+                self.stmt(body);
+                self.leave();
+            }
+            Stmt::Switch { value, cases } => {
+                if let Some(ty) = self.expr(value) {
+                    if !ty.is_integer() {
+                        let message = format!("switch on {}, expected an integer", ty);
+                        self.error(value.location(), message);
+                    }
+                }
+                for case in cases {
+                    if let Some(value) = &case.value {
+                        self.expr(value);
+                    }
+                    for s in &case.body {
+                        self.stmt(s);
+                    }
+                }
+            }
+            Stmt::Return(value) => {
+                let returns = self.returns.clone();
+                // No location to report without a value, the parser doesn't keep it
+                if let Some(value) = value {
+                    let Some(ty) = self.expr(value) else {
+                        return;
+                    };
+                    if returns == Type::Void {
+                        self.error(value.location(), "void function returns a value".to_owned());
+                    } else {
+                        self.assignable(&returns, &ty, value.location());
+                    }
+                }
+            }
+            Stmt::Break | Stmt::Continue | Stmt::Goto(_) | Stmt::Label(_) | Stmt::Empty => {}
+        }
+    }
+
+    fn lvalue(&mut self, e: &Expr, op: &str) {
+        let is_lvalue = match e {
+            Expr::Ident(name, _) => !matches!(
+                self.lookup(name),
+                Some(Symbol {
+                    kind: Kind::Function { .. },
+                    ..
+                }) | Some(Symbol {
+                    ty: Type::Array(_),
+                    ..
+                })
+            ),
+            Expr::Index { .. } => true,
+            Expr::Unary { op, .. } => op == "*",
+            _ => false,
+        };
+        if !is_lvalue {
+            self.error(
+                e.location(),
+                format!("left operand of {} is not assignable", op),
+            );
+        }
+    }
+
+    /// Type of `e`, `None` when it couldn't be determined because of an earlier error
+    fn expr(&mut self, e: &Expr) -> Option<Type> {
+        match e {
+            Expr::Ident(name, location) => match self.lookup(name) {
+                Some(Symbol {
+                    kind: Kind::Function { .. },
+                    ..
+                }) => {
+                    self.error(
+                        location.clone(),
+                        format!("function {} used as a value", name),
+                    );
+                    None
+                }
+                Some(symbol) => Some(symbol.ty.clone()),
+                None => {
+                    self.error(location.clone(), format!("{} is not declared", name));
+                    None
+                }
+            },
+            Expr::Const(value, _) => {
+                let is_float = !value.starts_with("0x") && value.contains(['.', 'e', 'E']);
+                Some(if is_float { Type::Float } else { Type::Int })
+            }
+            Expr::Str(..) => Some(Type::Pointer(Box::new(Type::Char))),
+            Expr::Unary { op, operand } | Expr::Postfix { op, operand } => {
+                let ty = self.expr(operand)?;
+                let result = match op.as_str() {
+                    "-" | "+" if ty.is_arithmetic() => Some(Type::common(&ty, &Type::Int)),
+                    "!" if ty.is_scalar() => Some(Type::Int),
+                    "~" if ty.is_integer() => Some(Type::Int),
+                    "++" | "--" if ty.is_scalar() => {
+                        self.lvalue(operand, op);
+                        Some(ty.clone())
+                    }
+                    "*" => ty.element().cloned(),
+                    "&" => {
+                        self.lvalue(operand, op);
+                        Some(Type::Pointer(Box::new(ty.clone())))
+                    }
+                    _ => None,
+                };
+                if result.is_none() {
+                    let message = format!("operator {} cannot be applied to {}", op, ty);
+                    self.error(operand.location(), message);
+                }
+                result
+            }
+            Expr::Binary { op, left, right } => {
+                let (a, b) = (self.expr(left)?, self.expr(right)?);
+                let result = binary(op, &a, &b);
+                if result.is_none() {
+                    let message = format!("operator {} cannot be applied to {} and {}", op, a, b);
+                    self.error(left.location(), message);
+                }
+                result
+            }
+            Expr::Assign { op, target, value } => {
+                let target_type = self.expr(target);
+                let value_type = self.expr(value);
+                self.lvalue(target, op);
+                let (target_type, value_type) = (target_type?, value_type?);
+                if op == "=" {
+                    self.assignable(&target_type, &value_type, value.location());
+                } else if binary(&op[..op.len() - 1], &target_type, &value_type).is_none() {
+                    let message = format!(
+                        "operator {} cannot be applied to {} and {}",
+                        op, target_type, value_type
+                    );
+                    self.error(target.location(), message);
+                }
+                Some(target_type)
+            }
+            Expr::Call {
+                callee,
+                args,
+                location,
+            } => {
+                let arg_types: Vec<Option<Type>> = args.iter().map(|a| self.expr(a)).collect();
+                let symbol = match self.lookup(callee) {
+                    Some(symbol) => symbol.clone(),
+                    None if BUILTINS.contains(&callee.as_str()) => return Some(Type::Int),
+                    None => {
+                        self.error(location.clone(), format!("{} is not declared", callee));
+                        return None;
+                    }
+                };
+                let Kind::Function { params, .. } = symbol.kind else {
+                    self.error(location.clone(), format!("{} is not a function", callee));
+                    return None;
+                };
+                if params.len() != args.len() {
+                    let message = format!(
+                        "{} takes {} arguments, {} given",
+                        callee,
+                        params.len(),
+                        args.len()
+                    );
+                    self.error(location.clone(), message);
+                }
+                for ((param, arg), arg_type) in params.iter().zip(args).zip(arg_types) {
+                    if let Some(arg_type) = arg_type {
+                        self.assignable(param, &arg_type, arg.location());
+                    }
+                }
+                Some(symbol.ty)
+            }
+            Expr::Index { array, index } => {
+                let array_type = self.expr(array);
+                let index_type = self.expr(index);
+                if let Some(index_type) = index_type {
+                    if !index_type.is_integer() {
+                        let message = format!("index of type {}", index_type);
+                        self.error(index.location(), message);
+                    }
+                }
+                let array_type = array_type?;
+                match array_type.element() {
+                    Some(element) => Some(element.clone()),
+                    None => {
+                        let message = format!("{} cannot be indexed", array_type);
+                        self.error(array.location(), message);
+                        None
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Result type of a binary operator, `None` if the operands don't fit
+fn binary(op: &str, a: &Type, b: &Type) -> Option<Type> {
+    match op {
+        "+" | "-" | "*" | "/" if a.is_arithmetic() && b.is_arithmetic() => Some(Type::common(a, b)),
+        // Pointer arithmetic
+        "+" | "-" if a.element().is_some() && b.is_integer() => {
+            Some(Type::Pointer(Box::new(a.element()?.clone())))
+        }
+        "+" if a.is_integer() && b.element().is_some() => {
+            Some(Type::Pointer(Box::new(b.element()?.clone())))
+        }
+        "%" | "&" | "|" | "^" | "<<" | ">>" if a.is_integer() && b.is_integer() => Some(Type::Int),
+        "<" | ">" | "<=" | ">=" | "==" | "!=" if a.is_arithmetic() && b.is_arithmetic() => {
+            Some(Type::Int)
+        }
+        "<" | ">" | "<=" | ">=" | "==" | "!="
+            if a.element().is_some() && a.element() == b.element() =>
+        {
+            Some(Type::Int)
+        }
+        "&&" | "||" if a.is_scalar() && b.is_scalar() => Some(Type::Int),
+        _ => None,
+    }
+}
+
+/// Location of the first thing in a block, used to name its scope
+fn first_location(stmts: &[Stmt]) -> Option<Location> {
+    stmts.iter().find_map(|s| match s {
+        Stmt::Expr(e) => Some(e.location()),
+        Stmt::Declaration(d) => d.declarators.first().map(|x| x.location.clone()),
+        Stmt::Block(stmts) => first_location(stmts),
+        Stmt::If { cond, .. } | Stmt::While { cond, .. } => Some(cond.location()),
+        Stmt::Return(Some(e)) => Some(e.location()),
+        _ => None,
+    })
+}
+
+/// Checks declaration before use, redeclarations and operand types
+pub fn check(program: &Program) -> Report {
+    let mut checker = Checker {
+        scopes: vec![],
+        returns: Type::Void,
+        table: String::new(),
+        diagnostics: vec![],
+    };
+    checker.enter("global".to_owned());
+    for item in &program.items {
+        match item {
+            Item::Function(f) => checker.function(f),
+            Item::Declaration(d) => checker.declaration(d),
+        }
+    }
+    Report {
+        symbols: checker.table,
+        diagnostics: checker.diagnostics,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keywords::count_tokens;
+    use crate::syntax::parse;
+
+    fn check_program(program: &str) -> Report {
+        check(&parse(count_tokens(program.to_owned()).unwrap()).unwrap())
+    }
+
+    fn diagnostics(program: &str) -> Vec<String> {
+        check_program(program)
+            .diagnostics
+            .iter()
+            .map(|d| d.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn a_correct_program_has_no_diagnostics() {
+        let program = "int square(int x) { return x * x; }
+int main() { int a[3]; a[0] = square(2); printf(\"%d\", a[0]); return 0; }";
+        assert_eq!(diagnostics(program), Vec::<String>::new());
+    }
+
+    #[test]
+    fn symbols_are_listed_with_their_scope() {
+        let report = check_program("int n;\nint main() { float f; return 0; }");
+        assert_eq!(
+            report.symbols,
+            "\nglobal variable n: int (0:5)\nglobal function main: int (1:5)\nmain variable f: float (1:20)"
+        );
+    }
+
+    #[test]
+    fn reports_undeclared_names() {
+        assert_eq!(
+            diagnostics("int main() { return y; }"),
+            ["Semantic error at 0:21: y is not declared"]
+        );
+    }
+
+    #[test]
+    fn reports_mismatched_types() {
+        let program = "int main() { int *p; float f = 1.5; p = f; return 0; }";
+        assert_eq!(
+            diagnostics(program),
+            ["Semantic error at 0:41: cannot assign float to int*"]
+        );
+    }
+
+    #[test]
+    fn prototypes_must_match_the_definition() {
+        assert_eq!(
+            diagnostics("int f(int a);\nint f(int a) { return a; }\nint f(int a);"),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            diagnostics("int f(int a) { return a; }\nint f(int a);\nint f(int a) { return a; }"),
+            ["Semantic error at 2:5: f is already declared at 1:5"]
+        );
+        assert_eq!(
+            diagnostics("int f(int a) { return a; }\nint f(float a, int b);"),
+            ["Semantic error at 1:5: f is already declared at 0:5"]
+        );
+    }
+
+    #[test]
+    fn void_functions_return_nothing() {
+        assert_eq!(
+            diagnostics("void f() { return 1; }"),
+            ["Semantic error at 0:19: void function returns a value"]
+        );
+    }
+}
@@ -0,0 +1,241 @@
+use crate::automaton::{alphabet, escape, Automaton, CharClass};
+use crate::consistency::lexer_automaton;
+use crate::descriptors::create_descriptors;
+use crate::diagram::Diagram;
+use crate::grammar::format_table;
+use crate::keywords::{Stepper, Token};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Longer inputs are cut off, every step carries its own diagram and tables
+const MAX_STEPS: usize = 2000;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SimulationStep {
+    pub current: String,
+    /// `line:column` of the character
+    pub at: String,
+    pub from: String,
+    pub to: String,
+    pub buffer: String,
+    /// The character is read again by the next step
+    pub reread: bool,
+    /// The token this step flushed, empty when `is_writable` wasn't set
+    pub flushed: String,
+    /// Tokens emitted so far
+    pub tokens: String,
+    pub descriptors: String,
+    /// The states of the current token and where the lexer can go next,
+    /// the state reached drawn filled
+    pub svg: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SimulationReport {
+    pub steps: Vec<SimulationStep>,
+    /// Every step in one table
+    pub trace: String,
+    pub error: String,
+}
+
+fn describe(token: &Token) -> String {
+    format!(
+        "{:?} {:?} {}:{}",
+        token.token_type, token.token, token.location.line, token.location.column
+    )
+}
+
+fn format_tokens(tokens: &[Token]) -> String {
+    let mut rows = vec![vec![
+        "#".to_owned(),
+        "class".to_owned(),
+        "token".to_owned(),
+        "at".to_owned(),
+    ]];
+    for (idx, token) in tokens.iter().enumerate() {
+        rows.push(vec![
+            idx.to_string(),
+            format!("{:?}", token.token_type),
+            format!("{:?}", token.token),
+            format!("{}:{}", token.location.line, token.location.column),
+        ]);
+    }
+    format!("\n{}", format_table(&rows))
+}
+
+/// Edges leaving `state`, grouped by target; the full `Automaton::edges`
+/// of the lexer takes seconds, so states are expanded as the run reaches them
+fn outgoing(lexer: &Automaton, state: &str) -> Vec<(String, CharClass)> {
+    let mut targets: Vec<(&str, Vec<char>)> = vec![];
+    for c in alphabet() {
+        if let Some(t) = lexer.step(state, c) {
+            match targets.iter_mut().find(|(to, _)| *to == t.to) {
+                Some((_, chars)) => chars.push(c),
+                None => targets.push((&t.to, vec![c])),
+            }
+        }
+    }
+    targets
+        .into_iter()
+        .map(|(to, chars)| (to.to_owned(), CharClass::from_chars(&chars)))
+        .collect()
+}
+
+/// Part of the lexer automaton around `current`: the states `path` went
+/// through and the ones `current` moves to; `current` is the end of `path`
+fn neighbourhood(
+    lexer: &Automaton,
+    edges: &mut BTreeMap<String, Vec<(String, CharClass)>>,
+    path: &[String],
+    current: &str,
+) -> Diagram {
+    for state in path {
+        if !edges.contains_key(state) {
+            edges.insert(state.to_owned(), outgoing(lexer, state));
+        }
+    }
+    let mut names: Vec<String> = path.to_vec();
+    for (to, _) in &edges[current] {
+        if !names.contains(to) {
+            names.push(to.clone());
+        }
+    }
+    let id = |state: &str| names.iter().position(|s| s == state);
+    Diagram {
+        start: 0,
+        accepting: names
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| lexer.accepting.contains(*s))
+            .map(|(idx, _)| idx)
+            .collect::<BTreeSet<usize>>(),
+        edges: path
+            .iter()
+            .flat_map(|from| edges[from].iter().map(move |(to, class)| (from, to, class)))
+            .filter_map(|(from, to, class)| Some((id(from)?, id(to)?, class.text.clone())))
+            .collect(),
+        highlight: id(current),
+        names,
+    }
+}
+
+/// Runs `count_tokens` on `text` step by step, recording each step
+pub fn simulate(text: &str) -> SimulationReport {
+    let lexer = lexer_automaton();
+    let mut edges = BTreeMap::new();
+    let mut stepper = Stepper::new(text.to_owned());
+    let start = lexer.start.clone();
+    let mut path = vec![start.clone()];
+    let mut steps = vec![];
+    let mut rows = vec![vec![
+        "#".to_owned(),
+        "char".to_owned(),
+        "at".to_owned(),
+        "from".to_owned(),
+        "to".to_owned(),
+        "buffer".to_owned(),
+        "flushed".to_owned(),
+    ]];
+    let mut error = String::new();
+    while let Some(step) = stepper.step() {
+        if steps.len() == MAX_STEPS {
+            error = format!("stopped after {} steps", MAX_STEPS);
+            break;
+        }
+        let step = match step {
+            Ok(step) => step,
+            Err(e) => {
+                error = e.to_string();
+                break;
+            }
+        };
+        let (from, to) = (format!("{:?}", step.from), format!("{:?}", step.to));
+        if !path.contains(&to) {
+            path.push(to.clone());
+        }
+        let svg = neighbourhood(&lexer, &mut edges, &path, &to).to_svg();
+        let flushed = step.flushed.as_ref().map_or(String::new(), describe);
+        let current = escape(step.current);
+        let at = format!("{}:{}", step.location.line, step.location.column);
+        let reread = if step.reread { " (read again)" } else { "" };
+        rows.push(vec![
+            steps.len().to_string(),
+            format!("{}{}", current, reread),
+            at.clone(),
+            from.clone(),
+            to.clone(),
+            format!("{:?}", step.buffer),
+            flushed.clone(),
+        ]);
+        steps.push(SimulationStep {
+            current,
+            at,
+            from,
+            to,
+            buffer: step.buffer,
+            reread: step.reread,
+            flushed,
+            tokens: format_tokens(&stepper.tokens),
+            descriptors: create_descriptors(stepper.tokens.clone())
+                .map_or(String::new(), |table| table.descriptors),
+            svg,
+        });
+        if step.flushed.is_some() {
+            path = vec![start.clone()];
+        }
+    }
+    SimulationReport {
+        steps,
+        trace: format!("\n{}", format_table(&rows)),
+        error,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_every_step_with_its_states() {
+        let report = simulate("x;");
+        assert_eq!(report.error, "");
+        let moves: Vec<(&str, &str, &str, bool)> = report
+            .steps
+            .iter()
+            .map(|s| (s.current.as_str(), s.from.as_str(), s.to.as_str(), s.reread))
+            .collect();
+        assert_eq!(
+            moves,
+            [
+                ("x", "Whitespace", "Letter('x')", false),
+                (";", "Letter('x')", "Letter('x')", true),
+                (";", "Whitespace", "Separator(';')", false)
+            ]
+        );
+        assert_eq!(report.steps[1].flushed, "Identifier \"x\" 0:1");
+        assert!(report.steps[2].tokens.contains("Separator"));
+        assert_eq!(report.trace.lines().count(), report.steps.len() + 2);
+    }
+
+    #[test]
+    fn draws_the_path_of_the_current_token() {
+        let lexer = lexer_automaton();
+        let mut edges = BTreeMap::new();
+        let path = vec![lexer.start.clone(), "Letter('x')".to_owned()];
+        let diagram = neighbourhood(&lexer, &mut edges, &path, "Letter('x')");
+        assert_eq!(diagram.names[..2], path[..]);
+        assert_eq!(diagram.highlight, Some(1));
+        assert!(diagram
+            .edges
+            .iter()
+            .any(|(from, to, _)| (*from, *to) == (0, 1)));
+        assert_eq!(edges.len(), 2);
+    }
+
+    #[test]
+    fn stops_at_the_lexer_error() {
+        let report = simulate("x++");
+        assert!(report.error.starts_with("Keyword error at 0:"));
+        assert!(!report.steps.is_empty());
+    }
+}
@@ -0,0 +1,792 @@
+use crate::keywords::{Location, Token, TokenType};
+
+/// Words that may start a declaration
+const TYPES: [&str; 15] = [
+    "int", "float", "double", "char", "void", "long", "short", "unsigned", "signed", "bool",
+    "const", "static", "auto", "extern", "volatile",
+];
+
+/// Statement keywords that can never be identifiers
+const KEYWORDS: [&str; 12] = [
+    "if", "else", "while", "do", "for", "switch", "case", "default", "return", "break", "continue",
+    "goto",
+];
+
+const ASSIGN: [&str; 11] = [
+    "=", "+=", "-=", "*=", "/=", "%=", "<<=", ">>=", "&=", "|=", "^=",
+];
+
+/// Binary operators from the lowest to the highest precedence
+const BINARY: [&[&str]; 10] = [
+    &["||"],
+    &["&&"],
+    &["|"],
+    &["^"],
+    &["&"],
+    &["==", "!="],
+    &["<", ">", "<=", ">="],
+    &["<<", ">>"],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+const UNARY: [&str; 8] = ["-", "+", "!", "~", "++", "--", "*", "&"];
+
+#[derive(Debug)]
+pub enum Error {
+    /// Expected something else at the token
+    Expected(String, Token),
+    /// Ran out of tokens, location of the last one
+    UnexpectedEnd(String, Location),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Expected(what, t) => write!(
+                f,
+                "Syntax error at {}:{}: expected {}, found {}",
+                t.location.line, t.location.column, what, t.token
+            ),
+            Self::UnexpectedEnd(what, l) => write!(
+                f,
+                "Syntax error at {}:{}: expected {}, found end of program",
+                l.line, l.column, what
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Program {
+    pub items: Vec<Item>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Item {
+    Function(Function),
+    Declaration(Declaration),
+}
+
+#[derive(Debug, Clone)]
+pub struct Function {
+    pub return_type: String,
+    pub name: String,
+    pub params: Vec<Param>,
+    /// `None` for prototypes
+    pub body: Option<Vec<Stmt>>,
+    pub location: Location,
+}
+
+#[derive(Debug, Clone)]
+pub struct Param {
+    pub param_type: String,
+    pub name: String,
+    pub location: Location,
+}
+
+#[derive(Debug, Clone)]
+pub struct Declaration {
+    pub var_type: String,
+    pub declarators: Vec<Declarator>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Declarator {
+    pub name: String,
+    /// Array size, `int a[10]`
+    pub size: Option<Expr>,
+    pub init: Option<Expr>,
+    pub location: Location,
+}
+
+#[derive(Debug, Clone)]
+pub enum Stmt {
+    Block(Vec<Stmt>),
+    Declaration(Declaration),
+    Expr(Expr),
+    If {
+        cond: Expr,
+        then: Box<Stmt>,
+        otherwise: Option<Box<Stmt>>,
+    },
+    While {
+        cond: Expr,
+        body: Box<Stmt>,
+    },
+    DoWhile {
+        body: Box<Stmt>,
+        cond: Expr,
+    },
+    For {
+        init: Option<Box<Stmt>>,
+        cond: Option<Expr>,
+        step: Option<Expr>,
+        body: Box<Stmt>,
+    },
+    Switch {
+        value: Expr,
+        cases: Vec<Case>,
+    },
+    Return(Option<Expr>),
+    Break,
+    Continue,
+    Goto(String),
+    Label(String),
+    Empty,
+}
+
+#[derive(Debug, Clone)]
+pub struct Case {
+    /// `None` for `default`
+    pub value: Option<Expr>,
+    pub body: Vec<Stmt>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Expr {
+    Ident(String, Location),
+    Const(String, Location),
+    Str(String, Location),
+    Unary {
+        op: String,
+        operand: Box<Expr>,
+    },
+    /// `i++`, `i--`
+    Postfix {
+        op: String,
+        operand: Box<Expr>,
+    },
+    Binary {
+        op: String,
+        left: Box<Expr>,
+        right: Box<Expr>,
+    },
+    Assign {
+        op: String,
+        target: Box<Expr>,
+        value: Box<Expr>,
+    },
+    Call {
+        callee: String,
+        args: Vec<Expr>,
+        location: Location,
+    },
+    Index {
+        array: Box<Expr>,
+        index: Box<Expr>,
+    },
+}
+
+impl Expr {
+    pub fn location(&self) -> Location {
+        match self {
+            Expr::Ident(_, l) | Expr::Const(_, l) | Expr::Str(_, l) => l.clone(),
+            Expr::Call { location, .. } => location.clone(),
+            Expr::Unary { operand, .. } | Expr::Postfix { operand, .. } => operand.location(),
+            Expr::Binary { left, .. } => left.location(),
+            Expr::Assign { target, .. } => target.location(),
+            Expr::Index { array, .. } => array.location(),
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(|t| t.token.as_str())
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<&str> {
+        self.tokens.get(self.pos + offset).map(|t| t.token.as_str())
+    }
+
+    fn error(&self, what: &str) -> Error {
+        match self.tokens.get(self.pos) {
+            Some(t) => Error::Expected(what.to_owned(), t.clone()),
+            None => Error::UnexpectedEnd(
+                what.to_owned(),
+                self.tokens
+                    .last()
+                    .map(|t| t.location.clone())
+                    .unwrap_or(Location {
+                        line: 0,
+                        column: 0,
+                        char: ' ',
+                    }),
+            ),
+        }
+    }
+
+    fn next(&mut self, what: &str) -> Result<Token, Error> {
+        match self.tokens.get(self.pos) {
+            Some(t) => {
+                self.pos += 1;
+                Ok(t.clone())
+            }
+            None => Err(self.error(what)),
+        }
+    }
+
+    fn eat(&mut self, text: &str) -> bool {
+        if self.peek() == Some(text) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, text: &str) -> Result<Location, Error> {
+        if self.peek() == Some(text) {
+            Ok(self.next(text)?.location)
+        } else {
+            Err(self.error(&format!("'{}'", text)))
+        }
+    }
+
+    fn is_type(&self) -> bool {
+        self.peek().is_some_and(|t| TYPES.contains(&t))
+    }
+
+    /// The lexer marks some keywords as identifiers, so names are checked by text
+    fn is_name(&self) -> bool {
+        match self.tokens.get(self.pos) {
+            Some(t) => {
+                let text = t.token.as_str();
+                t.token_type == TokenType::Identifier
+                    && !text.starts_with('#')
+                    && !TYPES.contains(&text)
+                    && !KEYWORDS.contains(&text)
+            }
+            None => false,
+        }
+    }
+
+    fn name(&mut self) -> Result<(String, Location), Error> {
+        if !self.is_name() {
+            return Err(self.error("identifier"));
+        }
+        let t = self.next("identifier")?;
+        Ok((t.token, t.location))
+    }
+
+    fn type_name(&mut self) -> Result<String, Error> {
+        let mut words = vec![];
+        while self.is_type() {
+            words.push(self.next("type")?.token);
+        }
+        if words.is_empty() {
+            return Err(self.error("type"));
+        }
+        while self.eat("*") {
+            words.push("*".to_owned());
+        }
+        Ok(words.join(" "))
+    }
+
+    /// `#include`, `#define` and the rest of their line are skipped
+    fn skip_preprocessor(&mut self) {
+        while let Some(t) = self.tokens.get(self.pos) {
+            if !t.token.starts_with('#') {
+                break;
+            }
+            let line = t.location.line;
+            self.pos += 1;
+            while self
+                .tokens
+                .get(self.pos)
+                .is_some_and(|t| t.location.line == line)
+            {
+                self.pos += 1;
+            }
+        }
+    }
+
+    fn program(&mut self) -> Result<Program, Error> {
+        let mut items = vec![];
+        loop {
+            self.skip_preprocessor();
+            if self.peek().is_none() {
+                break;
+            }
+            items.push(self.item()?);
+        }
+        Ok(Program { items })
+    }
+
+    fn item(&mut self) -> Result<Item, Error> {
+        let var_type = self.type_name()?;
+        let (name, location) = self.name()?;
+        if !self.eat("(") {
+            let declaration = self.declaration_rest(var_type, name, location)?;
+            return Ok(Item::Declaration(declaration));
+        }
+        let mut params = vec![];
+        if self.peek() == Some("void") && self.peek_at(1) == Some(")") {
+            self.pos += 1;
+        }
+        if !self.eat(")") {
+            loop {
+                let param_type = self.type_name()?;
+                let (name, location) = self.name()?;
+                if self.eat("[") {
+                    self.expect("]")?;
+                }
+                params.push(Param {
+                    param_type,
+                    name,
+                    location,
+                });
+                if !self.eat(",") {
+                    break;
+                }
+            }
+            self.expect(")")?;
+        }
+        let body = if self.eat(";") {
+            None
+        } else {
+            self.expect("{")?;
+            Some(self.block_rest()?)
+        };
+        Ok(Item::Function(Function {
+            return_type: var_type,
+            name,
+            params,
+            body,
+            location,
+        }))
+    }
+
+    fn declarator_rest(&mut self, name: String, location: Location) -> Result<Declarator, Error> {
+        let size = if self.eat("[") {
+            let size = self.expr()?;
+            self.expect("]")?;
+            Some(size)
+        } else {
+            None
+        };
+        let init = if self.eat("=") {
+            Some(self.assignment()?)
+        } else {
+            None
+        };
+        Ok(Declarator {
+            name,
+            size,
+            init,
+            location,
+        })
+    }
+
+    /// Declarators after the first name and the closing `;`
+    fn declaration_rest(
+        &mut self,
+        var_type: String,
+        name: String,
+        location: Location,
+    ) -> Result<Declaration, Error> {
+        let mut declarators = vec![self.declarator_rest(name, location)?];
+        while self.eat(",") {
+            let (name, location) = self.name()?;
+            declarators.push(self.declarator_rest(name, location)?);
+        }
+        self.expect(";")?;
+        Ok(Declaration {
+            var_type,
+            declarators,
+        })
+    }
+
+    fn declaration(&mut self) -> Result<Declaration, Error> {
+        let var_type = self.type_name()?;
+        let (name, location) = self.name()?;
+        self.declaration_rest(var_type, name, location)
+    }
+
+    /// Statements up to and including the closing `}`
+    fn block_rest(&mut self) -> Result<Vec<Stmt>, Error> {
+        let mut stmts = vec![];
+        while !self.eat("}") {
+            if self.peek().is_none() {
+                return Err(self.error("'}'"));
+            }
+            stmts.push(self.stmt()?);
+        }
+        Ok(stmts)
+    }
+
+    fn condition(&mut self) -> Result<Expr, Error> {
+        self.expect("(")?;
+        let cond = self.expr()?;
+        self.expect(")")?;
+        Ok(cond)
+    }
+
+    fn stmt(&mut self) -> Result<Stmt, Error> {
+        if self.is_type() {
+            return Ok(Stmt::Declaration(self.declaration()?));
+        }
+        if self.is_name() && self.peek_at(1) == Some(":") {
+            let (label, _) = self.name()?;
+            self.pos += 1;
+            return Ok(Stmt::Label(label));
+        }
+        let stmt = match self.peek() {
+            Some("{") => {
+                self.pos += 1;
+                Stmt::Block(self.block_rest()?)
+            }
+            Some(";") => {
+                self.pos += 1;
+                Stmt::Empty
+            }
+            Some("if") => {
+                self.pos += 1;
+                let cond = self.condition()?;
+                let then = Box::new(self.stmt()?);
+                let otherwise = if self.eat("else") {
+                    Some(Box::new(self.stmt()?))
+                } else {
+                    None
+                };
+                Stmt::If {
+                    cond,
+                    then,
+                    otherwise,
+                }
+            }
+            Some("while") => {
+                self.pos += 1;
+                let cond = self.condition()?;
+                let body = Box::new(self.stmt()?);
+                Stmt::While { cond, body }
+            }
+            Some("do") => {
+                self.pos += 1;
+                let body = Box::new(self.stmt()?);
+                self.expect("while")?;
+                let cond = self.condition()?;
+                self.expect(";")?;
+                Stmt::DoWhile { body, cond }
+            }
+            Some("for") => {
+                self.pos += 1;
+                self.expect("(")?;
+                let init = if self.eat(";") {
+                    None
+                } else if self.is_type() {
+                    Some(Box::new(Stmt::Declaration(self.declaration()?)))
+                } else {
+                    let init = self.expr()?;
+                    self.expect(";")?;
+                    Some(Box::new(Stmt::Expr(init)))
+                };
+                let cond = if self.peek() == Some(";") {
+                    None
+                } else {
+                    Some(self.expr()?)
+                };
+                self.expect(";")?;
+                let step = if self.peek() == Some(")") {
+                    None
+                } else {
+                    Some(self.expr()?)
+                };
+                self.expect(")")?;
+                let body = Box::new(self.stmt()?);
+                Stmt::For {
+                    init,
+                    cond,
+                    step,
+                    body,
+                }
+            }
+            Some("switch") => {
+                self.pos += 1;
+                let value = self.condition()?;
+                self.expect("{")?;
+                let mut cases: Vec<Case> = vec![];
+                while !self.eat("}") {
+                    let value = if self.eat("case") {
+                        Some(self.expr()?)
+                    } else if self.eat("default") {
+                        None
+                    } else {
+                        return Err(self.error("'case' or 'default'"));
+                    };
+                    self.expect(":")?;
+                    let mut body = vec![];
+                    while !matches!(self.peek(), Some("case" | "default" | "}") | None) {
+                        body.push(self.stmt()?);
+                    }
+                    cases.push(Case { value, body });
+                }
+                Stmt::Switch { value, cases }
+            }
+            Some("return") => {
+                self.pos += 1;
+                let value = if self.peek() == Some(";") {
+                    None
+                } else {
+                    Some(self.expr()?)
+                };
+                self.expect(";")?;
+                Stmt::Return(value)
+            }
+            Some("break") => {
+                self.pos += 1;
+                self.expect(";")?;
+                Stmt::Break
+            }
+            Some("continue") => {
+                self.pos += 1;
+                self.expect(";")?;
+                Stmt::Continue
+            }
+            Some("goto") => {
+                self.pos += 1;
+                let (label, _) = self.name()?;
+                self.expect(";")?;
+                Stmt::Goto(label)
+            }
+            _ => {
+                let expr = self.expr()?;
+                self.expect(";")?;
+                Stmt::Expr(expr)
+            }
+        };
+        Ok(stmt)
+    }
+
+    fn expr(&mut self) -> Result<Expr, Error> {
+        self.assignment()
+    }
+
+    fn assignment(&mut self) -> Result<Expr, Error> {
+        let target = self.binary(0)?;
+        match self.peek() {
+            Some(op) if ASSIGN.contains(&op) => {
+                let op = op.to_owned();
+                self.pos += 1;
+                let value = self.assignment()?;
+                Ok(Expr::Assign {
+                    op,
+                    target: Box::new(target),
+                    value: Box::new(value),
+                })
+            }
+            _ => Ok(target),
+        }
+    }
+
+    /// Signed constants such as `-1` come from the lexer as one token,
+    /// after an operand they are a `+`/`-` followed by the constant
+    fn split_signed_const(&mut self) -> Option<String> {
+        let t = self.tokens.get(self.pos)?;
+        let text = t.token.as_str();
+        if t.token_type != TokenType::ConstValue || text.len() < 2 {
+            return None;
+        }
+        let op = &text[..1];
+        if op != "-" && op != "+" {
+            return None;
+        }
+        let op = op.to_owned();
+        let mut rest = t.clone();
+        rest.token = text[1..].to_owned();
+        rest.location.column += 1;
+        self.tokens[self.pos] = rest;
+        Some(op)
+    }
+
+    fn binary(&mut self, level: usize) -> Result<Expr, Error> {
+        if level == BINARY.len() {
+            return self.unary();
+        }
+        let mut left = self.binary(level + 1)?;
+        loop {
+            let op = match self.peek() {
+                Some(op) if BINARY[level].contains(&op) => {
+                    let op = op.to_owned();
+                    self.pos += 1;
+                    op
+                }
+                _ if BINARY[level].contains(&"+") => match self.split_signed_const() {
+                    Some(op) => op,
+                    None => break,
+                },
+                _ => break,
+            };
+            let right = self.binary(level + 1)?;
+            left = Expr::Binary {
+                op,
+                left: Box::new(left),
+                right: Box::new(right),
+            };
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, Error> {
+        match self.peek() {
+            Some(op) if UNARY.contains(&op) => {
+                let op = op.to_owned();
+                self.pos += 1;
+                let operand = self.unary()?;
+                Ok(Expr::Unary {
+                    op,
+                    operand: Box::new(operand),
+                })
+            }
+            _ => self.postfix(),
+        }
+    }
+
+    fn postfix(&mut self) -> Result<Expr, Error> {
+        let mut expr = self.primary()?;
+        loop {
+            if self.eat("[") {
+                let index = self.expr()?;
+                self.expect("]")?;
+                expr = Expr::Index {
+                    array: Box::new(expr),
+                    index: Box::new(index),
+                };
+            } else if matches!(self.peek(), Some("++" | "--")) {
+                let op = self.next("operator")?.token;
+                expr = Expr::Postfix {
+                    op,
+                    operand: Box::new(expr),
+                };
+            } else {
+                return Ok(expr);
+            }
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, Error> {
+        if self.eat("(") {
+            let expr = self.expr()?;
+            self.expect(")")?;
+            return Ok(expr);
+        }
+        if self.is_name() {
+            let (name, location) = self.name()?;
+            if !self.eat("(") {
+                return Ok(Expr::Ident(name, location));
+            }
+            let mut args = vec![];
+            if !self.eat(")") {
+                loop {
+                    args.push(self.assignment()?);
+                    if !self.eat(",") {
+                        break;
+                    }
+                }
+                self.expect(")")?;
+            }
+            return Ok(Expr::Call {
+                callee: name,
+                args,
+                location,
+            });
+        }
+        match self.tokens.get(self.pos).map(|t| t.token_type.clone()) {
+            Some(TokenType::ConstValue) => {
+                let t = self.next("constant")?;
+                Ok(Expr::Const(t.token, t.location))
+            }
+            Some(TokenType::StringLiteral) => {
+                let t = self.next("string")?;
+                Ok(Expr::Str(t.token, t.location))
+            }
+            _ => Err(self.error("expression")),
+        }
+    }
+}
+
+/// Parses the tokens from `keywords::count_tokens` into a syntax tree
+pub fn parse(tokens: Vec<Token>) -> Result<Program, Error> {
+    let mut parser = Parser { tokens, pos: 0 };
+    parser.program()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keywords::count_tokens;
+
+    fn parse_text(text: &str) -> Result<Program, Error> {
+        parse(count_tokens(text.to_owned()).unwrap())
+    }
+
+    fn body(text: &str) -> Vec<Stmt> {
+        match parse_text(text).unwrap().items.as_slice() {
+            [Item::Function(Function {
+                body: Some(body), ..
+            })] => body.clone(),
+            _ => panic!("expected one function definition"),
+        }
+    }
+
+    #[test]
+    fn call_arguments_are_bare_names() {
+        let body = body("int main() {\n  int y = 1;\n  f(y);\n  printf(\"%d\\n\", y);\n}\n");
+        let Stmt::Expr(Expr::Call { callee, args, .. }) = &body[1] else {
+            panic!("expected a call, got {:?}", body[1]);
+        };
+        assert_eq!(callee, "f");
+        assert!(matches!(args.as_slice(), [Expr::Ident(name, _)] if name == "y"));
+        let Stmt::Expr(Expr::Call { callee, args, .. }) = &body[2] else {
+            panic!("expected a call, got {:?}", body[2]);
+        };
+        assert_eq!(callee, "printf");
+        assert!(
+            matches!(args.as_slice(), [Expr::Str(format, _), Expr::Ident(name, _)]
+            if format == "%d\\n" && name == "y")
+        );
+    }
+
+    #[test]
+    fn returned_name_is_bare() {
+        let body = body("int f(int y) {\n  return y;\n}\n");
+        let [Stmt::Return(Some(Expr::Ident(name, location)))] = body.as_slice() else {
+            panic!("expected return y, got {:?}", body);
+        };
+        assert_eq!(name, "y");
+        assert_eq!((location.line, location.column), (1, 10));
+    }
+
+    #[test]
+    fn binary_operators_bind_by_precedence() {
+        let body = body("int main() {\n  x = a + b * c;\n}\n");
+        let Stmt::Expr(Expr::Assign { value, .. }) = &body[0] else {
+            panic!("expected an assignment, got {:?}", body[0]);
+        };
+        let Expr::Binary { op, right, .. } = value.as_ref() else {
+            panic!("expected a sum, got {:?}", value);
+        };
+        assert_eq!(op, "+");
+        assert!(matches!(right.as_ref(), Expr::Binary { op, .. } if op == "*"));
+    }
+
+    #[test]
+    fn reports_the_unexpected_token() {
+        let error = parse_text("int main() {\n  return y\n}\n").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Syntax error at 2:1: expected ';', found }"
+        );
+        let error = parse_text("int main() {\n").err().unwrap();
+        assert_eq!(
+            error.to_string(),
+            "Syntax error at 0:12: expected '}', found end of program"
+        );
+    }
+}
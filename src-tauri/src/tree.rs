@@ -0,0 +1,243 @@
+use crate::automaton_export::escape_dot;
+use crate::syntax::{Case, Declaration, Expr, Item, Program, Stmt};
+use serde::{Deserialize, Serialize};
+
+/// Syntax tree flattened to labels for display and export
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Node {
+    pub label: String,
+    pub children: Vec<Node>,
+}
+
+impl Node {
+    fn leaf(label: impl Into<String>) -> Node {
+        Node {
+            label: label.into(),
+            children: vec![],
+        }
+    }
+
+    fn new(label: impl Into<String>, children: Vec<Node>) -> Node {
+        Node {
+            label: label.into(),
+            children,
+        }
+    }
+
+    /// Wraps `child` so the role of a subtree (condition, body, ...) is visible
+    fn role(label: &str, child: Node) -> Node {
+        Node::new(label, vec![child])
+    }
+}
+
+pub fn from_program(program: &Program) -> Node {
+    Node::new(
+        "program",
+        program
+            .items
+            .iter()
+            .map(|item| match item {
+                Item::Function(f) => {
+                    let params = f
+                        .params
+                        .iter()
+                        .map(|p| Node::leaf(format!("{} {}", p.param_type, p.name)))
+                        .collect();
+                    let mut children = vec![Node::new("params", params)];
+                    if let Some(body) = &f.body {
+                        children.push(Node::new("body", body.iter().map(stmt).collect()));
+                    }
+                    Node::new(format!("function {} {}", f.return_type, f.name), children)
+                }
+                Item::Declaration(d) => declaration(d),
+            })
+            .collect(),
+    )
+}
+
+fn declaration(d: &Declaration) -> Node {
+    Node::new(
+        format!("declaration {}", d.var_type),
+        d.declarators
+            .iter()
+            .map(|x| {
+                let mut children = vec![];
+                if let Some(size) = &x.size {
+                    children.push(Node::role("size", expr(size)));
+                }
+                if let Some(init) = &x.init {
+                    children.push(Node::role("=", expr(init)));
+                }
+                Node::new(x.name.clone(), children)
+            })
+            .collect(),
+    )
+}
+
+fn case(c: &Case) -> Node {
+    let mut children = vec![];
+    let label = match &c.value {
+        Some(value) => {
+            children.push(Node::role("value", expr(value)));
+            "case"
+        }
+        None => "default",
+    };
+    children.extend(c.body.iter().map(stmt));
+    Node::new(label, children)
+}
+
+fn stmt(s: &Stmt) -> Node {
+    match s {
+        Stmt::Block(stmts) => Node::new("block", stmts.iter().map(stmt).collect()),
+        Stmt::Declaration(d) => declaration(d),
+        Stmt::Expr(e) => expr(e),
+        Stmt::If {
+            cond,
+            then,
+            otherwise,
+        } => {
+            let mut children = vec![Node::role("cond", expr(cond)), Node::role("then", stmt(then))];
+            if let Some(otherwise) = otherwise {
+                children.push(Node::role("else", stmt(otherwise)));
+            }
+            Node::new("if", children)
+        }
+        Stmt::While { cond, body } => Node::new(
+            "while",
+            vec![Node::role("cond", expr(cond)), Node::role("body", stmt(body))],
+        ),
+        Stmt::DoWhile { body, cond } => Node::new(
+            "do-while",
+            vec![Node::role("body", stmt(body)), Node::role("cond", expr(cond))],
+        ),
+        Stmt::For {
+            init,
+            cond,
+            step,
+            body,
+        } => {
+            let mut children = vec![];
+            if let Some(init) = init {
+                children.push(Node::role("init", stmt(init)));
+            }
+            if let Some(cond) = cond {
+                children.push(Node::role("cond", expr(cond)));
+            }
+            if let Some(step) = step {
+                children.push(Node::role("step", expr(step)));
+            }
+            children.push(Node::role("body", stmt(body)));
+            Node::new("for", children)
+        }
+        Stmt::Switch { value, cases } => {
+            let mut children = vec![Node::role("value", expr(value))];
+            children.extend(cases.iter().map(case));
+            Node::new("switch", children)
+        }
+        Stmt::Return(value) => Node::new("return", value.iter().map(expr).collect()),
+        Stmt::Break => Node::leaf("break"),
+        Stmt::Continue => Node::leaf("continue"),
+        Stmt::Goto(label) => Node::leaf(format!("goto {}", label)),
+        Stmt::Label(label) => Node::leaf(format!("label {}", label)),
+        Stmt::Empty => Node::leaf(";"),
+    }
+}
+
+fn expr(e: &Expr) -> Node {
+    match e {
+        Expr::Ident(name, _) => Node::leaf(name.clone()),
+        Expr::Const(value, _) => Node::leaf(value.clone()),
+        Expr::Str(value, _) => Node::leaf(format!("\"{}\"", value)),
+        Expr::Unary { op, operand } => Node::new(format!("unary {}", op), vec![expr(operand)]),
+        Expr::Postfix { op, operand } => Node::new(format!("postfix {}", op), vec![expr(operand)]),
+        Expr::Binary { op, left, right } => Node::new(op.clone(), vec![expr(left), expr(right)]),
+        Expr::Assign { op, target, value } => {
+            Node::new(op.clone(), vec![expr(target), expr(value)])
+        }
+        Expr::Call { callee, args, .. } => {
+            Node::new(format!("call {}", callee), args.iter().map(expr).collect())
+        }
+        Expr::Index { array, index } => Node::new("[]", vec![expr(array), expr(index)]),
+    }
+}
+
+/// Indented text, two spaces per level
+pub fn to_text(node: &Node) -> String {
+    fn walk(node: &Node, depth: usize, out: &mut String) {
+        out.push_str(&format!("{}{}\n", "  ".repeat(depth), node.label));
+        for child in &node.children {
+            walk(child, depth + 1, out);
+        }
+    }
+    let mut out = String::new();
+    walk(node, 0, &mut out);
+    out
+}
+
+/// Graphviz DOT digraph, nodes are numbered in preorder
+pub fn to_dot(node: &Node) -> String {
+    fn walk(node: &Node, next: &mut usize, out: &mut String) -> usize {
+        let id = *next;
+        *next += 1;
+        out.push_str(&format!(
+            "    n{} [label=\"{}\"];\n",
+            id,
+            escape_dot(&node.label)
+        ));
+        for child in &node.children {
+            let child_id = walk(child, next, out);
+            out.push_str(&format!("    n{} -> n{};\n", id, child_id));
+        }
+        id
+    }
+    let mut out = String::from("digraph ast {\n    node [shape=box];\n");
+    walk(node, &mut 0, &mut out);
+    out.push_str("}\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keywords::count_tokens;
+    use crate::syntax::parse;
+
+    fn tree(text: &str) -> Node {
+        from_program(&parse(count_tokens(text.to_owned()).unwrap()).unwrap())
+    }
+
+    #[test]
+    fn text_shows_roles_of_subtrees() {
+        let node = tree("int main() {\n  if (x < 2) y = x; else return 0;\n}\n");
+        assert_eq!(
+            to_text(&node),
+            "program\n\
+             \x20 function int main\n\
+             \x20   params\n\
+             \x20   body\n\
+             \x20     if\n\
+             \x20       cond\n\
+             \x20         <\n\
+             \x20           x\n\
+             \x20           2\n\
+             \x20       then\n\
+             \x20         =\n\
+             \x20           y\n\
+             \x20           x\n\
+             \x20       else\n\
+             \x20         return\n\
+             \x20           0\n"
+        );
+    }
+
+    #[test]
+    fn dot_numbers_nodes_in_preorder() {
+        let node = Node::new("a \"b\"", vec![Node::leaf("c"), Node::leaf("d")]);
+        assert_eq!(
+            to_dot(&node),
+            "digraph ast {\n    node [shape=box];\n    n0 [label=\"a \\\"b\\\"\"];\n    \
+             n1 [label=\"c\"];\n    n0 -> n1;\n    n2 [label=\"d\"];\n    n0 -> n2;\n}\n"
+        );
+    }
+}
@@ -0,0 +1,636 @@
+use crate::rpn::{Element, Rpn};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Steps taken by `run` before giving up on a program that doesn't halt
+pub const MAX_STEPS: usize = 1_000_000;
+
+/// Steps `run` and `resume` take before returning, so the caller can let go of the machine
+pub const SLICE: usize = 10_000;
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Place {
+    Var(usize),
+    Element(usize, i64),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Value {
+    Int(i64),
+    Float(f64),
+    Str(String),
+    /// Assignment target, dereferenced when used as an operand
+    Ref(Place),
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Value::Int(x) => write!(f, "{}", x),
+            Value::Float(x) => write!(f, "{}", x),
+            Value::Str(x) => write!(f, "\"{}\"", x),
+            Value::Ref(Place::Var(idx)) => write!(f, "&id{}", idx),
+            Value::Ref(Place::Element(idx, i)) => write!(f, "&id{}[{}]", idx, i),
+        }
+    }
+}
+
+impl Value {
+    fn truthy(&self) -> bool {
+        match self {
+            Value::Int(x) => *x != 0,
+            Value::Float(x) => *x != 0.0,
+            Value::Str(_) | Value::Ref(_) => true,
+        }
+    }
+
+    fn as_int(&self) -> Result<i64, Error> {
+        match self {
+            Value::Int(x) => Ok(*x),
+            Value::Float(x) => Ok(*x as i64),
+            _ => Err(Error::Type(self.to_string())),
+        }
+    }
+
+    fn as_float(&self) -> Result<f64, Error> {
+        match self {
+            Value::Int(x) => Ok(*x as f64),
+            Value::Float(x) => Ok(*x),
+            _ => Err(Error::Type(self.to_string())),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    StackUnderflow(usize),
+    Uninitialized(String),
+    Type(String),
+    DivisionByZero(usize),
+    UnknownFunction(String),
+    UnknownOperator(String),
+    StepLimit,
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::StackUnderflow(pc) => write!(f, "Runtime error: stack underflow at {}", pc),
+            Self::Uninitialized(name) => {
+                write!(f, "Runtime error: {} is used before assignment", name)
+            }
+            Self::Type(value) => write!(f, "Runtime error: {} is not a number", value),
+            Self::DivisionByZero(pc) => write!(f, "Runtime error: division by zero at {}", pc),
+            Self::UnknownFunction(name) => write!(f, "Runtime error: unknown function {}", name),
+            Self::UnknownOperator(op) => write!(f, "Runtime error: unsupported operator {}", op),
+            Self::StepLimit => write!(f, "Runtime error: stopped after {} steps", MAX_STEPS),
+        }
+    }
+}
+
+struct Frame {
+    function: usize,
+    vars: BTreeMap<Place, Value>,
+    /// Address to continue at after `ret`
+    return_to: usize,
+    /// Stack height when the frame was entered
+    base: usize,
+}
+
+/// What the UI shows after every step
+#[derive(Serialize, Deserialize, Clone)]
+pub struct State {
+    /// Address of the next element, `None` once the program halted
+    pub pc: Option<usize>,
+    pub listing: String,
+    pub stack: String,
+    pub variables: String,
+    pub console: String,
+    pub error: String,
+}
+
+/// Stack machine executing the postfix program. Global initialisers run first,
+/// then `main` is called
+pub struct Machine {
+    rpn: Rpn,
+    /// Identifier table, for display and error messages
+    names: Vec<String>,
+    pc: usize,
+    halted: bool,
+    stack: Vec<Value>,
+    globals: BTreeMap<Place, Value>,
+    frames: Vec<Frame>,
+    console: String,
+    error: String,
+    /// Steps taken since `run` was called, checked against `MAX_STEPS`
+    ran: usize,
+}
+
+impl Machine {
+    pub fn new(rpn: Rpn, names: Vec<String>) -> Machine {
+        Machine {
+            rpn,
+            names,
+            pc: 0,
+            halted: false,
+            stack: vec![],
+            globals: BTreeMap::new(),
+            frames: vec![],
+            console: String::new(),
+            error: String::new(),
+            ran: 0,
+        }
+    }
+
+    fn name(&self, idx: usize) -> String {
+        self.names
+            .get(idx)
+            .map_or(format!("id{}", idx), |x| x.clone())
+    }
+
+    fn pop(&mut self) -> Result<Value, Error> {
+        let base = self.frames.last().map_or(0, |f| f.base);
+        if self.stack.len() <= base {
+            return Err(Error::StackUnderflow(self.pc));
+        }
+        Ok(self.stack.pop().unwrap())
+    }
+
+    fn is_global(&self, place: &Place) -> bool {
+        let idx = match place {
+            Place::Var(idx) | Place::Element(idx, _) => idx,
+        };
+        self.frames.is_empty() || self.rpn.globals.contains(idx)
+    }
+
+    fn load(&self, place: &Place) -> Result<Value, Error> {
+        let vars = match self.frames.last() {
+            Some(frame) if !self.is_global(place) => &frame.vars,
+            _ => &self.globals,
+        };
+        match vars.get(place) {
+            Some(value) => Ok(value.clone()),
+            None => Err(Error::Uninitialized(match place {
+                Place::Var(idx) => self.name(*idx),
+                Place::Element(idx, i) => format!("{}[{}]", self.name(*idx), i),
+            })),
+        }
+    }
+
+    /// `value` as the declared type of the variable at `place` holds it
+    fn convert(&self, place: &Place, value: Value) -> Value {
+        let idx = match place {
+            Place::Var(idx) | Place::Element(idx, _) => idx,
+        };
+        match value {
+            Value::Int(x) if self.rpn.floats.contains(idx) => Value::Float(x as f64),
+            value => value,
+        }
+    }
+
+    fn store(&mut self, place: Place, value: Value) {
+        if self.is_global(&place) {
+            self.globals.insert(place, value);
+        } else {
+            self.frames.last_mut().unwrap().vars.insert(place, value);
+        }
+    }
+
+    /// Pops an operand, reading the variable if it's a reference
+    fn value(&mut self) -> Result<Value, Error> {
+        match self.pop()? {
+            Value::Ref(place) => self.load(&place),
+            value => Ok(value),
+        }
+    }
+
+    fn call(&mut self, function: usize, args: Vec<Value>) {
+        let f = &self.rpn.functions[function];
+        let mut vars = BTreeMap::new();
+        for (param, arg) in f.params.iter().zip(args) {
+            let place = Place::Var(*param);
+            vars.insert(place.clone(), self.convert(&place, arg));
+        }
+        self.frames.push(Frame {
+            function,
+            vars,
+            return_to: self.pc,
+            base: self.stack.len(),
+        });
+        self.pc = f.start;
+    }
+
+    fn binary(&self, op: &str, left: Value, right: Value) -> Result<Value, Error> {
+        let float = matches!(left, Value::Float(_)) || matches!(right, Value::Float(_));
+        let bool = |x: bool| Value::Int(x as i64);
+        if float {
+            let (a, b) = (left.as_float()?, right.as_float()?);
+            return Ok(match op {
+                "+" => Value::Float(a + b),
+                "-" => Value::Float(a - b),
+                "*" => Value::Float(a * b),
+                "/" => Value::Float(a / b),
+                "<" => bool(a < b),
+                ">" => bool(a > b),
+                "<=" => bool(a <= b),
+                ">=" => bool(a >= b),
+                "==" => bool(a == b),
+                "!=" => bool(a != b),
+                _ => return Err(Error::UnknownOperator(op.to_owned())),
+            });
+        }
+        let (a, b) = (left.as_int()?, right.as_int()?);
+        Ok(match op {
+            "+" => Value::Int(a.wrapping_add(b)),
+            "-" => Value::Int(a.wrapping_sub(b)),
+            "*" => Value::Int(a.wrapping_mul(b)),
+            "/" | "%" if b == 0 => return Err(Error::DivisionByZero(self.pc - 1)),
+            "/" => Value::Int(a.wrapping_div(b)),
+            "%" => Value::Int(a.wrapping_rem(b)),
+            "&" => Value::Int(a & b),
+            "|" => Value::Int(a | b),
+            "^" => Value::Int(a ^ b),
+            "<<" => Value::Int(a.wrapping_shl(b as u32)),
+            ">>" => Value::Int(a.wrapping_shr(b as u32)),
+            "<" => bool(a < b),
+            ">" => bool(a > b),
+            "<=" => bool(a <= b),
+            ">=" => bool(a >= b),
+            "==" => bool(a == b),
+            "!=" => bool(a != b),
+            _ => return Err(Error::UnknownOperator(op.to_owned())),
+        })
+    }
+
+    fn unary(&self, op: &str, value: Value) -> Result<Value, Error> {
+        Ok(match (op, value) {
+            ("u-", Value::Int(x)) => Value::Int(x.wrapping_neg()),
+            ("u-", Value::Float(x)) => Value::Float(-x),
+            ("u+", value @ (Value::Int(_) | Value::Float(_))) => value,
+            ("u+", value) => return Err(Error::Type(value.to_string())),
+            ("u!", value) => Value::Int(!value.truthy() as i64),
+            ("u~", value) => Value::Int(!value.as_int()?),
+            (op, _) => return Err(Error::UnknownOperator(op.to_owned())),
+        })
+    }
+
+    /// `printf`, `puts` and `putchar`; anything else has to be defined in the program
+    fn builtin(&mut self, name: &str, args: Vec<Value>) -> Result<Value, Error> {
+        let start = self.console.len();
+        match name {
+            "printf" => {
+                let mut args = args.into_iter();
+                let format = match args.next() {
+                    Some(Value::Str(format)) => format,
+                    Some(value) => value.to_string(),
+                    None => String::new(),
+                };
+                let mut chars = format.chars();
+                while let Some(c) = chars.next() {
+                    if c != '%' {
+                        self.console.push(c);
+                        continue;
+                    }
+                    let spec = chars.next();
+                    if spec == Some('%') {
+                        self.console.push('%');
+                        continue;
+                    }
+                    let arg = args.next().unwrap_or(Value::Int(0));
+                    let text = match (spec, arg) {
+                        (Some('d' | 'i'), arg) => arg.as_int()?.to_string(),
+                        (Some('f'), arg) => format!("{:.6}", arg.as_float()?),
+                        (Some('c'), arg) => {
+                            char::from_u32(arg.as_int()? as u32).map_or(String::new(), String::from)
+                        }
+                        (_, Value::Str(s)) => s,
+                        (_, arg) => arg.to_string(),
+                    };
+                    self.console.push_str(&text);
+                }
+            }
+            "puts" => {
+                for arg in args {
+                    match arg {
+                        Value::Str(s) => self.console.push_str(&s),
+                        arg => self.console.push_str(&arg.to_string()),
+                    }
+                }
+                self.console.push('\n');
+            }
+            "putchar" => {
+                let c = args.first().map_or(Ok(0), |x| x.as_int())?;
+                self.console.push(char::from_u32(c as u32).unwrap_or('?'));
+            }
+            _ => return Err(Error::UnknownFunction(name.to_owned())),
+        }
+        Ok(Value::Int((self.console.len() - start) as i64))
+    }
+
+    /// Executes one element
+    fn execute(&mut self) -> Result<(), Error> {
+        // Global initialisers are done, enter `main`
+        let init_end = self
+            .rpn
+            .functions
+            .first()
+            .map_or(self.rpn.code.len(), |f| f.start);
+        if self.frames.is_empty() && self.pc == init_end {
+            match self.rpn.functions.iter().position(|f| f.name == "main") {
+                Some(main) => self.call(main, vec![]),
+                None => self.halted = true,
+            }
+            return Ok(());
+        }
+        let element = self.rpn.code[self.pc].clone();
+        self.pc += 1;
+        match element {
+            Element::Ident(idx, _) => self.stack.push(Value::Ref(Place::Var(idx))),
+            Element::Const(_, value) => {
+                let parsed = if let Some(hex) = value.strip_prefix("0x") {
+                    i64::from_str_radix(hex, 16).ok().map(Value::Int)
+                } else {
+                    value.parse::<i64>().ok().map(Value::Int)
+                };
+                let parsed = parsed
+                    .or_else(|| value.parse::<f64>().ok().map(Value::Float))
+                    .unwrap_or(Value::Int(0));
+                self.stack.push(parsed);
+            }
+            Element::Str(_, value) => {
+                let value = value.strip_prefix('"').unwrap_or(&value);
+                let value = value.strip_suffix('"').unwrap_or(value);
+                self.stack
+                    .push(Value::Str(value.replace("\\n", "\n").replace("\\t", "\t")));
+            }
+            // Leaves the stored value, `a = b = 3` assigns it again
+            Element::Op(op) if op == "=" => {
+                let value = self.value()?;
+                let value = match self.pop()? {
+                    Value::Ref(place) => {
+                        let value = self.convert(&place, value);
+                        self.store(place, value.clone());
+                        value
+                    }
+                    target => return Err(Error::Type(target.to_string())),
+                };
+                self.stack.push(value);
+            }
+            Element::Op(op) if op.starts_with('u') => {
+                let value = self.value()?;
+                let result = self.unary(&op, value)?;
+                self.stack.push(result);
+            }
+            Element::Op(op) => {
+                let right = self.value()?;
+                let left = self.value()?;
+                let result = self.binary(&op, left, right)?;
+                self.stack.push(result);
+            }
+            Element::Index => {
+                let index = self.value()?.as_int()?;
+                match self.pop()? {
+                    Value::Ref(Place::Var(idx)) => {
+                        self.stack.push(Value::Ref(Place::Element(idx, index)))
+                    }
+                    array => return Err(Error::Type(array.to_string())),
+                }
+            }
+            Element::Call(name, argc) => {
+                let mut args = vec![];
+                for _ in 0..argc {
+                    args.push(self.value()?);
+                }
+                args.reverse();
+                match self.rpn.functions.iter().position(|f| f.name == name) {
+                    Some(function) => self.call(function, args),
+                    None => {
+                        let result = self.builtin(&name, args)?;
+                        self.stack.push(result);
+                    }
+                }
+            }
+            Element::Address(target) => self.stack.push(Value::Int(target as i64)),
+            Element::JumpFalse => {
+                let target = self.pop()?.as_int()? as usize;
+                if !self.value()?.truthy() {
+                    self.pc = target;
+                }
+            }
+            Element::Jump => self.pc = self.pop()?.as_int()? as usize,
+            Element::Return => {
+                let frame = self.frames.last().unwrap();
+                let result = if self.stack.len() > frame.base {
+                    self.value()?
+                } else {
+                    Value::Int(0)
+                };
+                let frame = self.frames.pop().unwrap();
+                self.stack.truncate(frame.base);
+                if self.frames.is_empty() {
+                    self.console.push_str(&format!("\n[exit {}]", result));
+                    self.halted = true;
+                } else {
+                    self.stack.push(result);
+                    self.pc = frame.return_to;
+                }
+            }
+            Element::Pop => {
+                self.pop()?;
+            }
+        }
+        Ok(())
+    }
+
+    pub fn step(&mut self) {
+        if self.halted {
+            return;
+        }
+        if let Err(e) = self.execute() {
+            self.error = e.to_string();
+            self.halted = true;
+        }
+    }
+
+    /// Runs until the program halts or the next element is a breakpoint.
+    /// The element under `pc` is always executed, so `run` moves past a breakpoint.
+    /// Returns `false` when `SLICE` steps were taken first, `resume` continues the run
+    pub fn run(&mut self, breakpoints: &[usize]) -> bool {
+        self.ran = 0;
+        self.resume(breakpoints)
+    }
+
+    pub fn resume(&mut self, breakpoints: &[usize]) -> bool {
+        for _ in 0..SLICE {
+            if self.halted {
+                return true;
+            }
+            if self.ran > 0 && breakpoints.contains(&self.pc) {
+                return true;
+            }
+            if self.ran == MAX_STEPS {
+                self.error = Error::StepLimit.to_string();
+                self.halted = true;
+                return true;
+            }
+            self.step();
+            self.ran += 1;
+        }
+        false
+    }
+
+    pub fn state(&self) -> State {
+        let pc = (!self.halted).then_some(self.pc);
+        let listing: String = self
+            .rpn
+            .code
+            .iter()
+            .enumerate()
+            .map(|(idx, element)| {
+                let marker = if pc == Some(idx) { ">" } else { " " };
+                let entry = match self.rpn.functions.iter().find(|f| f.start == idx) {
+                    Some(f) => format!("\n{}:", f.name),
+                    None => String::new(),
+                };
+                format!("{}\n{}{:<4}{}", entry, marker, idx, element)
+            })
+            .collect();
+        let stack: String = self
+            .stack
+            .iter()
+            .rev()
+            .map(|x| format!("\n{}", x))
+            .collect();
+        let show = |vars: &BTreeMap<Place, Value>| -> String {
+            vars.iter()
+                .map(|(place, value)| {
+                    let name = match place {
+                        Place::Var(idx) => format!("id{} {}", idx, self.name(*idx)),
+                        Place::Element(idx, i) => format!("id{} {}[{}]", idx, self.name(*idx), i),
+                    };
+                    format!("\n{} = {}", name, value)
+                })
+                .collect()
+        };
+        let mut variables = format!("\nглобальные{}", show(&self.globals));
+        if let Some(frame) = self.frames.last() {
+            variables.push_str(&format!(
+                "\n{}{}",
+                self.rpn.functions[frame.function].name,
+                show(&frame.vars)
+            ));
+        }
+        State {
+            pc,
+            listing,
+            stack,
+            variables,
+            console: self.console.clone(),
+            error: self.error.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::translate;
+
+    fn load(program: &str) -> Machine {
+        let (rpn, symbols) = translate(program.to_owned()).unwrap();
+        Machine::new(rpn, symbols.identifiers)
+    }
+
+    fn finish(machine: &mut Machine, breakpoints: &[usize]) {
+        let mut done = machine.run(breakpoints);
+        while !done {
+            done = machine.resume(breakpoints);
+        }
+    }
+
+    /// Runs the program to the end and returns its console
+    fn run(program: &str) -> String {
+        let mut machine = load(program);
+        finish(&mut machine, &[]);
+        let state = machine.state();
+        assert_eq!(state.error, "");
+        state.console
+    }
+
+    #[test]
+    fn recursion_returns_through_the_frames() {
+        let program = "int fact(int n) { if (n < 2) return 1; return n * fact(n - 1); }
+int main() { return fact(5); }";
+        assert_eq!(run(program), "\n[exit 120]");
+    }
+
+    #[test]
+    fn chained_assignment_stores_every_target() {
+        assert_eq!(
+            run("int main() { int a; int b; a = b = 3; return a + b; }"),
+            "\n[exit 6]"
+        );
+    }
+
+    #[test]
+    fn assignment_is_usable_as_a_condition() {
+        let program = "int main() { int c; int n = 0; if ((c = 7) != 0) n = c; return n; }";
+        assert_eq!(run(program), "\n[exit 7]");
+    }
+
+    #[test]
+    fn increments_yield_the_old_and_new_value() {
+        let program = "int main() { int x; int y = 1; x = y ++; return x * 10 + ++y; }";
+        assert_eq!(run(program), "\n[exit 13]");
+    }
+
+    #[test]
+    fn unary_plus_keeps_the_fraction() {
+        let program = "int main() { float f = 1.5; printf(\"%f\", +f); return 0; }";
+        assert_eq!(run(program), "1.500000\n[exit 0]");
+    }
+
+    #[test]
+    fn logical_operators_short_circuit() {
+        let program = "int main() { int p = 0; int n = 0; int r = p != 0 && 10 / p; \
+            if (p == 0 || n ++) n = n + 5; return r * 10 + n; }";
+        assert_eq!(run(program), "\n[exit 5]");
+        assert_eq!(
+            run("int main() { float f = 0.5; return (f && 2) + (0 || f); }"),
+            "\n[exit 2]"
+        );
+    }
+
+    #[test]
+    fn float_variables_convert_what_is_stored() {
+        let program = "float half(float x) { return x / 2; }
+int main() { float x = 1; float y; y = 3; printf(\"%f %f %f\", x / 2, half(1), y / 2); return 0; }";
+        assert_eq!(run(program), "0.500000 0.500000 1.500000\n[exit 0]");
+    }
+
+    #[test]
+    fn printf_formats_its_arguments() {
+        let program = "int main() { printf(\"%d-%c\\n\", 4, 65); return 0; }";
+        assert_eq!(run(program), "4-A\n\n[exit 0]");
+    }
+
+    #[test]
+    fn stops_an_endless_loop() {
+        let mut machine = load("int main() { while (1) ; }");
+        assert!(!machine.run(&[]));
+        finish(&mut machine, &[]);
+        assert_eq!(
+            machine.state().error,
+            "Runtime error: stopped after 1000000 steps"
+        );
+    }
+
+    #[test]
+    fn stops_at_a_breakpoint_and_moves_past_it() {
+        let mut machine = load("int main() { int x = 1; x = 2; return x; }");
+        finish(&mut machine, &[4]);
+        assert_eq!(machine.state().pc, Some(4));
+        finish(&mut machine, &[4]);
+        assert_eq!(machine.state().pc, None);
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{Clean, Session};
+use std::fs;
+use std::thread;
+use std::time::Duration;
+use tauri::{AppHandle, Manager};
+
+const INTERVAL: Duration = Duration::from_millis(500);
+
+/// Polls the opened file and emits `file-changed` with its contents
+/// whenever it is modified outside of the app. A change is reported once and
+/// stays unseen until the frontend calls `file_change_handled`
+pub fn spawn(app: AppHandle) {
+    thread::spawn(move || loop {
+        thread::sleep(INTERVAL);
+        let session = app.state::<Session>();
+        let Some(path) = session.current.lock().unwrap().clone() else {
+            continue;
+        };
+        let Ok(modified) = fs::metadata(&path).and_then(|m| m.modified()) else {
+            continue;
+        };
+        if *session.modified.lock().unwrap() == Some(modified) {
+            continue;
+        }
+        {
+            let mut reported = session.reported.lock().unwrap();
+            if *reported == Some(modified) {
+                continue;
+            }
+            *reported = Some(modified);
+        }
+        if let Ok(base) = fs::read_to_string(&path) {
+            let _ = app.emit_all("file-changed", Clean { base });
+        }
+    });
+}
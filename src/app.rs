@@ -1,10 +1,17 @@
-use crate::cleanup::{add_line_numbers, remove_comments};
-use crate::{descriptors, keywords};
+use crate::automaton::AutomatonView;
+use crate::grammar::GrammarView;
+use crate::lexgen::LexerView;
+use crate::project::ProjectView;
+use crate::regex::RegexView;
+use crate::simulator::SimulatorView;
+use crate::tree::{Node, TreeView};
+use crate::vm::VmView;
 use leptos::ev::Event;
 use leptos::leptos_dom::ev::SubmitEvent;
 use leptos::*;
 use serde::{Deserialize, Serialize};
 use serde_wasm_bindgen::{from_value, to_value};
+use std::time::Duration;
 use wasm_bindgen::prelude::*;
 
 #[derive(Serialize, Deserialize)]
@@ -12,28 +19,108 @@ pub struct Clean {
     base: String,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Table {
+    pub descriptors: String,
+    pub pseudocode: String,
+    pub identifiers: String,
+    pub keywords: String,
+    pub separators: String,
+    pub strings: String,
+    pub consts: String,
+    pub operators: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Analysis {
+    pub output: String,
+    pub table: Option<Table>,
+    pub tree: Option<Node>,
+    pub symbols: String,
+    pub rpn: String,
+    pub quads: String,
+    pub optimized: String,
+    pub diff: String,
+    pub blocks: String,
+    pub cfg: String,
+    pub error: String,
+}
+
 #[wasm_bindgen]
 extern "C" {
-    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "tauri"])]
-    async fn invoke(cmd: &str, args: JsValue) -> JsValue;
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "tauri"], catch)]
+    pub async fn invoke(cmd: &str, args: JsValue) -> Result<JsValue, JsValue>;
+
+    #[wasm_bindgen(js_namespace = ["window", "__TAURI__", "event"])]
+    async fn listen(event: &str, handler: &Closure<dyn FnMut(JsValue)>) -> JsValue;
+}
+
+#[derive(Serialize, Deserialize)]
+struct TauriEvent<T> {
+    payload: T,
+}
+#[derive(Serialize, Deserialize)]
+pub struct PathArgs<'a> {
+    pub path: &'a str,
+}
+#[derive(Serialize, Deserialize)]
+pub struct TextArgs<'a> {
+    pub text: &'a str,
+}
+#[derive(Serialize, Deserialize)]
+struct SaveArgs<'a> {
+    text: &'a str,
+    output: &'a str,
+}
+#[derive(Serialize, Deserialize)]
+struct ExportArgs<'a> {
+    text: &'a str,
+    name: &'a str,
 }
 #[derive(Serialize, Deserialize)]
-struct PathArgs<'a> {
-    path: &'a str,
+struct DirtyArgs {
+    dirty: bool,
 }
 #[derive(Serialize, Deserialize)]
 struct GreetArgs<'a> {
     name: &'a str,
 }
 
+/// Pause in typing after which the program is analysed again
+const ANALYZE_DELAY: Duration = Duration::from_millis(300);
+
+/// Writes `text` to a file picked in a dialog, `name` is the suggested file name
+pub fn export(text: String, name: &'static str, set_error: WriteSignal<String>) {
+    spawn_local(async move {
+        let args = to_value(&ExportArgs { text: &text, name }).unwrap();
+        if let Err(e) = invoke("export", args).await {
+            set_error.set(e.as_string().unwrap_or_default());
+        }
+    });
+}
+
 #[component]
 pub fn App() -> impl IntoView {
     let (name, set_name) = create_signal(String::new());
     let (file_input, set_file_input) = create_signal(String::new());
+    // Program as currently edited, `file_input` only changes on open
+    let (source, set_source) = create_signal(String::new());
+    let (dirty, set_dirty) = create_signal(false);
+    let (status, set_status) = create_signal(String::new());
+    // Contents of the opened file changed on disk while there were unsaved edits
+    let (changed, set_changed) = create_signal(None::<String>);
     let (file_output, set_file_output) = create_signal(String::new());
     let (descriptors, set_descriptors) = create_signal(String::new());
     let (pseudo, set_pseudo) = create_signal(String::new());
     let (error, set_error) = create_signal(String::new());
+    let (tree, set_tree) = create_signal(None::<Node>);
+    let (symbols, set_symbols) = create_signal(String::new());
+    let (rpn, set_rpn) = create_signal(String::new());
+    let (quads, set_quads) = create_signal(String::new());
+    let (optimized, set_optimized) = create_signal(String::new());
+    let (diff, set_diff) = create_signal(String::new());
+    let (blocks, set_blocks) = create_signal(String::new());
+    let (cfg, set_cfg) = create_signal(String::new());
 
     let (identifiers, set_identifiers) = create_signal(String::new());
     let (keywords, set_keywords) = create_signal(String::new());
@@ -47,32 +134,19 @@ pub fn App() -> impl IntoView {
         set_name.set(v);
     };
 
-    let update_filtered = move |ev: Event| {
-        let v = event_target_value(&ev).clone();
-        if !v.is_empty() {
-            let filtered = remove_comments(v);
-            let descr = keywords::count_tokens(filtered.clone());
-            set_file_output.set(add_line_numbers(filtered));
-            let table = match descr {
-                Ok(val) => descriptors::create_descriptors(val),
-                Err(e) => {
-                    set_error.set(e.to_string());
-                    set_descriptors.set("".to_owned());
-                    set_pseudo.set("".to_owned());
-
-                    set_identifiers.set("".to_owned());
-                    set_keywords.set("".to_owned());
-                    set_separators.set("".to_owned());
-                    set_strings.set("".to_owned());
-                    set_consts.set("".to_owned());
-                    set_operators.set("".to_owned());
-                    None
-                }
-            };
-
-            if table.is_some() {
-                let table = table.unwrap();
-                set_error.set("".to_owned());
+    let show_analysis = move |analysis: Analysis| {
+        set_file_output.set(analysis.output);
+        set_error.set(analysis.error);
+        set_tree.set(analysis.tree);
+        set_symbols.set("\n".to_owned() + &analysis.symbols);
+        set_rpn.set("\n".to_owned() + &analysis.rpn);
+        set_quads.set("\n".to_owned() + &analysis.quads);
+        set_optimized.set("\n".to_owned() + &analysis.optimized);
+        set_diff.set(analysis.diff);
+        set_blocks.set(analysis.blocks);
+        set_cfg.set(analysis.cfg);
+        match analysis.table {
+            Some(table) => {
                 set_descriptors.set("\n".to_owned() + &table.descriptors);
                 set_pseudo.set("\n".to_owned() + &table.pseudocode);
 
@@ -83,54 +157,146 @@ pub fn App() -> impl IntoView {
                 set_consts.set("\n".to_owned() + &table.consts);
                 set_operators.set("\n".to_owned() + &table.operators);
             }
+            None => {
+                set_descriptors.set("".to_owned());
+                set_pseudo.set("".to_owned());
+
+                set_identifiers.set("".to_owned());
+                set_keywords.set("".to_owned());
+                set_separators.set("".to_owned());
+                set_strings.set("".to_owned());
+                set_consts.set("".to_owned());
+                set_operators.set("".to_owned());
+            }
         }
     };
 
-    let greet = move |ev: SubmitEvent| {
-        ev.prevent_default();
+    // Bumped by every edit and analysis, responses to older ones are dropped
+    let generation = store_value(0u64);
+    let analyze = move |text: String| {
+        generation.update_value(|g| *g += 1);
+        let id = generation.get_value();
         spawn_local(async move {
-            let mut name = name.get_untracked();
-            if name.is_empty() {
-                name = "/".to_owned();
+            let args = to_value(&TextArgs { text: &text }).unwrap();
+            let result = invoke("analyze", args).await;
+            if generation.get_value() != id {
+                return;
+            }
+            match result {
+                Ok(analysis) => show_analysis(from_value(analysis).unwrap()),
+                Err(e) => set_error.set(e.as_string().unwrap_or_default()),
             }
+        });
+    };
 
-            let args = to_value(&PathArgs { path: &name }).unwrap();
-            let fs: JsValue = invoke("read", args).await;
-            let fs: Clean = from_value(fs).unwrap();
-            if !fs.base.is_empty() {
-                let filtered = remove_comments(fs.base.clone());
-                set_file_output.set(add_line_numbers(filtered.clone()));
-                let descr = keywords::count_tokens(filtered.clone());
-                let table = match descr {
-                    Ok(val) => descriptors::create_descriptors(val),
-                    Err(e) => {
-                        set_descriptors.set("".to_owned());
-                        set_pseudo.set("".to_owned());
-
-                        set_error.set(e.to_string());
-                        set_identifiers.set("".to_owned());
-                        set_keywords.set("".to_owned());
-                        set_separators.set("".to_owned());
-                        set_strings.set("".to_owned());
-                        set_consts.set("".to_owned());
-                        set_operators.set("".to_owned());
-                        None
+    let mark_dirty = move |value: bool| {
+        set_dirty.set(value);
+        spawn_local(async move {
+            let args = to_value(&DirtyArgs { dirty: value }).unwrap();
+            let _ = invoke("set_dirty", args).await;
+        });
+    };
+
+    let update_filtered = move |ev: Event| {
+        let v = event_target_value(&ev);
+        set_source.set(v.clone());
+        if !dirty.get_untracked() {
+            mark_dirty(true);
+        }
+        // Typing only reanalyses once it pauses
+        generation.update_value(|g| *g += 1);
+        let id = generation.get_value();
+        set_timeout(
+            move || {
+                if generation.get_value() == id && !v.is_empty() {
+                    analyze(v);
+                }
+            },
+            ANALYZE_DELAY,
+        );
+    };
+
+    // Tells the backend the change on disk was dealt with so it isn't reported again
+    let change_handled = move || {
+        set_changed.set(None);
+        spawn_local(async move {
+            let _ = invoke("file_change_handled", JsValue::UNDEFINED).await;
+        });
+    };
+
+    let reload = move |base: String| {
+        if dirty.get_untracked() {
+            mark_dirty(false);
+        }
+        analyze(base.clone());
+        set_source.set(base.clone());
+        set_file_input.set(base);
+        change_handled();
+    };
+
+    // The backend watches the opened file, reload it unless there are unsaved edits,
+    // then ask which version to keep
+    spawn_local(async move {
+        let handler = Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+            let event: TauriEvent<Clean> = from_value(event).unwrap();
+            if dirty.get_untracked() {
+                set_changed.set(Some(event.payload.base));
+            } else {
+                reload(event.payload.base);
+            }
+        });
+        listen("file-changed", &handler).await;
+        handler.forget();
+    });
+
+    // `output` is one of the backend `Output` variants: Source, Cleaned or Pseudocode
+    let save = move |cmd: &'static str, output: &'static str| {
+        spawn_local(async move {
+            let text = source.get_untracked();
+            let args = to_value(&SaveArgs {
+                text: &text,
+                output,
+            })
+            .unwrap();
+            match invoke(cmd, args).await {
+                Ok(path) => {
+                    let path: Option<String> = from_value(path).unwrap();
+                    if let Some(path) = path {
+                        if output == "Source" {
+                            set_dirty.set(false);
+                            set_changed.set(None);
+                        }
+                        set_status.set(format!("Saved {}", path));
                     }
-                };
-                if table.is_some() {
-                    let table = table.unwrap();
-                    set_error.set("".to_owned());
-                    set_descriptors.set("\n".to_owned() + &table.descriptors);
-                    set_pseudo.set("\n".to_owned() + &table.pseudocode);
-
-                    set_identifiers.set("\n".to_owned() + &table.identifiers);
-                    set_keywords.set("\n".to_owned() + &table.keywords);
-                    set_separators.set("\n".to_owned() + &table.separators);
-                    set_strings.set("\n".to_owned() + &table.strings);
-                    set_consts.set("\n".to_owned() + &table.consts);
-                    set_operators.set("\n".to_owned() + &table.operators);
                 }
+                Err(e) => set_error.set(e.as_string().unwrap_or_default()),
             }
+        });
+    };
+
+    let greet = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        spawn_local(async move {
+            let name = name.get_untracked();
+            let args = to_value(&PathArgs { path: name.trim() }).unwrap();
+            let fs: Option<Clean> = match invoke("read", args).await {
+                Ok(fs) => from_value(fs).unwrap(),
+                Err(e) => {
+                    set_error.set(e.as_string().unwrap_or_default());
+                    return;
+                }
+            };
+            // Dialog was cancelled, keep the current program
+            let Some(fs) = fs else {
+                return;
+            };
+            if !fs.base.is_empty() {
+                analyze(fs.base.clone());
+            }
+            set_dirty.set(false);
+            set_changed.set(None);
+            set_status.set(String::new());
+            set_source.set(fs.base.clone());
             set_file_input.set(fs.base);
         });
     };
@@ -140,11 +306,38 @@ pub fn App() -> impl IntoView {
             <form class="row top" on:submit=greet>
                 <input
                     id="greet-input"
-                    placeholder="Enter a file directory..."
+                    placeholder="Enter a file path or leave empty to browse..."
                     on:input=update_name
                 />
                 <button type="submit">"Open"</button>
+                <button type="button" on:click=move |_| save("save", "Source")>"Save"</button>
+                <button type="button" on:click=move |_| save("save_as", "Source")>"Save as"</button>
+                <button type="button" on:click=move |_| save("save_as", "Cleaned")>"Save cleaned"</button>
+                <button type="button" on:click=move |_| save("save_as", "Pseudocode")>"Save pseudocode"</button>
+                <button type="button" on:click=move |_| save("save_as", "TreeDot")>"Save tree (DOT)"</button>
+                <button type="button" on:click=move |_| save("save_as", "TreeText")>"Save tree (text)"</button>
+                <button type="button" on:click=move |_| save("save_as", "Rpn")>"Save RPN"</button>
+                <button type="button" on:click=move |_| save("save_as", "Quads")>"Save quadruples"</button>
+                <button type="button" on:click=move |_| save("save_as", "Optimized")>"Save optimized"</button>
+                <button type="button" on:click=move |_| save("save_as", "CfgDot")>"Save CFG (DOT)"</button>
+                <button type="button" on:click=move |_| save("save_as", "CfgSvg")>"Save CFG (SVG)"</button>
+                <span>{move || if dirty.get() { "*" } else { "" }}{status}</span>
             </form>
+            <Show when=move || changed.with(Option::is_some) fallback=|| ()>
+                <div class="row top">
+                    <span>"File changed on disk"</span>
+                    <button
+                        type="button"
+                        on:click=move |_| {
+                            if let Some(base) = changed.get_untracked() {
+                                reload(base);
+                            }
+                        }>
+                        "Reload"
+                    </button>
+                    <button type="button" on:click=move |_| change_handled()>"Keep my edits"</button>
+                </div>
+            </Show>
             <div class="row main">
                 <textarea
                     class="display_text"
@@ -196,6 +389,45 @@ pub fn App() -> impl IntoView {
                     <code>60 - разделители{separators}</code>
                 </div>
             </div>
+            <div class="row main">
+                <TreeView tree=tree/>
+                <div class="display_text"
+                    prop:value=move || symbols.get()>
+                    <code>области видимости{symbols}</code>
+                </div>
+                <div class="display_text"
+                    prop:value=move || rpn.get()>
+                    <code>ПОЛИЗ{rpn}</code>
+                </div>
+            </div>
+            <div class="row main">
+                <div class="display_text"
+                    prop:value=move || quads.get()>
+                    <code>тетрады{quads}</code>
+                </div>
+                <div class="display_text"
+                    prop:value=move || optimized.get()>
+                    <code>после оптимизации{optimized}</code>
+                </div>
+                <div class="display_text"
+                    prop:value=move || diff.get()>
+                    <code>изменения{diff}</code>
+                </div>
+                <div class="display_text"
+                    prop:value=move || blocks.get()>
+                    <code>базовые блоки{blocks}</code>
+                </div>
+            </div>
+            <div class="row main">
+                <div class="display_text cfg" inner_html=move || cfg.get()></div>
+            </div>
+            <VmView source=source/>
+            <GrammarView source=source/>
+            <AutomatonView/>
+            <RegexView/>
+            <LexerView source=source/>
+            <SimulatorView/>
+            <ProjectView/>
         </main>
     }
 }
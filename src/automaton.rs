@@ -0,0 +1,254 @@
+use crate::app::{invoke, PathArgs, TextArgs};
+use crate::minimize::{MinimizePanel, MinimizeReport};
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::{from_value, to_value};
+use wasm_bindgen::JsValue;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AutomatonReport {
+    pub states: String,
+    pub transitions: String,
+    pub issues: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ConsistencyReport {
+    pub differences: String,
+    pub summary: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DiffReport {
+    pub states: String,
+    pub transitions: String,
+    pub accepting: String,
+    pub equivalence: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ExportArgs<'a> {
+    text: &'a str,
+    format: &'a str,
+}
+
+#[derive(Serialize, Deserialize)]
+struct DiffArgs<'a> {
+    old: &'a str,
+    new: &'a str,
+}
+
+/// Editor for `State->class->State` transition lists with their validation
+#[component]
+pub fn AutomatonView() -> impl IntoView {
+    let (text, set_text) = create_signal(String::new());
+    let (report, set_report) = create_signal(None::<AutomatonReport>);
+    let (consistency, set_consistency) = create_signal(None::<ConsistencyReport>);
+    // Another revision of the description to diff against
+    let (other, set_other) = create_signal(String::new());
+    let (diff, set_diff) = create_signal(None::<DiffReport>);
+    let (minimal, set_minimal) = create_signal(None::<MinimizeReport>);
+    let (error, set_error) = create_signal(String::new());
+
+    let check = move || {
+        spawn_local(async move {
+            let text = text.get_untracked();
+            let args = to_value(&TextArgs { text: &text }).unwrap();
+            match invoke("check_automaton", args).await {
+                Ok(value) => {
+                    let value: AutomatonReport = from_value(value).unwrap();
+                    set_error.set(value.error.clone());
+                    set_report.set(Some(value));
+                }
+                Err(e) => set_error.set(e.as_string().unwrap_or_default()),
+            }
+        });
+    };
+
+    let compare = move |_| {
+        spawn_local(async move {
+            let text = text.get_untracked();
+            let args = to_value(&TextArgs { text: &text }).unwrap();
+            match invoke("check_lexer", args).await {
+                Ok(value) => {
+                    let value: ConsistencyReport = from_value(value).unwrap();
+                    set_error.set(value.error.clone());
+                    set_consistency.set(Some(value));
+                }
+                Err(e) => set_error.set(e.as_string().unwrap_or_default()),
+            }
+        });
+    };
+
+    let run_diff = move |_| {
+        spawn_local(async move {
+            let (old, new) = (other.get_untracked(), text.get_untracked());
+            let args = to_value(&DiffArgs {
+                old: &old,
+                new: &new,
+            })
+            .unwrap();
+            match invoke("diff_automata", args).await {
+                Ok(value) => {
+                    let value: DiffReport = from_value(value).unwrap();
+                    set_error.set(value.error.clone());
+                    set_diff.set(Some(value));
+                }
+                Err(e) => set_error.set(e.as_string().unwrap_or_default()),
+            }
+        });
+    };
+
+    let minimize = move |_| {
+        spawn_local(async move {
+            let text = text.get_untracked();
+            let args = to_value(&TextArgs { text: &text }).unwrap();
+            match invoke("minimize_automaton", args).await {
+                Ok(value) => {
+                    let value: MinimizeReport = from_value(value).unwrap();
+                    set_error.set(value.error.clone());
+                    set_minimal.set(Some(value));
+                }
+                Err(e) => set_error.set(e.as_string().unwrap_or_default()),
+            }
+        });
+    };
+
+    let import = move |_| {
+        spawn_local(async move {
+            let args = to_value(&PathArgs { path: "" }).unwrap();
+            match invoke("import_drawio", args).await {
+                Ok(value) => {
+                    if let Some(imported) = from_value::<Option<String>>(value).unwrap() {
+                        set_text.set(imported);
+                        check();
+                    }
+                }
+                Err(e) => set_error.set(e.as_string().unwrap_or_default()),
+            }
+        });
+    };
+
+    let from_lexer = move |_| {
+        spawn_local(async move {
+            match invoke("lexer_automaton", JsValue::UNDEFINED).await {
+                Ok(value) => {
+                    set_text.set(from_value(value).unwrap());
+                    check();
+                }
+                Err(e) => set_error.set(e.as_string().unwrap_or_default()),
+            }
+        });
+    };
+
+    // `format` is one of the backend `Format` variants: Drawio, Dot or Mermaid
+    let export = move |format: &'static str| {
+        spawn_local(async move {
+            let text = text.get_untracked();
+            let args = to_value(&ExportArgs {
+                text: &text,
+                format,
+            })
+            .unwrap();
+            if let Err(e) = invoke("export_automaton", args).await {
+                set_error.set(e.as_string().unwrap_or_default());
+            }
+        });
+    };
+
+    // `main` is the edited description, otherwise the one it is diffed against
+    let open = move |main: bool| {
+        spawn_local(async move {
+            let args = to_value(&PathArgs { path: "" }).unwrap();
+            match invoke("read_automaton", args).await {
+                Ok(value) => {
+                    if let Some(opened) = from_value::<Option<String>>(value).unwrap() {
+                        if main {
+                            set_text.set(opened);
+                            check();
+                        } else {
+                            set_other.set(opened);
+                        }
+                    }
+                }
+                Err(e) => set_error.set(e.as_string().unwrap_or_default()),
+            }
+        });
+    };
+
+    let field = move |field: fn(&AutomatonReport) -> &String| {
+        move || report.with(|r| r.as_ref().map(|r| field(r).clone()))
+    };
+
+    let consistency_field = move |field: fn(&ConsistencyReport) -> &String| {
+        move || consistency.with(|r| r.as_ref().map(|r| field(r).clone()))
+    };
+
+    let diff_field = move |field: fn(&DiffReport) -> &String| {
+        move || diff.with(|r| r.as_ref().map(|r| field(r).clone()))
+    };
+
+    view! {
+        <div class="row top">
+            <button type="button" on:click=move |_| open(true)>"Open automaton"</button>
+            <button type="button" on:click=import>"Import draw.io"</button>
+            <button type="button" on:click=from_lexer>"From lexer"</button>
+            <button type="button" on:click=move |_| check()>"Check"</button>
+            <button type="button" on:click=move |_| export("Drawio")>"Export draw.io"</button>
+            <button type="button" on:click=move |_| export("Dot")>"Export DOT"</button>
+            <button type="button" on:click=move |_| export("Mermaid")>"Export Mermaid"</button>
+            <button type="button" on:click=compare>"Compare with lexer"</button>
+            <button type="button" on:click=move |_| open(false)>"Open old version"</button>
+            <button type="button" on:click=run_diff>"Diff"</button>
+            <button type="button" on:click=minimize>"Minimise"</button>
+            <span>"автомат "{error}</span>
+        </div>
+        <div class="row main">
+            <textarea
+                class="display_text"
+                placeholder="Whitespace->a-zA-Z->Identifier"
+                prop:value=move || text.get()
+                on:input=move |ev| set_text.set(event_target_value(&ev))>
+            </textarea>
+            <div class="display_text">
+                <code>состояния{field(|r| &r.states)}</code>
+            </div>
+            <div class="display_text">
+                <code>переходы{field(|r| &r.transitions)}</code>
+            </div>
+            <div class="display_text" style="width: 40%">
+                <code>замечания{field(|r| &r.issues)}</code>
+            </div>
+        </div>
+        <div class="row main">
+            <textarea
+                class="display_text"
+                placeholder="old version"
+                prop:value=move || other.get()
+                on:input=move |ev| set_other.set(event_target_value(&ev))>
+            </textarea>
+            <div class="display_text">
+                <code>
+                    "состояния"{diff_field(|r| &r.states)}"\n"
+                    "допускающие"{diff_field(|r| &r.accepting)}"\n"
+                    "эквивалентность"{diff_field(|r| &r.equivalence)}
+                </code>
+            </div>
+            <div class="display_text" style="width: 60%">
+                <code>изменённые переходы{diff_field(|r| &r.transitions)}</code>
+            </div>
+        </div>
+        <div class="row main">
+            <div class="display_text" style="width: 100%">
+                <code>
+                    "сверка с лексером"{consistency_field(|r| &r.summary)}"\n"
+                    {consistency_field(|r| &r.differences)}
+                </code>
+            </div>
+        </div>
+        <MinimizePanel report=minimal set_error=set_error/>
+    }
+}
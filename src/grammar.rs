@@ -0,0 +1,171 @@
+use crate::app::{export, invoke};
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::{from_value, to_value};
+
+/// Statements of the form `id = expr ;`, LL(1) as written
+const DEFAULT_GRAMMAR: &str = "Program -> { Stmt }
+Stmt -> Identifier '=' Expr ';'
+Expr -> Term { ( '+' | '-' ) Term }
+Term -> Factor { ( '*' | '/' ) Factor }
+Factor -> Identifier | ConstValue | '(' Expr ')'
+";
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Ll1Report {
+    pub productions: String,
+    pub first: String,
+    pub follow: String,
+    pub table: String,
+    pub conflicts: String,
+    pub trace: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LrReport {
+    pub productions: String,
+    pub states: String,
+    pub table: String,
+    pub conflicts: String,
+    pub trace: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct GrammarArgs<'a> {
+    grammar: &'a str,
+    text: &'a str,
+}
+#[derive(Serialize, Deserialize)]
+struct LrArgs<'a> {
+    grammar: &'a str,
+    method: &'a str,
+    text: &'a str,
+}
+
+/// Grammar editor with LL(1) and LR tables and parse traces
+#[component]
+pub fn GrammarView(source: ReadSignal<String>) -> impl IntoView {
+    let (grammar, set_grammar) = create_signal(DEFAULT_GRAMMAR.to_owned());
+    let (ll1, set_ll1) = create_signal(None::<Ll1Report>);
+    let (lr, set_lr) = create_signal(None::<LrReport>);
+    let (error, set_error) = create_signal(String::new());
+
+    let run_ll1 = move |_| {
+        spawn_local(async move {
+            let grammar = grammar.get_untracked();
+            let text = source.get_untracked();
+            let args = to_value(&GrammarArgs {
+                grammar: &grammar,
+                text: &text,
+            })
+            .unwrap();
+            match invoke("ll1", args).await {
+                Ok(report) => {
+                    let report: Ll1Report = from_value(report).unwrap();
+                    set_error.set(report.error.clone());
+                    set_ll1.set(Some(report));
+                }
+                Err(e) => {
+                    set_ll1.set(None);
+                    set_error.set(e.as_string().unwrap_or_default());
+                }
+            }
+        });
+    };
+
+    // `method` is one of the backend `Method` variants: Lr0, Slr1 or Lalr1
+    let run_lr = move |method: &'static str| {
+        spawn_local(async move {
+            let grammar = grammar.get_untracked();
+            let text = source.get_untracked();
+            let args = to_value(&LrArgs {
+                grammar: &grammar,
+                method,
+                text: &text,
+            })
+            .unwrap();
+            match invoke("lr", args).await {
+                Ok(report) => {
+                    let report: LrReport = from_value(report).unwrap();
+                    set_error.set(report.error.clone());
+                    set_lr.set(Some(report));
+                }
+                Err(e) => {
+                    set_lr.set(None);
+                    set_error.set(e.as_string().unwrap_or_default());
+                }
+            }
+        });
+    };
+
+    let ll1_field = move |field: fn(&Ll1Report) -> &String| {
+        move || ll1.with(|r| r.as_ref().map(|r| field(r).clone()))
+    };
+    let lr_field = move |field: fn(&LrReport) -> &String| {
+        move || lr.with(|r| r.as_ref().map(|r| field(r).clone()))
+    };
+    let export_lr = move |field: fn(&LrReport) -> &String, name: &'static str| {
+        if let Some(text) = lr.with_untracked(|r| r.as_ref().map(|r| field(r).clone())) {
+            export(text, name, set_error);
+        }
+    };
+
+    view! {
+        <div class="row top">
+            <button type="button" on:click=run_ll1>"LL(1)"</button>
+            <button type="button" on:click=move |_| run_lr("Lr0")>"LR(0)"</button>
+            <button type="button" on:click=move |_| run_lr("Slr1")>"SLR(1)"</button>
+            <button type="button" on:click=move |_| run_lr("Lalr1")>"LALR(1)"</button>
+            <button type="button" on:click=move |_| export_lr(|r| &r.table, "lr_table.txt")>
+                "Export LR table"
+            </button>
+            <button type="button" on:click=move |_| export_lr(|r| &r.trace, "lr_trace.txt")>
+                "Export LR trace"
+            </button>
+            <span>{error}</span>
+        </div>
+        <div class="row main">
+            <textarea
+                class="display_text"
+                prop:value=DEFAULT_GRAMMAR
+                on:input=move |ev| set_grammar.set(event_target_value(&ev))>
+            </textarea>
+            <div class="display_text">
+                <code>правила{ll1_field(|r| &r.productions)}</code>
+            </div>
+            <div class="display_text">
+                <code>FIRST{ll1_field(|r| &r.first)}</code>
+            </div>
+            <div class="display_text">
+                <code>FOLLOW{ll1_field(|r| &r.follow)}</code>
+            </div>
+        </div>
+        <div class="row main">
+            <div class="display_text" style="width: 40%">
+                <code>"таблица LL(1)\n"{ll1_field(|r| &r.table)}</code>
+            </div>
+            <div class="display_text">
+                <code>конфликты{ll1_field(|r| &r.conflicts)}</code>
+            </div>
+            <div class="display_text" style="width: 40%">
+                <code>"разбор\n"{ll1_field(|r| &r.trace)}</code>
+            </div>
+        </div>
+        <div class="row main">
+            <div class="display_text">
+                <code>состояния LR{lr_field(|r| &r.states)}</code>
+            </div>
+            <div class="display_text" style="width: 40%">
+                <code>"ACTION / GOTO\n"{lr_field(|r| &r.table)}</code>
+            </div>
+            <div class="display_text">
+                <code>конфликты LR{lr_field(|r| &r.conflicts)}</code>
+            </div>
+            <div class="display_text" style="width: 40%">
+                <code>"разбор LR\n"{lr_field(|r| &r.trace)}</code>
+            </div>
+        </div>
+    }
+}
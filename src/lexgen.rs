@@ -0,0 +1,118 @@
+use crate::app::{export, invoke, PathArgs};
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::{from_value, to_value};
+
+/// The language `keywords.rs` scans, as rules
+const DEFAULT_SPEC: &str = include_str!("../lexer_spec.txt");
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LexgenReport {
+    pub rules: String,
+    pub automaton: String,
+    pub tokens: String,
+    pub comparison: String,
+    pub descriptors: String,
+    pub rust: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LexgenArgs<'a> {
+    spec: &'a str,
+    program: &'a str,
+}
+
+/// Lexer spec editor: builds the scanner and runs it on the program
+#[component]
+pub fn LexerView(source: ReadSignal<String>) -> impl IntoView {
+    let (spec, set_spec) = create_signal(DEFAULT_SPEC.to_owned());
+    let (report, set_report) = create_signal(None::<LexgenReport>);
+    let (error, set_error) = create_signal(String::new());
+
+    let build = move |_| {
+        spawn_local(async move {
+            let (spec, program) = (spec.get_untracked(), source.get_untracked());
+            let args = to_value(&LexgenArgs {
+                spec: &spec,
+                program: &program,
+            })
+            .unwrap();
+            match invoke("generate_lexer", args).await {
+                Ok(value) => {
+                    let value: LexgenReport = from_value(value).unwrap();
+                    set_error.set(value.error.clone());
+                    set_report.set(Some(value));
+                }
+                Err(e) => set_error.set(e.as_string().unwrap_or_default()),
+            }
+        });
+    };
+
+    let open = move |_| {
+        spawn_local(async move {
+            let args = to_value(&PathArgs { path: "" }).unwrap();
+            match invoke("read_automaton", args).await {
+                Ok(value) => {
+                    if let Some(opened) = from_value::<Option<String>>(value).unwrap() {
+                        set_spec.set(opened);
+                    }
+                }
+                Err(e) => set_error.set(e.as_string().unwrap_or_default()),
+            }
+        });
+    };
+
+    let field = move |field: fn(&LexgenReport) -> &String| {
+        move || report.with(|r| r.as_ref().map(|r| field(r).clone()))
+    };
+    let save = move |field: fn(&LexgenReport) -> &String, name: &'static str| {
+        if let Some(text) = report.with_untracked(|r| r.as_ref().map(|r| field(r).clone())) {
+            export(text, name, set_error);
+        }
+    };
+
+    view! {
+        <div class="row top">
+            <button type="button" on:click=open>"Open lexer spec"</button>
+            <button type="button" on:click=move |_| export(spec.get_untracked(), "lexer_spec.txt", set_error)>
+                "Save lexer spec"
+            </button>
+            <button type="button" on:click=build>"Build lexer"</button>
+            <button type="button" on:click=move |_| save(|r| &r.rust, "lexer.rs")>
+                "Generate Rust scanner"
+            </button>
+            <button type="button" on:click=move |_| save(|r| &r.tokens, "tokens.txt")>
+                "Export tokens"
+            </button>
+            <span>"генератор лексера "{error}</span>
+        </div>
+        <div class="row main">
+            <textarea
+                class="display_text"
+                style="width: 50%"
+                prop:value=move || spec.get()
+                on:input=move |ev| set_spec.set(event_target_value(&ev))>
+            </textarea>
+            <div class="display_text" style="width: 50%">
+                <code>правила{field(|r| &r.rules)}</code>
+            </div>
+        </div>
+        <div class="row main">
+            <div class="display_text" style="width: 100%">
+                <code>автомат{field(|r| &r.automaton)}</code>
+            </div>
+        </div>
+        <div class="row main">
+            <div class="display_text">
+                <code>лексемы{field(|r| &r.tokens)}</code>
+            </div>
+            <div class="display_text">
+                <code>сверка с keywords.rs{field(|r| &r.comparison)}</code>
+            </div>
+            <div class="display_text">
+                <code>дескрипторы{field(|r| &r.descriptors)}</code>
+            </div>
+        </div>
+    }
+}
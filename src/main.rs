@@ -1,7 +1,13 @@
 mod app;
-pub mod cleanup;
-mod keywords;
-mod descriptors;
+mod automaton;
+mod grammar;
+mod lexgen;
+mod minimize;
+mod project;
+mod regex;
+mod simulator;
+mod tree;
+mod vm;
 
 use app::*;
 use leptos::*;
@@ -0,0 +1,60 @@
+use crate::app::export;
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MinimizeReport {
+    pub merged: String,
+    pub hopcroft: String,
+    pub table_filling: String,
+    pub table: String,
+    pub text: String,
+    pub rust: String,
+    pub error: String,
+}
+
+/// Result of a minimisation, shared by the automaton and regex views
+#[component]
+pub fn MinimizePanel(
+    report: ReadSignal<Option<MinimizeReport>>,
+    set_error: WriteSignal<String>,
+) -> impl IntoView {
+    let field = move |field: fn(&MinimizeReport) -> &String| {
+        move || report.with(|r| r.as_ref().map(|r| field(r).clone()))
+    };
+    let save = move |field: fn(&MinimizeReport) -> &String, name: &'static str| {
+        if let Some(text) = report.with_untracked(|r| r.as_ref().map(|r| field(r).clone())) {
+            export(text, name, set_error);
+        }
+    };
+
+    view! {
+        <div class="row top">
+            <button type="button" on:click=move |_| save(|r| &r.table, "min_dfa_table.txt")>
+                "Export minimal DFA table"
+            </button>
+            <button type="button" on:click=move |_| save(|r| &r.text, "min_dfa.txt")>
+                "Export minimal DFA transitions"
+            </button>
+            <button type="button" on:click=move |_| save(|r| &r.rust, "lexer.rs")>
+                "Generate lexer"
+            </button>
+        </div>
+        <div class="row main">
+            <div class="display_text" style="width: 30%">
+                <code>объединённые состояния{field(|r| &r.merged)}</code>
+            </div>
+            <div class="display_text" style="width: 40%">
+                <code>алгоритм Хопкрофта{field(|r| &r.hopcroft)}</code>
+            </div>
+            <div class="display_text" style="width: 30%">
+                <code>заполнение таблицы{field(|r| &r.table_filling)}</code>
+            </div>
+        </div>
+        <div class="row main">
+            <div class="display_text" style="width: 100%">
+                <code>минимальный ДКА{field(|r| &r.table)}</code>
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,214 @@
+use crate::app::{invoke, Analysis, PathArgs};
+use leptos::leptos_dom::ev::SubmitEvent;
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::{from_value, to_value};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ProjectFile {
+    pub path: String,
+    pub analysis: Analysis,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Usage {
+    pub file: usize,
+    pub line: usize,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Entry {
+    pub name: String,
+    pub usages: Vec<Usage>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Project {
+    pub root: String,
+    pub files: Vec<ProjectFile>,
+    pub identifiers: Vec<Entry>,
+    pub consts: Vec<Entry>,
+}
+
+/// Directory mode: file tree, per-file tables and project-wide identifier tables
+#[component]
+pub fn ProjectView() -> impl IntoView {
+    let (path, set_path) = create_signal(String::new());
+    let (project, set_project) = create_signal(None::<Project>);
+    let (error, set_error) = create_signal(String::new());
+    let (file, set_file) = create_signal(0usize);
+    let (line, set_line) = create_signal(None::<usize>);
+    // Index into `Project::identifiers` whose usages are listed
+    let (identifier, set_identifier) = create_signal(None::<usize>);
+
+    let open = move |ev: SubmitEvent| {
+        ev.prevent_default();
+        spawn_local(async move {
+            let path = path.get_untracked();
+            let args = to_value(&PathArgs { path: path.trim() }).unwrap();
+            match invoke("open_project", args).await {
+                Ok(value) => {
+                    let opened: Option<Project> = from_value(value).unwrap();
+                    if opened.is_some() {
+                        set_error.set(String::new());
+                        set_file.set(0);
+                        set_line.set(None);
+                        set_identifier.set(None);
+                        set_project.set(opened);
+                    }
+                }
+                Err(e) => set_error.set(e.as_string().unwrap_or_default()),
+            }
+        });
+    };
+
+    let tree = move || {
+        project.with(|project| {
+            project.as_ref().map(|project| {
+                project
+                    .files
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, f)| {
+                        let depth = f.path.matches(['/', '\\']).count();
+                        let name = f.path.rsplit(['/', '\\']).next().unwrap_or("").to_owned();
+                        view! {
+                            <div
+                                class:selected=move || file.get() == idx
+                                style=format!("padding-left: {}em; cursor: pointer", depth)
+                                title=f.path.clone()
+                                on:click=move |_| {
+                                    set_file.set(idx);
+                                    set_line.set(None);
+                                }>
+                                {name}
+                            </div>
+                        }
+                    })
+                    .collect_view()
+            })
+        })
+    };
+
+    let listing = move || {
+        project.with(|project| {
+            project.as_ref().and_then(|p| p.files.get(file.get())).map(|f| {
+                f.analysis
+                    .output
+                    .lines()
+                    .enumerate()
+                    .map(|(idx, l)| {
+                        let l = l.to_owned();
+                        view! { <div class:selected=move || line.get() == Some(idx)>{l}</div> }
+                    })
+                    .collect_view()
+            })
+        })
+    };
+
+    let file_tables = move || {
+        project.with(|project| {
+            project.as_ref().and_then(|p| p.files.get(file.get())).map(|f| {
+                match &f.analysis.table {
+                    Some(table) => format!(
+                        "\nдескрипторы\n{}\n\nидентификаторы{}\n\nчисловые константы{}",
+                        table.descriptors, table.identifiers, table.consts
+                    ),
+                    None => format!("\nошибки\n{}", f.analysis.error),
+                }
+            })
+        })
+    };
+
+    let entries = move |consts: bool| {
+        project.with(|project| {
+            project.as_ref().map(|project| {
+                let entries = if consts {
+                    &project.consts
+                } else {
+                    &project.identifiers
+                };
+                entries
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, entry)| {
+                        let text = format!("{} {}", idx, entry.name);
+                        if consts {
+                            view! { <div>{text}</div> }
+                        } else {
+                            view! {
+                                <div
+                                    class:selected=move || identifier.get() == Some(idx)
+                                    style="cursor: pointer"
+                                    on:click=move |_| set_identifier.set(Some(idx))>
+                                    {text}
+                                </div>
+                            }
+                        }
+                    })
+                    .collect_view()
+            })
+        })
+    };
+
+    let usages = move || {
+        project.with(|project| {
+            let project = project.as_ref()?;
+            let entry = project.identifiers.get(identifier.get()?)?;
+            Some(
+                entry
+                    .usages
+                    .iter()
+                    .map(|usage| {
+                        let (target, target_line) = (usage.file, usage.line);
+                        let text = format!("{}:{}", project.files[target].path, target_line);
+                        view! {
+                            <div
+                                style="cursor: pointer"
+                                on:click=move |_| {
+                                    set_file.set(target);
+                                    set_line.set(Some(target_line));
+                                }>
+                                {text}
+                            </div>
+                        }
+                    })
+                    .collect_view(),
+            )
+        })
+    };
+
+    view! {
+        <form class="row top" on:submit=open>
+            <input
+                id="project-input"
+                placeholder="Enter a project directory or leave empty to browse..."
+                on:input=move |ev| set_path.set(event_target_value(&ev))
+            />
+            <button type="submit">"Open project"</button>
+            <span>{error}</span>
+        </form>
+        <Show when=move || project.with(Option::is_some) fallback=|| ()>
+            <div class="row main">
+                <div class="display_text">
+                    <code>файлы{tree}</code>
+                </div>
+                <div class="display_text" style="width: 30%">
+                    <code>{listing}</code>
+                </div>
+                <div class="display_text">
+                    <code>{file_tables}</code>
+                </div>
+                <div class="display_text">
+                    <code>20 - идентификаторы проекта{move || entries(false)}</code>
+                </div>
+                <div class="display_text">
+                    <code>использования{usages}</code>
+                </div>
+                <div class="display_text">
+                    <code>30 - числовые константы проекта{move || entries(true)}</code>
+                </div>
+            </div>
+        </Show>
+    }
+}
@@ -0,0 +1,164 @@
+use crate::app::{export, invoke};
+use crate::minimize::{MinimizePanel, MinimizeReport};
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::{from_value, to_value};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct NfaReport {
+    pub table: String,
+    pub dot: String,
+    pub svg: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct DfaReport {
+    pub trace: String,
+    pub table: String,
+    pub text: String,
+    pub svg: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RegexArgs<'a> {
+    regex: &'a str,
+}
+
+/// Regular expression to NFA by Thompson's construction and on to a DFA
+#[component]
+pub fn RegexView() -> impl IntoView {
+    let (regex, set_regex) = create_signal(String::new());
+    let (nfa, set_nfa) = create_signal(None::<NfaReport>);
+    let (dfa, set_dfa) = create_signal(None::<DfaReport>);
+    let (lexer, set_lexer) = create_signal(String::new());
+    let (minimal, set_minimal) = create_signal(None::<MinimizeReport>);
+    let (error, set_error) = create_signal(String::new());
+
+    let build = move |_| {
+        spawn_local(async move {
+            let regex = regex.get_untracked();
+            let args = to_value(&RegexArgs { regex: &regex }).unwrap();
+            match invoke("regex_to_nfa", args).await {
+                Ok(value) => {
+                    let value: NfaReport = from_value(value).unwrap();
+                    set_error.set(value.error.clone());
+                    set_nfa.set(Some(value));
+                }
+                Err(e) => set_error.set(e.as_string().unwrap_or_default()),
+            }
+        });
+    };
+
+    let build_dfa = move |_| {
+        spawn_local(async move {
+            let regex = regex.get_untracked();
+            let args = to_value(&RegexArgs { regex: &regex }).unwrap();
+            match invoke("regex_to_dfa", args).await {
+                Ok(value) => {
+                    let value: DfaReport = from_value(value).unwrap();
+                    set_error.set(value.error.clone());
+                    set_dfa.set(Some(value));
+                }
+                Err(e) => set_error.set(e.as_string().unwrap_or_default()),
+            }
+        });
+    };
+
+    let compare = move |_| {
+        spawn_local(async move {
+            let regex = regex.get_untracked();
+            let args = to_value(&RegexArgs { regex: &regex }).unwrap();
+            match invoke("compare_dfa_with_lexer", args).await {
+                Ok(value) => set_lexer.set(from_value(value).unwrap()),
+                Err(e) => set_error.set(e.as_string().unwrap_or_default()),
+            }
+        });
+    };
+
+    let minimize = move |_| {
+        spawn_local(async move {
+            let regex = regex.get_untracked();
+            let args = to_value(&RegexArgs { regex: &regex }).unwrap();
+            match invoke("minimize_regex", args).await {
+                Ok(value) => {
+                    let value: MinimizeReport = from_value(value).unwrap();
+                    set_error.set(value.error.clone());
+                    set_minimal.set(Some(value));
+                }
+                Err(e) => set_error.set(e.as_string().unwrap_or_default()),
+            }
+        });
+    };
+
+    let field = move |field: fn(&NfaReport) -> &String| {
+        move || nfa.with(|r| r.as_ref().map(|r| field(r).clone()))
+    };
+    let export_nfa = move |field: fn(&NfaReport) -> &String, name: &'static str| {
+        if let Some(text) = nfa.with_untracked(|r| r.as_ref().map(|r| field(r).clone())) {
+            export(text, name, set_error);
+        }
+    };
+    let dfa_field = move |field: fn(&DfaReport) -> &String| {
+        move || dfa.with(|r| r.as_ref().map(|r| field(r).clone()))
+    };
+    let export_dfa = move |field: fn(&DfaReport) -> &String, name: &'static str| {
+        if let Some(text) = dfa.with_untracked(|r| r.as_ref().map(|r| field(r).clone())) {
+            export(text, name, set_error);
+        }
+    };
+
+    view! {
+        <div class="row top">
+            <input
+                placeholder="[a-zA-Z_][a-zA-Z0-9_]*"
+                on:input=move |ev| set_regex.set(event_target_value(&ev))
+            />
+            <button type="button" on:click=build>"NFA"</button>
+            <button type="button" on:click=move |_| export_nfa(|r| &r.dot, "nfa.dot")>
+                "Export NFA (DOT)"
+            </button>
+            <button type="button" on:click=move |_| export_nfa(|r| &r.svg, "nfa.svg")>
+                "Export NFA (SVG)"
+            </button>
+            <button type="button" on:click=build_dfa>"DFA"</button>
+            <button type="button" on:click=move |_| export_dfa(|r| &r.table, "dfa_table.txt")>
+                "Export DFA table"
+            </button>
+            <button type="button" on:click=move |_| export_dfa(|r| &r.text, "dfa.txt")>
+                "Export DFA transitions"
+            </button>
+            <button type="button" on:click=compare>"Compare DFA with lexer"</button>
+            <button type="button" on:click=minimize>"Minimise DFA"</button>
+            <span>"регулярное выражение "{error}</span>
+        </div>
+        <div class="row main">
+            <div class="display_text" style="width: 30%">
+                <code>НКА{field(|r| &r.table)}</code>
+            </div>
+            <div
+                class="display_text cfg"
+                inner_html=move || nfa.with(|r| r.as_ref().map_or(String::new(), |r| r.svg.clone()))>
+            </div>
+        </div>
+        <div class="row main">
+            <div class="display_text" style="width: 40%">
+                <code>построение ДКА{dfa_field(|r| &r.trace)}</code>
+            </div>
+            <div class="display_text" style="width: 60%">
+                <code>
+                    "ДКА"{dfa_field(|r| &r.table)}"\n"
+                    "сверка с лексером"{lexer}
+                </code>
+            </div>
+        </div>
+        <div class="row main">
+            <div
+                class="display_text cfg"
+                inner_html=move || dfa.with(|r| r.as_ref().map_or(String::new(), |r| r.svg.clone()))>
+            </div>
+        </div>
+        <MinimizePanel report=minimal set_error=set_error/>
+    }
+}
@@ -0,0 +1,128 @@
+use crate::app::{export, invoke, TextArgs};
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::{from_value, to_value};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SimulationStep {
+    pub current: String,
+    pub at: String,
+    pub from: String,
+    pub to: String,
+    pub buffer: String,
+    pub reread: bool,
+    pub flushed: String,
+    pub tokens: String,
+    pub descriptors: String,
+    pub svg: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SimulationReport {
+    pub steps: Vec<SimulationStep>,
+    pub trace: String,
+    pub error: String,
+}
+
+/// Steps through the lexer automaton one character of the input at a time
+#[component]
+pub fn SimulatorView() -> impl IntoView {
+    let (text, set_text) = create_signal(String::new());
+    let (report, set_report) = create_signal(None::<SimulationReport>);
+    let (position, set_position) = create_signal(0usize);
+    let (error, set_error) = create_signal(String::new());
+
+    let simulate = move |_| {
+        spawn_local(async move {
+            let text = text.get_untracked();
+            let args = to_value(&TextArgs { text: &text }).unwrap();
+            match invoke("simulate_lexer", args).await {
+                Ok(value) => {
+                    let value: SimulationReport = from_value(value).unwrap();
+                    set_error.set(value.error.clone());
+                    set_position.set(0);
+                    set_report.set(Some(value));
+                }
+                Err(e) => set_error.set(e.as_string().unwrap_or_default()),
+            }
+        });
+    };
+
+    let count = move || report.with(|r| r.as_ref().map_or(0, |r| r.steps.len()));
+    let step = move |field: fn(&SimulationStep) -> String| {
+        move || {
+            report.with(|r| {
+                r.as_ref()
+                    .and_then(|r| r.steps.get(position.get()))
+                    .map_or(String::new(), field)
+            })
+        }
+    };
+    let progress = move || match count() {
+        0 => String::new(),
+        count => format!("шаг {} из {}", position.get() + 1, count),
+    };
+    let save_trace = move |_| {
+        if let Some(trace) = report.with_untracked(|r| r.as_ref().map(|r| r.trace.clone())) {
+            export(trace, "simulation.txt", set_error);
+        }
+    };
+
+    view! {
+        <div class="row top">
+            <button type="button" on:click=simulate>"Simulate"</button>
+            <button type="button" on:click=move |_| set_position.set(0)>"Reset"</button>
+            <button type="button" on:click=move |_| set_position.update(|p| *p = p.saturating_sub(1))>
+                "Back"
+            </button>
+            <button
+                type="button"
+                on:click=move |_| set_position.update(|p| *p = (*p + 1).min(count().saturating_sub(1)))>
+                "Step"
+            </button>
+            <button type="button" on:click=move |_| set_position.set(count().saturating_sub(1))>
+                "Run to end"
+            </button>
+            <button type="button" on:click=save_trace>"Export trace"</button>
+            <span>"симуляция лексера "{progress}" "{error}</span>
+        </div>
+        <div class="row main">
+            <textarea
+                class="display_text"
+                style="width: 40%"
+                placeholder="int a = 5;"
+                prop:value=move || text.get()
+                on:input=move |ev| set_text.set(event_target_value(&ev))>
+            </textarea>
+            <div class="display_text" style="width: 60%">
+                <code>
+                    "символ: "{step(|s| s.current.clone())}
+                    {step(|s| if s.reread { " (читается повторно)".to_owned() } else { String::new() })}
+                    " в "{step(|s| s.at.clone())}"\n"
+                    "переход: "{step(|s| s.from.clone())}" → "{step(|s| s.to.clone())}"\n"
+                    "буфер: "{step(|s| format!("{:?}", s.buffer))}"\n"
+                    "is_writable: "{step(|s| s.flushed.clone())}
+                </code>
+            </div>
+        </div>
+        <div class="row main">
+            <div
+                class="display_text cfg"
+                inner_html=move || step(|s| s.svg.clone())()>
+            </div>
+        </div>
+        <div class="row main">
+            <div class="display_text">
+                <code>лексемы{step(|s| s.tokens.clone())}</code>
+            </div>
+            <div class="display_text">
+                <code>дескрипторы{step(|s| s.descriptors.clone())}</code>
+            </div>
+            <div class="display_text">
+                <code>
+                    "трасса"{move || report.with(|r| r.as_ref().map(|r| r.trace.clone()))}
+                </code>
+            </div>
+        </div>
+    }
+}
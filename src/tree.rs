@@ -0,0 +1,32 @@
+use leptos::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Node {
+    pub label: String,
+    pub children: Vec<Node>,
+}
+
+fn node_view(node: Node) -> View {
+    if node.children.is_empty() {
+        return view! { <div class="tree-leaf">{node.label}</div> }.into_view();
+    }
+    view! {
+        <details class="tree-node" open=true>
+            <summary>{node.label}</summary>
+            {node.children.into_iter().map(node_view).collect_view()}
+        </details>
+    }
+    .into_view()
+}
+
+/// Collapsible syntax tree
+#[component]
+pub fn TreeView(tree: ReadSignal<Option<Node>>) -> impl IntoView {
+    view! {
+        <div class="display_text" style="width: 90%">
+            <code>синтаксическое дерево</code>
+            {move || tree.get().map(node_view)}
+        </div>
+    }
+}
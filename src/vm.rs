@@ -0,0 +1,103 @@
+use crate::app::{invoke, TextArgs};
+use leptos::*;
+use serde::{Deserialize, Serialize};
+use serde_wasm_bindgen::{from_value, to_value};
+use wasm_bindgen::JsValue;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct State {
+    pub pc: Option<usize>,
+    pub listing: String,
+    pub stack: String,
+    pub variables: String,
+    pub console: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RunArgs {
+    breakpoints: Vec<usize>,
+}
+
+/// Addresses separated by spaces or commas, anything else is ignored
+fn parse_breakpoints(text: &str) -> Vec<usize> {
+    text.split(|c: char| c == ',' || c.is_whitespace())
+        .filter_map(|x| x.parse().ok())
+        .collect()
+}
+
+/// Stack machine running the reverse Polish notation of the program
+#[component]
+pub fn VmView(source: ReadSignal<String>) -> impl IntoView {
+    let (state, set_state) = create_signal(None::<State>);
+    let (breakpoints, set_breakpoints) = create_signal(String::new());
+    let (error, set_error) = create_signal(String::new());
+
+    let call = move |cmd: &'static str, args: JsValue| {
+        spawn_local(async move {
+            match invoke(cmd, args).await {
+                Ok(result) => {
+                    let result: State = from_value(result).unwrap();
+                    set_error.set(result.error.clone());
+                    set_state.set(Some(result));
+                }
+                Err(e) => set_error.set(e.as_string().unwrap_or_default()),
+            }
+        });
+    };
+
+    let load = move |_| {
+        let text = source.get_untracked();
+        call("vm_load", to_value(&TextArgs { text: &text }).unwrap());
+    };
+    let step = move |_| call("vm_step", JsValue::UNDEFINED);
+    let run = move |_| {
+        let breakpoints = parse_breakpoints(&breakpoints.get_untracked());
+        call("vm_run", to_value(&RunArgs { breakpoints }).unwrap());
+    };
+    // The pending run answers with the state it stopped in
+    let stop = move |_| {
+        spawn_local(async move {
+            let _ = invoke("vm_stop", JsValue::UNDEFINED).await;
+        });
+    };
+
+    let field = move |field: fn(&State) -> &String| {
+        move || state.with(|s| s.as_ref().map(|s| field(s).clone()))
+    };
+    let pc = move || {
+        state.with(|s| match s.as_ref().map(|s| s.pc) {
+            Some(Some(pc)) => format!(" {}", pc),
+            Some(None) => " остановлена".to_owned(),
+            None => String::new(),
+        })
+    };
+
+    view! {
+        <div class="row top">
+            <button type="button" on:click=load>"Load"</button>
+            <button type="button" on:click=step>"Step"</button>
+            <button type="button" on:click=run>"Run"</button>
+            <button type="button" on:click=stop>"Stop"</button>
+            <input
+                placeholder="Breakpoints, e.g. 12, 30"
+                on:input=move |ev| set_breakpoints.set(event_target_value(&ev))
+            />
+            <span>"машина"{pc}" "{error}</span>
+        </div>
+        <div class="row main">
+            <div class="display_text">
+                <code>ПОЛИЗ{field(|s| &s.listing)}</code>
+            </div>
+            <div class="display_text">
+                <code>стек{field(|s| &s.stack)}</code>
+            </div>
+            <div class="display_text">
+                <code>переменные{field(|s| &s.variables)}</code>
+            </div>
+            <div class="display_text">
+                <code>"консоль\n"{field(|s| &s.console)}</code>
+            </div>
+        </div>
+    }
+}